@@ -0,0 +1,104 @@
+//! Measures writing the assembly of a large function to its destination as
+//! it's generated, the time and the most bytes allocated at once. Run with
+//! `cargo bench --bench emission`.
+
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    fmt::Write as _,
+    io::{self, BufWriter},
+    sync::atomic::{AtomicUsize, Ordering},
+    time::Instant,
+};
+
+use comp::{
+    asm_ast,
+    code_emission::{self, Options},
+    code_gen,
+    irc_gen::IrcGenerator,
+    label_resolution::LabelResolver,
+    lexer::Lexer,
+    parser::Parser,
+    register_allocation,
+    var_resolution::VarResolver,
+};
+
+const STATEMENTS: usize = 20_000;
+const RUNS: u32 = 10;
+
+/// Keeps track of the most bytes allocated at once.
+struct PeakAllocator;
+
+static ALLOCATED: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for PeakAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let allocated = ALLOCATED.fetch_add(layout.size(), Ordering::Relaxed) + layout.size();
+        PEAK.fetch_max(allocated, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        ALLOCATED.fetch_sub(layout.size(), Ordering::Relaxed);
+        System.dealloc(ptr, layout);
+    }
+}
+
+#[global_allocator]
+static GLOBAL: PeakAllocator = PeakAllocator;
+
+fn main() {
+    let source = generate();
+    let mut lexer = Lexer::new(&source);
+    lexer.tokenize().expect("generated source lexes");
+    let program = compile(&lexer);
+    let options = Options::default();
+    measure("emission", || {
+        code_emission::emit_program(BufWriter::new(io::sink()), &program, options)
+    });
+}
+
+fn measure(name: &str, mut emit: impl FnMut() -> io::Result<()>) {
+    let before = ALLOCATED.load(Ordering::Relaxed);
+    PEAK.store(before, Ordering::Relaxed);
+    let start = Instant::now();
+    for _ in 0..RUNS {
+        emit().expect("writing to a sink doesn't fail");
+    }
+    let elapsed = start.elapsed() / RUNS;
+    let peak = PEAK.load(Ordering::Relaxed) - before;
+    println!("{name:>10}: {elapsed:>10.2?} per run, {peak:>9} bytes allocated at peak");
+}
+
+fn generate() -> String {
+    let mut source = String::from("int main(void) {\n    int a = 1;\n    int b = 2;\n");
+    for index in 0..STATEMENTS {
+        let _ = match index % 4 {
+            0 => writeln!(source, "    a = a + b * {index};"),
+            1 => writeln!(source, "    b = (a ^ b) / {};", index % 7 + 1),
+            2 => writeln!(source, "    if (a > b) a = a - b; else b = b << 1;"),
+            _ => writeln!(source, "    a = a < {index} ? b : a % 13;"),
+        };
+    }
+    source.push_str("    return a;\n}\n");
+    source
+}
+
+/// Runs the pipeline up to emission.
+fn compile<'a>(lexer: &'a Lexer) -> asm_ast::Program<'a> {
+    let mut ast = Parser::new(lexer).parse().expect("generated source parses");
+    let mut var_resolver = VarResolver::new(0);
+    var_resolver
+        .resolve_program(&mut ast)
+        .expect("generated source resolves");
+    let mut label_resolver = LabelResolver::new(var_resolver.counter);
+    label_resolver
+        .resolve_program(&mut ast)
+        .expect("generated source resolves");
+    let irc = IrcGenerator::gen_program(ast, label_resolver.counter, false, false);
+    let mut program = code_gen::gen_program(irc, false);
+    register_allocation::allocate_registers(&mut program);
+    let stack_allocation = code_gen::replace_pseudo(&mut program);
+    code_gen::fix_instructions(&mut program, stack_allocation);
+    program
+}
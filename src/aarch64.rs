@@ -0,0 +1,735 @@
+use std::{
+    collections::HashMap,
+    io::{self, Write},
+};
+
+use crate::{
+    code_emission, constant_folding, irc,
+    pass_manager::PassManager,
+    target::{Abi, Assembly, DataLayout, RegisterInfo, Target},
+};
+
+#[derive(Debug, Clone)]
+pub enum Program<'a> {
+    Function(Function<'a>),
+}
+
+#[derive(Debug, Clone)]
+pub struct Function<'a> {
+    pub name: &'a str,
+    pub instructons: Vec<Instruction>,
+    /// Bytes of stack below the frame record for the variables, a multiple
+    /// of 16.
+    pub frame_size: usize,
+}
+
+/// A 32 bit general purpose register, `w0` to `w30`.
+pub type Register = u8;
+
+/// Registers used while lowering, all of them temporaries in AAPCS64.
+const SRC1: Register = 9;
+const SRC2: Register = 10;
+const DST: Register = 11;
+const QUOTIENT: Register = 12;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Operand {
+    Register(Register),
+    /// Small enough for the instruction's immediate field.
+    Imm(i32),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UnaryOp {
+    Neg,
+    Mvn,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BinaryOp {
+    Add,
+    Sub,
+    Mul,
+    Sdiv,
+    And,
+    Orr,
+    Eor,
+    Lsl,
+    Asr,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CondCode {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Instruction {
+    Mov {
+        dst: Register,
+        value: i32,
+    },
+    /// Loads the variable `slot` bytes above the stack pointer.
+    Load {
+        dst: Register,
+        slot: usize,
+    },
+    Store {
+        src: Register,
+        slot: usize,
+    },
+    Unary {
+        operator: UnaryOp,
+        src: Register,
+        dst: Register,
+    },
+    Binary {
+        operator: BinaryOp,
+        src1: Register,
+        src2: Operand,
+        dst: Register,
+    },
+    /// `dst = minuend - src1 * src2`.
+    Msub {
+        src1: Register,
+        src2: Register,
+        minuend: Register,
+        dst: Register,
+    },
+    Cmp {
+        src1: Register,
+        src2: Operand,
+    },
+    Cset {
+        cond_code: CondCode,
+        dst: Register,
+    },
+    /// `dst = cond_code ? src1 : src2`.
+    Csel {
+        cond_code: CondCode,
+        src1: Register,
+        src2: Register,
+        dst: Register,
+    },
+    B(String),
+    BCond {
+        cond_code: CondCode,
+        target: String,
+    },
+    Cbz {
+        src: Register,
+        target: String,
+    },
+    Cbnz {
+        src: Register,
+        target: String,
+    },
+    Label(String),
+    /// Returns the value in `w0`, tearing down the frame.
+    Ret,
+}
+
+impl CondCode {
+    fn negate(self) -> Self {
+        match self {
+            Self::Eq => Self::Ne,
+            Self::Ne => Self::Eq,
+            Self::Lt => Self::Ge,
+            Self::Le => Self::Gt,
+            Self::Gt => Self::Le,
+            Self::Ge => Self::Lt,
+        }
+    }
+
+    fn from_operator(operator: irc::BinaryOp) -> Option<Self> {
+        match operator {
+            irc::BinaryOp::Equal => Some(Self::Eq),
+            irc::BinaryOp::NotEqual => Some(Self::Ne),
+            irc::BinaryOp::LessThan => Some(Self::Lt),
+            irc::BinaryOp::LessOrEqual => Some(Self::Le),
+            irc::BinaryOp::GreaterThan => Some(Self::Gt),
+            irc::BinaryOp::GreaterOrEqual => Some(Self::Ge),
+            _ => None,
+        }
+    }
+}
+
+/// AArch64 Linux with the AAPCS64 ABI.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Aarch64;
+
+impl Target for Aarch64 {
+    fn name(&self) -> &'static str {
+        "aarch64-linux-gnu"
+    }
+
+    fn architecture(&self) -> &'static str {
+        "aarch64"
+    }
+
+    fn data_layout(&self) -> DataLayout {
+        DataLayout {
+            int_size: 4,
+            pointer_size: 8,
+            stack_alignment: 16,
+        }
+    }
+
+    fn abi(&self) -> Abi {
+        Abi {
+            return_register: Some("w0"),
+            argument_registers: &["x0", "x1", "x2", "x3", "x4", "x5", "x6", "x7"],
+            callee_saved: &[
+                "x19", "x20", "x21", "x22", "x23", "x24", "x25", "x26", "x27", "x28", "x29",
+            ],
+            red_zone: 0,
+        }
+    }
+
+    fn registers(&self) -> RegisterInfo {
+        RegisterInfo {
+            allocatable: &[],
+            scratch: &["x9", "x10", "x11", "x12", "x16"],
+            stack_pointer: Some("sp"),
+            frame_pointer: Some("x29"),
+        }
+    }
+
+    fn gcc(&self) -> Option<&'static str> {
+        Some("aarch64-linux-gnu-gcc")
+    }
+
+    fn select_instructions<'a>(
+        &self,
+        program: irc::Program<'a>,
+        _pass_manager: &mut PassManager,
+        _verbose: bool,
+    ) -> Box<dyn Assembly + 'a> {
+        Box::new(gen_program(program))
+    }
+}
+
+impl Assembly for Program<'_> {
+    fn emit(&self, out: &mut dyn Write, _options: code_emission::Options) -> io::Result<()> {
+        emit_program(out, self)
+    }
+}
+
+pub fn gen_program(program: irc::Program) -> Program {
+    match program {
+        irc::Program::Function(function) => Program::Function(gen_function(function)),
+    }
+}
+
+/// Every variable lives in a stack slot, instructions load their operands
+/// into scratch registers and store the result back.
+fn gen_function(function: irc::Function) -> Function {
+    let mut uses: HashMap<&str, usize> = HashMap::new();
+    for ins in &function.instructons {
+        for src in ins.sources() {
+            if let irc::Value::Var(name) = src {
+                *uses.entry(name.as_str()).or_default() += 1;
+            }
+        }
+    }
+    let layout = Aarch64.data_layout();
+    let mut generator = Generator::default();
+    let mut index = 0;
+    while let Some(ins) = function.instructons.get(index) {
+        let fused = function
+            .instructons
+            .get(index + 1)
+            .is_some_and(|user| generator.gen_fused_branch(ins, user, &uses));
+        if !fused {
+            generator.gen_instruction(ins);
+        }
+        index += if fused { 2 } else { 1 };
+    }
+    Function {
+        name: function.name,
+        frame_size: (generator.slots.len() * layout.int_size)
+            .next_multiple_of(layout.stack_alignment),
+        instructons: generator.instructions,
+    }
+}
+
+#[derive(Default)]
+struct Generator {
+    instructions: Vec<Instruction>,
+    slots: HashMap<String, usize>,
+}
+
+impl Generator {
+    fn slot(&mut self, name: &str) -> usize {
+        let next = self.slots.len() * Aarch64.data_layout().int_size;
+        *self.slots.entry(name.to_string()).or_insert(next)
+    }
+
+    fn load(&mut self, value: &irc::Value, dst: Register) {
+        let ins = match value {
+            irc::Value::Constant(value) => Instruction::Mov { dst, value: *value },
+            irc::Value::Var(name) => Instruction::Load {
+                dst,
+                slot: self.slot(name),
+            },
+        };
+        self.instructions.push(ins);
+    }
+
+    /// Uses the constant directly if it's in `range`.
+    fn operand(
+        &mut self,
+        value: &irc::Value,
+        dst: Register,
+        range: std::ops::RangeInclusive<i32>,
+    ) -> Operand {
+        match value {
+            irc::Value::Constant(value) if range.contains(value) => Operand::Imm(*value),
+            value => {
+                self.load(value, dst);
+                Operand::Register(dst)
+            }
+        }
+    }
+
+    fn store(&mut self, dst: &str) {
+        let slot = self.slot(dst);
+        self.instructions
+            .push(Instruction::Store { src: DST, slot });
+    }
+
+    fn compare(&mut self, src1: &irc::Value, src2: &irc::Value) {
+        self.load(src1, SRC1);
+        let src2 = self.operand(src2, SRC2, 0..=4095);
+        self.instructions
+            .push(Instruction::Cmp { src1: SRC1, src2 });
+    }
+
+    /// `cmp` and `b.cond` for a comparison only used by the jump after it.
+    fn gen_fused_branch(
+        &mut self,
+        instruction: &irc::Instruction,
+        user: &irc::Instruction,
+        uses: &HashMap<&str, usize>,
+    ) -> bool {
+        let irc::Instruction::Binary {
+            operator,
+            src1,
+            src2,
+            dst,
+        } = instruction
+        else {
+            return false;
+        };
+        let Some(cond_code) = CondCode::from_operator(*operator) else {
+            return false;
+        };
+        let (cond_code, condition, target) = match user {
+            irc::Instruction::JumpIfZero { condition, target } => {
+                (cond_code.negate(), condition, target)
+            }
+            irc::Instruction::JumpIfNotZero { condition, target } => (cond_code, condition, target),
+            _ => return false,
+        };
+        if *condition != irc::Value::Var(dst.clone()) || uses.get(dst.as_str()) != Some(&1) {
+            return false;
+        }
+        self.compare(src1, src2);
+        self.instructions.push(Instruction::BCond {
+            cond_code,
+            target: target.clone(),
+        });
+        true
+    }
+
+    fn gen_instruction(&mut self, instruction: &irc::Instruction) {
+        match instruction {
+            irc::Instruction::Ret(value) => {
+                self.load(value, 0);
+                self.instructions.push(Instruction::Ret);
+            }
+            irc::Instruction::Unary {
+                operator: irc::UnaryOp::Not,
+                src,
+                dst,
+            } => {
+                self.compare(src, &irc::Value::Constant(0));
+                self.instructions.push(Instruction::Cset {
+                    cond_code: CondCode::Eq,
+                    dst: DST,
+                });
+                self.store(dst);
+            }
+            irc::Instruction::Unary { operator, src, dst } => {
+                self.load(src, SRC1);
+                let operator = match operator {
+                    irc::UnaryOp::Negate => UnaryOp::Neg,
+                    irc::UnaryOp::Complement => UnaryOp::Mvn,
+                    irc::UnaryOp::Not => unreachable!("`!` is a comparison"),
+                };
+                self.instructions.push(Instruction::Unary {
+                    operator,
+                    src: SRC1,
+                    dst: DST,
+                });
+                self.store(dst);
+            }
+            irc::Instruction::Binary {
+                operator,
+                src1,
+                src2,
+                dst,
+            } => {
+                self.gen_binary(*operator, src1, src2);
+                self.store(dst);
+            }
+            irc::Instruction::Copy { src, dst } => {
+                self.load(src, DST);
+                self.store(dst);
+            }
+            irc::Instruction::Select {
+                condition,
+                src1,
+                src2,
+                dst,
+            } => {
+                self.compare(condition, &irc::Value::Constant(0));
+                self.load(src1, SRC1);
+                self.load(src2, SRC2);
+                self.instructions.push(Instruction::Csel {
+                    cond_code: CondCode::Ne,
+                    src1: SRC1,
+                    src2: SRC2,
+                    dst: DST,
+                });
+                self.store(dst);
+            }
+            irc::Instruction::Jump { target } => {
+                self.instructions.push(Instruction::B(target.clone()));
+            }
+            irc::Instruction::JumpIfZero { condition, target } => {
+                self.load(condition, SRC1);
+                self.instructions.push(Instruction::Cbz {
+                    src: SRC1,
+                    target: target.clone(),
+                });
+            }
+            irc::Instruction::JumpIfNotZero { condition, target } => {
+                self.load(condition, SRC1);
+                self.instructions.push(Instruction::Cbnz {
+                    src: SRC1,
+                    target: target.clone(),
+                });
+            }
+            irc::Instruction::Label(label) => {
+                self.instructions.push(Instruction::Label(label.clone()));
+            }
+            irc::Instruction::Line(_) | irc::Instruction::Variable { .. } => {}
+        }
+    }
+
+    /// Computes `src1 operator src2` into `DST`.
+    fn gen_binary(&mut self, operator: irc::BinaryOp, src1: &irc::Value, src2: &irc::Value) {
+        if let (irc::Value::Constant(src1), irc::Value::Constant(src2)) = (src1, src2) {
+            if let Some(value) = constant_folding::fold_binary(operator, *src1, *src2) {
+                self.instructions.push(Instruction::Mov { dst: DST, value });
+                return;
+            }
+        }
+        if let Some(cond_code) = CondCode::from_operator(operator) {
+            self.compare(src1, src2);
+            self.instructions.push(Instruction::Cset {
+                cond_code,
+                dst: DST,
+            });
+            return;
+        }
+        self.load(src1, SRC1);
+        let (operator, src2) = match (operator, src2) {
+            // Negative constants fit the immediate of the opposite instruction.
+            (irc::BinaryOp::Add, irc::Value::Constant(value)) if (-4095..0).contains(value) => {
+                (BinaryOp::Sub, Operand::Imm(-value))
+            }
+            (irc::BinaryOp::Subtract, irc::Value::Constant(value))
+                if (-4095..0).contains(value) =>
+            {
+                (BinaryOp::Add, Operand::Imm(-value))
+            }
+            (irc::BinaryOp::Add, _) => (BinaryOp::Add, self.operand(src2, SRC2, 0..=4095)),
+            (irc::BinaryOp::Subtract, _) => (BinaryOp::Sub, self.operand(src2, SRC2, 0..=4095)),
+            (irc::BinaryOp::LeftShift, _) => (BinaryOp::Lsl, self.operand(src2, SRC2, 0..=31)),
+            (irc::BinaryOp::RightShift, _) => (BinaryOp::Asr, self.operand(src2, SRC2, 0..=31)),
+            (irc::BinaryOp::Remainder, _) => {
+                self.load(src2, SRC2);
+                self.instructions.push(Instruction::Binary {
+                    operator: BinaryOp::Sdiv,
+                    src1: SRC1,
+                    src2: Operand::Register(SRC2),
+                    dst: QUOTIENT,
+                });
+                self.instructions.push(Instruction::Msub {
+                    src1: QUOTIENT,
+                    src2: SRC2,
+                    minuend: SRC1,
+                    dst: DST,
+                });
+                return;
+            }
+            (operator, _) => {
+                let operator = match operator {
+                    irc::BinaryOp::Multiply => BinaryOp::Mul,
+                    irc::BinaryOp::Divide => BinaryOp::Sdiv,
+                    irc::BinaryOp::BitAnd => BinaryOp::And,
+                    irc::BinaryOp::BitOr => BinaryOp::Orr,
+                    irc::BinaryOp::Xor => BinaryOp::Eor,
+                    _ => unreachable!("{operator:?} is handled above"),
+                };
+                self.load(src2, SRC2);
+                (operator, Operand::Register(SRC2))
+            }
+        };
+        self.instructions.push(Instruction::Binary {
+            operator,
+            src1: SRC1,
+            src2,
+            dst: DST,
+        });
+    }
+}
+
+/// Writes the program as GNU assembler source.
+pub fn emit_program(mut out: impl Write, program: &Program) -> io::Result<()> {
+    match program {
+        Program::Function(function) => emit_function(&mut out, function)?,
+    }
+    writeln!(out, "\n.section .note.GNU-stack,\"\",@progbits")?;
+    out.flush()
+}
+
+fn emit_function(out: &mut impl Write, function: &Function) -> io::Result<()> {
+    let name = function.name;
+    writeln!(out, "    .global {name}")?;
+    writeln!(out, "    .type {name}, %function")?;
+    writeln!(out, "    .p2align 2")?;
+    writeln!(out, "{name}:")?;
+    // The frame record, then the variables below it.
+    writeln!(out, "    stp x29, x30, [sp, #-16]!")?;
+    writeln!(out, "    mov x29, sp")?;
+    match function.frame_size {
+        0 => {}
+        bytes @ ..4096 => writeln!(out, "    sub sp, sp, #{bytes}")?,
+        bytes => {
+            emit_mov(out, "x16", bytes as i64)?;
+            writeln!(out, "    sub sp, sp, x16")?;
+        }
+    }
+    let long = relax(function);
+    for (index, (ins, long)) in function.instructons.iter().zip(long).enumerate() {
+        if long {
+            emit_long_branch(out, ins, index)?;
+        } else {
+            emit_instruction(out, ins)?;
+        }
+    }
+    writeln!(out, "    .size {name}, .-{name}")
+}
+
+/// Upper bound of the bytes the instruction takes.
+fn size(instruction: &Instruction, long: bool) -> i64 {
+    match instruction {
+        Instruction::Label(_) => 0,
+        Instruction::Mov { .. } => 8,
+        Instruction::Load { slot, .. } | Instruction::Store { slot, .. } if *slot > 16380 => 20,
+        Instruction::BCond { .. } | Instruction::Cbz { .. } | Instruction::Cbnz { .. } if long => 8,
+        Instruction::Ret => 12,
+        _ => 4,
+    }
+}
+
+/// Conditional branches reach 1 MiB, the ones that don't are inverted to
+/// skip a `b`. Branches start short and only ever grow, so this settles.
+fn relax(function: &Function) -> Vec<bool> {
+    let instructions = &function.instructons;
+    let mut long = vec![false; instructions.len()];
+    loop {
+        let mut offsets = Vec::with_capacity(instructions.len());
+        let mut labels = HashMap::new();
+        let mut offset = 0;
+        for (ins, &long) in instructions.iter().zip(&long) {
+            offsets.push(offset);
+            if let Instruction::Label(label) = ins {
+                labels.insert(label.as_str(), offset);
+            }
+            offset += size(ins, long);
+        }
+        let mut changed = false;
+        for (index, ins) in instructions.iter().enumerate() {
+            let (Instruction::BCond { target, .. }
+            | Instruction::Cbz { target, .. }
+            | Instruction::Cbnz { target, .. }) = ins
+            else {
+                continue;
+            };
+            let distance = labels[target.as_str()] - offsets[index];
+            if !long[index] && !(-(1 << 20)..1 << 20).contains(&distance) {
+                long[index] = true;
+                changed = true;
+            }
+        }
+        if !changed {
+            return long;
+        }
+    }
+}
+
+fn emit_long_branch(
+    out: &mut impl Write,
+    instruction: &Instruction,
+    index: usize,
+) -> io::Result<()> {
+    let skip = format!(".L.branch.{index}");
+    let target = match instruction {
+        Instruction::BCond { cond_code, target } => {
+            writeln!(out, "    b.{} {skip}", emit_cond_code(cond_code.negate()))?;
+            target
+        }
+        Instruction::Cbz { src, target } => {
+            writeln!(out, "    cbnz w{src}, {skip}")?;
+            target
+        }
+        Instruction::Cbnz { src, target } => {
+            writeln!(out, "    cbz w{src}, {skip}")?;
+            target
+        }
+        _ => unreachable!("only conditional branches are relaxed"),
+    };
+    writeln!(out, "    b .L{target}")?;
+    writeln!(out, "{skip}:")
+}
+
+fn emit_instruction(out: &mut impl Write, instruction: &Instruction) -> io::Result<()> {
+    match instruction {
+        Instruction::Mov { dst, value } => emit_mov(out, &format!("w{dst}"), i64::from(*value)),
+        Instruction::Load { dst, slot } => emit_memory(out, "ldr", *dst, *slot),
+        Instruction::Store { src, slot } => emit_memory(out, "str", *src, *slot),
+        Instruction::Unary { operator, src, dst } => {
+            let mnemonic = match operator {
+                UnaryOp::Neg => "neg",
+                UnaryOp::Mvn => "mvn",
+            };
+            writeln!(out, "    {mnemonic} w{dst}, w{src}")
+        }
+        Instruction::Binary {
+            operator,
+            src1,
+            src2,
+            dst,
+        } => {
+            let mnemonic = match operator {
+                BinaryOp::Add => "add",
+                BinaryOp::Sub => "sub",
+                BinaryOp::Mul => "mul",
+                BinaryOp::Sdiv => "sdiv",
+                BinaryOp::And => "and",
+                BinaryOp::Orr => "orr",
+                BinaryOp::Eor => "eor",
+                BinaryOp::Lsl => "lsl",
+                BinaryOp::Asr => "asr",
+            };
+            writeln!(
+                out,
+                "    {mnemonic} w{dst}, w{src1}, {}",
+                emit_operand(src2)
+            )
+        }
+        Instruction::Msub {
+            src1,
+            src2,
+            minuend,
+            dst,
+        } => writeln!(out, "    msub w{dst}, w{src1}, w{src2}, w{minuend}"),
+        Instruction::Cmp { src1, src2 } => writeln!(out, "    cmp w{src1}, {}", emit_operand(src2)),
+        Instruction::Cset { cond_code, dst } => {
+            writeln!(out, "    cset w{dst}, {}", emit_cond_code(*cond_code))
+        }
+        Instruction::Csel {
+            cond_code,
+            src1,
+            src2,
+            dst,
+        } => writeln!(
+            out,
+            "    csel w{dst}, w{src1}, w{src2}, {}",
+            emit_cond_code(*cond_code)
+        ),
+        Instruction::B(target) => writeln!(out, "    b .L{target}"),
+        Instruction::BCond { cond_code, target } => {
+            writeln!(out, "    b.{} .L{target}", emit_cond_code(*cond_code))
+        }
+        Instruction::Cbz { src, target } => writeln!(out, "    cbz w{src}, .L{target}"),
+        Instruction::Cbnz { src, target } => writeln!(out, "    cbnz w{src}, .L{target}"),
+        Instruction::Label(label) => writeln!(out, ".L{label}:"),
+        Instruction::Ret => {
+            writeln!(out, "    mov sp, x29")?;
+            writeln!(out, "    ldp x29, x30, [sp], #16")?;
+            writeln!(out, "    ret")
+        }
+    }
+}
+
+fn emit_operand(operand: &Operand) -> String {
+    match operand {
+        Operand::Register(register) => format!("w{register}"),
+        Operand::Imm(value) => format!("#{value}"),
+    }
+}
+
+fn emit_cond_code(cond_code: CondCode) -> &'static str {
+    match cond_code {
+        CondCode::Eq => "eq",
+        CondCode::Ne => "ne",
+        CondCode::Lt => "lt",
+        CondCode::Le => "le",
+        CondCode::Gt => "gt",
+        CondCode::Ge => "ge",
+    }
+}
+
+/// `mov` only takes values that fit in 16 bits, possibly inverted, anything
+/// else is built with `movz` and `movk`.
+fn emit_mov(out: &mut impl Write, dst: &str, value: i64) -> io::Result<()> {
+    if (-0x10000..0x10000).contains(&value) {
+        return writeln!(out, "    mov {dst}, #{value}");
+    }
+    let bits = value as u64;
+    writeln!(out, "    movz {dst}, #{}", bits & 0xffff)?;
+    let halves = if dst.starts_with('w') { 2 } else { 4 };
+    for half in 1..halves {
+        let bits = (bits >> (16 * half)) & 0xffff;
+        if bits != 0 {
+            writeln!(out, "    movk {dst}, #{bits}, lsl #{}", 16 * half)?;
+        }
+    }
+    Ok(())
+}
+
+/// The scaled immediate offset reaches 16380 bytes, further slots go
+/// through `x16`.
+fn emit_memory(
+    out: &mut impl Write,
+    mnemonic: &str,
+    register: Register,
+    slot: usize,
+) -> io::Result<()> {
+    if slot <= 16380 {
+        writeln!(out, "    {mnemonic} w{register}, [sp, #{slot}]")
+    } else {
+        emit_mov(out, "x16", slot as i64)?;
+        writeln!(out, "    {mnemonic} w{register}, [sp, x16]")
+    }
+}
@@ -1,24 +1,58 @@
-#[derive(Debug)]
+use std::iter;
+
+#[derive(Debug, Clone)]
 pub enum Program<'a> {
     Function(Function<'a>),
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum Register {
     AX,
-    DX,
+    BX,
     CX,
+    DX,
+    SI,
+    DI,
+    SP,
+    BP,
+    R8,
+    R9,
     R10,
     R11,
+    R12,
+    R13,
+    R14,
+    R15,
 }
 
-#[derive(Debug, Clone, Copy)]
+impl Register {
+    pub const ALL: [Self; 16] = [
+        Self::AX,
+        Self::BX,
+        Self::CX,
+        Self::DX,
+        Self::SI,
+        Self::DI,
+        Self::SP,
+        Self::BP,
+        Self::R8,
+        Self::R9,
+        Self::R10,
+        Self::R11,
+        Self::R12,
+        Self::R13,
+        Self::R14,
+        Self::R15,
+    ];
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum UnaryOp {
     Neg,
     Not,
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum BinaryOp {
     Add,
     Sub,
@@ -28,9 +62,10 @@ pub enum BinaryOp {
     Xor,
     Shl,
     Shr,
+    ShrLogical,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum Operand {
     Imm(i32),
     Register(Register),
@@ -38,7 +73,7 @@ pub enum Operand {
     Stack(usize),
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Instruction {
     Mov {
         src: Operand,
@@ -58,6 +93,10 @@ pub enum Instruction {
         operand1: Operand,
         operand2: Operand,
     },
+    Test {
+        operand1: Operand,
+        operand2: Operand,
+    },
     Jmp(String),
     JumpCC {
         cond_code: CondCode,
@@ -67,13 +106,44 @@ pub enum Instruction {
         cond_code: CondCode,
         operand: Operand,
     },
+    /// Moves `src` into `dst` if the condition holds.
+    Cmov {
+        cond_code: CondCode,
+        src: Operand,
+        dst: Operand,
+    },
     Label(String),
     Idiv(Operand),
+    /// Signed multiply of `EAX` by the operand into `EDX:EAX`.
+    Imul(Operand),
+    /// `dst = base + index * scale + displacement`, without touching the flags.
+    Lea {
+        base: Operand,
+        index: Option<Operand>,
+        scale: u8,
+        displacement: i32,
+        dst: Operand,
+    },
     Cdq,
     AllocateStack(usize),
+    Push(Register),
+    Pop(Register),
+    /// The following instructions come from this source line. Emits nothing
+    /// unless the assembly is annotated.
+    Line(usize),
+    /// Annotation emitted as an assembly comment, e.g. the irc instruction
+    /// the following instructions were lowered from.
+    Comment(String),
+    /// Where a local variable lives, for the debug info. Emits nothing.
+    /// `location` stays a pseudo if the variable's value is never computed.
+    Variable {
+        name: String,
+        line: usize,
+        location: Operand,
+    },
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum CondCode {
     E,
     NE,
@@ -83,13 +153,60 @@ pub enum CondCode {
     LE,
 }
 
+#[derive(Debug, Clone, Copy)]
 pub enum RegisterSize {
     One,
     Four,
+    Eight,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Function<'a> {
     pub name: &'a str,
     pub instructons: Vec<Instruction>,
 }
+
+impl Instruction {
+    /// Whether the instruction only carries information for annotating the
+    /// assembly and doesn't generate any code.
+    pub fn is_annotation(&self) -> bool {
+        matches!(
+            self,
+            Self::Line(_) | Self::Comment(_) | Self::Variable { .. }
+        )
+    }
+
+    pub fn operands_mut(&mut self) -> Vec<&mut Operand> {
+        match self {
+            Self::Mov { src, dst } | Self::Cmov { src, dst, .. } => vec![src, dst],
+            Self::Binary {
+                operand1, operand2, ..
+            }
+            | Self::Cmp { operand1, operand2 }
+            | Self::Test { operand1, operand2 } => vec![operand1, operand2],
+            Self::Unary { operand, .. }
+            | Self::SetCC { operand, .. }
+            | Self::Idiv(operand)
+            | Self::Imul(operand)
+            | Self::Variable {
+                location: operand, ..
+            } => vec![operand],
+            Self::Lea {
+                base, index, dst, ..
+            } => iter::once(base)
+                .chain(index)
+                .chain(iter::once(dst))
+                .collect(),
+            Self::Return
+            | Self::Jmp(_)
+            | Self::JumpCC { .. }
+            | Self::Label(_)
+            | Self::Cdq
+            | Self::AllocateStack(_)
+            | Self::Push(_)
+            | Self::Pop(_)
+            | Self::Line(_)
+            | Self::Comment(_) => Vec::new(),
+        }
+    }
+}
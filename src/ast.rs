@@ -1,4 +1,4 @@
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub enum Program<'a> {
     Function(Function<'a>),
 }
@@ -76,12 +76,12 @@ pub enum BinaryOp {
     GreaterOrEqual,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Block {
     pub items: Vec<BlockItem>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub enum Stmt {
     Return(Expr),
     Expression(Expr),
@@ -117,27 +117,35 @@ pub enum Stmt {
         label: Option<String>,
     },
     Compound(Block),
+    /// The source line the statement starts on, 0 if it's from another file
+    /// such as an included header.
+    Line(usize, Box<Stmt>),
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub enum ForInit {
     InitDecl(Decleration),
     InitExp(Option<Expr>),
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub enum Decleration {
-    Decleration { name: String, init: Option<Expr> },
+    Decleration {
+        name: String,
+        init: Option<Expr>,
+        line: usize,
+    },
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub enum BlockItem {
     Statement(Stmt),
     Decleration(Decleration),
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Function<'a> {
     pub name: &'a str,
     pub body: Block,
+    pub line: usize,
 }
@@ -0,0 +1,104 @@
+use std::{
+    collections::HashSet,
+    io::{self, Write},
+};
+
+use crate::irc::{Function, Instruction, Program, UnaryOp, Value};
+
+/// Writes the program as C that does what the irc does, one statement per
+/// instruction, with gotos for the jumps and every variable declared up
+/// front.
+pub fn emit_program(mut out: impl Write, program: &Program) -> io::Result<()> {
+    match program {
+        Program::Function(function) => emit_function(&mut out, function)?,
+    }
+    out.flush()
+}
+
+/// Names in irc end in `.` and a unique number, which C doesn't allow.
+fn identifier(name: &str) -> String {
+    name.replace('.', "_")
+}
+
+fn value(value: &Value) -> String {
+    match value {
+        Value::Constant(i32::MIN) => "(-2147483647 - 1)".to_string(),
+        Value::Constant(value) if *value < 0 => format!("({value})"),
+        Value::Constant(value) => value.to_string(),
+        Value::Var(name) => identifier(name),
+    }
+}
+
+fn emit_function(out: &mut impl Write, function: &Function) -> io::Result<()> {
+    writeln!(out, "int {}(void) {{", function.name)?;
+    let mut declared = HashSet::new();
+    for ins in &function.instructons {
+        let sources = ins.sources().into_iter().filter_map(|value| match value {
+            Value::Var(name) => Some(name.as_str()),
+            Value::Constant(_) => None,
+        });
+        for name in sources.chain(ins.dst()) {
+            if declared.insert(name) {
+                writeln!(out, "    int {};", identifier(name))?;
+            }
+        }
+    }
+    for ins in &function.instructons {
+        match ins {
+            Instruction::Ret(src) => writeln!(out, "    return {};", value(src))?,
+            Instruction::Unary { operator, src, dst } => {
+                let operator = match operator {
+                    UnaryOp::Negate => "-",
+                    UnaryOp::Complement => "~",
+                    UnaryOp::Not => "!",
+                };
+                writeln!(out, "    {} = {operator}{};", identifier(dst), value(src))?;
+            }
+            Instruction::Binary {
+                operator,
+                src1,
+                src2,
+                dst,
+            } => writeln!(
+                out,
+                "    {} = {} {operator} {};",
+                identifier(dst),
+                value(src1),
+                value(src2)
+            )?,
+            Instruction::Copy { src, dst } => {
+                writeln!(out, "    {} = {};", identifier(dst), value(src))?;
+            }
+            Instruction::Select {
+                condition,
+                src1,
+                src2,
+                dst,
+            } => writeln!(
+                out,
+                "    {} = {} ? {} : {};",
+                identifier(dst),
+                value(condition),
+                value(src1),
+                value(src2)
+            )?,
+            Instruction::Jump { target } => writeln!(out, "    goto {};", identifier(target))?,
+            Instruction::JumpIfZero { condition, target } => writeln!(
+                out,
+                "    if (!{}) goto {};",
+                value(condition),
+                identifier(target)
+            )?,
+            Instruction::JumpIfNotZero { condition, target } => writeln!(
+                out,
+                "    if ({}) goto {};",
+                value(condition),
+                identifier(target)
+            )?,
+            Instruction::Label(label) => writeln!(out, "{}:;", identifier(label))?,
+            Instruction::Line(line) => writeln!(out, "    // line {line}")?,
+            Instruction::Variable { .. } => {}
+        }
+    }
+    writeln!(out, "}}")
+}
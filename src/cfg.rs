@@ -0,0 +1,181 @@
+use std::collections::HashMap;
+
+use crate::{asm_ast, irc};
+
+/// How an instruction affects control flow.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Flow<'a> {
+    Label(&'a str),
+    Jump(&'a str),
+    ConditionalJump(&'a str),
+    Return,
+    Next,
+}
+
+pub trait FlowInstruction {
+    fn flow(&self) -> Flow<'_>;
+}
+
+impl FlowInstruction for irc::Instruction {
+    fn flow(&self) -> Flow<'_> {
+        match self {
+            irc::Instruction::Label(label) => Flow::Label(label),
+            irc::Instruction::Jump { target } => Flow::Jump(target),
+            irc::Instruction::JumpIfZero { target, .. }
+            | irc::Instruction::JumpIfNotZero { target, .. } => Flow::ConditionalJump(target),
+            irc::Instruction::Ret(_) => Flow::Return,
+            irc::Instruction::Unary { .. }
+            | irc::Instruction::Binary { .. }
+            | irc::Instruction::Copy { .. }
+            | irc::Instruction::Select { .. }
+            | irc::Instruction::Line(_)
+            | irc::Instruction::Variable { .. } => Flow::Next,
+        }
+    }
+}
+
+impl FlowInstruction for asm_ast::Instruction {
+    fn flow(&self) -> Flow<'_> {
+        match self {
+            asm_ast::Instruction::Label(label) => Flow::Label(label),
+            asm_ast::Instruction::Jmp(target) => Flow::Jump(target),
+            asm_ast::Instruction::JumpCC { target, .. } => Flow::ConditionalJump(target),
+            asm_ast::Instruction::Return => Flow::Return,
+            asm_ast::Instruction::Mov { .. }
+            | asm_ast::Instruction::Unary { .. }
+            | asm_ast::Instruction::Binary { .. }
+            | asm_ast::Instruction::Cmp { .. }
+            | asm_ast::Instruction::Test { .. }
+            | asm_ast::Instruction::SetCC { .. }
+            | asm_ast::Instruction::Cmov { .. }
+            | asm_ast::Instruction::Idiv(_)
+            | asm_ast::Instruction::Imul(_)
+            | asm_ast::Instruction::Lea { .. }
+            | asm_ast::Instruction::Cdq
+            | asm_ast::Instruction::AllocateStack(_)
+            | asm_ast::Instruction::Push(_)
+            | asm_ast::Instruction::Pop(_)
+            | asm_ast::Instruction::Line(_)
+            | asm_ast::Instruction::Comment(_)
+            | asm_ast::Instruction::Variable { .. } => Flow::Next,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct BasicBlock<I> {
+    pub instructions: Vec<I>,
+    pub predecessors: Vec<usize>,
+    pub successors: Vec<usize>,
+}
+
+/// Control flow graph of a function, execution enters at block `0`.
+/// A block without successors leaves the function.
+#[derive(Debug)]
+pub struct Cfg<I> {
+    pub blocks: Vec<BasicBlock<I>>,
+}
+
+impl<I: FlowInstruction> Cfg<I> {
+    pub fn new(instructions: Vec<I>) -> Self {
+        let mut blocks: Vec<BasicBlock<I>> = Vec::new();
+        let mut current = Vec::new();
+        for instruction in instructions {
+            match instruction.flow() {
+                Flow::Label(_) => {
+                    if !current.is_empty() {
+                        blocks.push(BasicBlock::new(std::mem::take(&mut current)));
+                    }
+                    current.push(instruction);
+                }
+                Flow::Jump(_) | Flow::ConditionalJump(_) | Flow::Return => {
+                    current.push(instruction);
+                    blocks.push(BasicBlock::new(std::mem::take(&mut current)));
+                }
+                Flow::Next => current.push(instruction),
+            }
+        }
+        if !current.is_empty() {
+            blocks.push(BasicBlock::new(current));
+        }
+
+        let mut cfg = Self { blocks };
+        cfg.add_edges();
+        cfg
+    }
+
+    fn add_edges(&mut self) {
+        let labels: HashMap<&str, usize> = self
+            .blocks
+            .iter()
+            .enumerate()
+            .filter_map(|(index, block)| match block.instructions.first()?.flow() {
+                Flow::Label(label) => Some((label, index)),
+                _ => None,
+            })
+            .collect();
+        let block_count = self.blocks.len();
+        let edges: Vec<(usize, usize)> = self
+            .blocks
+            .iter()
+            .enumerate()
+            .flat_map(|(index, block)| {
+                let next = (index + 1 < block_count).then_some(index + 1);
+                let successors = match block.instructions.last().map(FlowInstruction::flow) {
+                    Some(Flow::Jump(target)) => vec![labels.get(target).copied()],
+                    Some(Flow::ConditionalJump(target)) => {
+                        vec![labels.get(target).copied(), next]
+                    }
+                    Some(Flow::Return) => Vec::new(),
+                    Some(Flow::Label(_) | Flow::Next) | None => vec![next],
+                };
+                successors
+                    .into_iter()
+                    .flatten()
+                    .map(move |successor| (index, successor))
+            })
+            .collect();
+        for (from, to) in edges {
+            if !self.blocks[from].successors.contains(&to) {
+                self.blocks[from].successors.push(to);
+                self.blocks[to].predecessors.push(from);
+            }
+        }
+    }
+
+    /// Returns which blocks can be reached from the entry.
+    pub fn reachable(&self) -> Vec<bool> {
+        let mut reachable = vec![false; self.blocks.len()];
+        let mut stack = if self.blocks.is_empty() {
+            Vec::new()
+        } else {
+            vec![0]
+        };
+        while let Some(index) = stack.pop() {
+            if reachable[index] {
+                continue;
+            }
+            reachable[index] = true;
+            stack.extend(&self.blocks[index].successors);
+        }
+        reachable
+    }
+
+    /// Flattens the graph back into instructions, in block order.
+    pub fn into_instructions(self) -> Vec<I> {
+        self.blocks
+            .into_iter()
+            .flat_map(|block| block.instructions)
+            .collect()
+    }
+}
+
+impl<I> BasicBlock<I> {
+    fn new(instructions: Vec<I>) -> Self {
+        Self {
+            instructions,
+            predecessors: Vec::new(),
+            successors: Vec::new(),
+        }
+    }
+}
@@ -1,144 +1,707 @@
+use std::{
+    fmt::{self, Display},
+    io::{self, Write},
+};
+
 use crate::asm_ast::{
     BinaryOp, CondCode, Function, Instruction, Operand, Program, Register, RegisterSize, UnaryOp,
 };
 
-pub fn emit_program(program: Program) -> String {
-    match program {
-        Program::Function(function) => format!(
-            r#"
-{function}
-.section .note.GNU-stack,"",@progbits
-"#,
-            function = emit_function(function)
-        ),
+/// The syntax the assembly is printed in. Both are accepted by the GNU assembler.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Syntax {
+    #[default]
+    Att,
+    Intel,
+}
+
+/// How the assembly is printed.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Options<'a> {
+    pub syntax: Syntax,
+    /// Annotate the assembly with the lines of this source and the irc
+    /// instructions they were compiled to.
+    pub source: Option<&'a str>,
+    /// Emit line numbers, call frame information and debug info.
+    pub debug: Option<DebugInfo<'a>>,
+}
+
+/// The compiled file, as the debugger should find it.
+#[derive(Debug, Clone, Copy)]
+pub struct DebugInfo<'a> {
+    pub file: &'a str,
+    pub directory: &'a str,
+}
+
+/// An instruction operand.
+enum Arg<'a> {
+    Operand(&'a Operand, RegisterSize),
+    /// The address computed by `lea`.
+    Address {
+        base: &'a Operand,
+        index: Option<&'a Operand>,
+        scale: u8,
+        displacement: i32,
+    },
+    Label(&'a str),
+}
+
+impl Syntax {
+    pub const ALL: [Self; 2] = [Self::Att, Self::Intel];
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|syntax| syntax.name() == name)
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Att => "att",
+            Self::Intel => "intel",
+        }
+    }
+
+    /// Writes an instruction given its mnemonic, the operand size AT&T adds as
+    /// a suffix, and its operands in AT&T order (sources first).
+    fn line(
+        self,
+        out: &mut impl Write,
+        mnemonic: impl Display,
+        suffix: Option<RegisterSize>,
+        args: &[Arg],
+    ) -> io::Result<()> {
+        write!(out, "    {mnemonic}")?;
+        if let (Self::Att, Some(suffix)) = (self, suffix) {
+            out.write_all(size_suffix(suffix).as_bytes())?;
+        }
+        for index in 0..args.len() {
+            let arg = match self {
+                Self::Att => &args[index],
+                Self::Intel => &args[args.len() - 1 - index],
+            };
+            out.write_all(if index == 0 { b"    " } else { b", " })?;
+            self.arg(out, arg)?;
+        }
+        writeln!(out)
+    }
+
+    fn arg(self, out: &mut impl Write, arg: &Arg) -> io::Result<()> {
+        match *arg {
+            Arg::Operand(Operand::Register(register), size) => match self {
+                Self::Att => write!(out, "%{}", emit_register(*register, size)),
+                Self::Intel => out.write_all(emit_register(*register, size).as_bytes()),
+            },
+            Arg::Operand(Operand::Imm(value), _) => match self {
+                Self::Att => write!(out, "${value}"),
+                Self::Intel => write!(out, "{value}"),
+            },
+            Arg::Operand(Operand::Stack(offset), size) => match self {
+                Self::Att => write!(out, "-{offset}(%rbp)"),
+                Self::Intel => write!(out, "{} PTR [rbp-{offset}]", size_name(size)),
+            },
+            Arg::Operand(Operand::Pseudo(_), _) => unreachable!(),
+            Arg::Address {
+                base,
+                index,
+                scale,
+                displacement,
+            } => self.address(out, base, index, scale, displacement),
+            Arg::Label(label) => write!(out, ".L{label}"),
+        }
+    }
+
+    /// Addresses are computed with the full registers.
+    fn address<W: Write>(
+        self,
+        out: &mut W,
+        base: &Operand,
+        index: Option<&Operand>,
+        scale: u8,
+        displacement: i32,
+    ) -> io::Result<()> {
+        let register =
+            |out: &mut W, operand| self.arg(out, &Arg::Operand(operand, RegisterSize::Eight));
+        match self {
+            Self::Att => {
+                if displacement != 0 {
+                    write!(out, "{displacement}")?;
+                }
+                out.write_all(b"(")?;
+                register(out, base)?;
+                if let Some(index) = index {
+                    out.write_all(b", ")?;
+                    register(out, index)?;
+                    write!(out, ", {scale}")?;
+                }
+                out.write_all(b")")
+            }
+            Self::Intel => {
+                out.write_all(b"[")?;
+                register(out, base)?;
+                if let Some(index) = index {
+                    out.write_all(b"+")?;
+                    register(out, index)?;
+                    write!(out, "*{scale}")?;
+                }
+                match displacement {
+                    0 => {}
+                    displacement if displacement < 0 => write!(out, "{displacement}")?,
+                    displacement => write!(out, "+{displacement}")?,
+                }
+                out.write_all(b"]")
+            }
+        }
     }
 }
 
-fn emit_function(function: Function) -> String {
-    format!(
-        r"    .global {name}
-{name}:
-    pushq    %rbp
-    movq    %rsp, %rbp
-    {instructions}
-",
-        name = function.name,
-        instructions = emit_instructions(function.instructons)
-    )
+/// Writes the assembly for the program to `out` as it's generated, `out`
+/// should be buffered.
+pub fn emit_program(out: impl Write, program: &Program, options: Options) -> io::Result<()> {
+    let mut emitter = Emitter {
+        out,
+        options,
+        source_lines: options
+            .source
+            .map(|source| source.lines().collect())
+            .unwrap_or_default(),
+        depth: 0,
+    };
+    emitter.program(program)
 }
 
-fn emit_instructions(instructions: Vec<Instruction>) -> String {
-    // PERF: doesn't seem efficient
-    instructions
-        .into_iter()
-        .map(|ins| match ins {
-            Instruction::Mov { src, dst } => format!(
-                "movl    {src}, {dst}",
-                src = emit_operand(src, RegisterSize::Four),
-                dst = emit_operand(dst, RegisterSize::Four)
+impl Display for Program<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut assembly = Vec::new();
+        emit_program(&mut assembly, self, Options::default()).map_err(|_| fmt::Error)?;
+        f.write_str(&String::from_utf8_lossy(&assembly))
+    }
+}
+
+struct Emitter<'a, W> {
+    out: W,
+    options: Options<'a>,
+    /// The lines of `options.source`, looked up by the line annotations.
+    source_lines: Vec<&'a str>,
+    /// Bytes below `%rbp`, to tell the debugger where the prologue saves registers.
+    depth: usize,
+}
+
+impl<W: Write> Emitter<'_, W> {
+    fn program(&mut self, program: &Program) -> io::Result<()> {
+        if self.options.syntax == Syntax::Intel {
+            write!(self.out, "    .intel_syntax noprefix")?;
+        }
+        if let Some(debug) = self.options.debug {
+            write!(self.out, "\n    .file 1 {}", quote(debug.file))?;
+        }
+        writeln!(self.out)?;
+        match program {
+            Program::Function(function) => {
+                self.function(function)?;
+                if let Some(debug) = self.options.debug {
+                    self.debug_info(function, debug)?;
+                }
+            }
+        }
+        writeln!(self.out, "\n.section .note.GNU-stack,\"\",@progbits")?;
+        self.out.flush()
+    }
+
+    fn function(&mut self, function: &Function) -> io::Result<()> {
+        let syntax = self.options.syntax;
+        let name = function.name;
+        let rbp = Operand::Register(Register::BP);
+        let rsp = Operand::Register(Register::SP);
+        let rbp = Arg::Operand(&rbp, RegisterSize::Eight);
+        let rsp = Arg::Operand(&rsp, RegisterSize::Eight);
+        let debug = self.options.debug.is_some();
+        writeln!(self.out, "    .global {name}")?;
+        if debug {
+            writeln!(self.out, "    .type {name}, @function")?;
+        }
+        writeln!(self.out, "{name}:")?;
+        if debug {
+            if let Some(line) = function.instructons.iter().find_map(|ins| match ins {
+                Instruction::Line(line) => Some(line),
+                _ => None,
+            }) {
+                writeln!(self.out, "    .loc 1 {line}")?;
+            }
+            writeln!(self.out, "    .cfi_startproc")?;
+        }
+        syntax.line(&mut self.out, "push", Some(RegisterSize::Eight), &[rbp])?;
+        if debug {
+            let rbp = dwarf_register(Register::BP);
+            writeln!(self.out, "    .cfi_def_cfa_offset 16")?;
+            writeln!(self.out, "    .cfi_offset {rbp}, -16")?;
+        }
+        let rbp = Operand::Register(Register::BP);
+        let rbp = Arg::Operand(&rbp, RegisterSize::Eight);
+        syntax.line(&mut self.out, "mov", Some(RegisterSize::Eight), &[rsp, rbp])?;
+        if debug {
+            let rbp = dwarf_register(Register::BP);
+            writeln!(self.out, "    .cfi_def_cfa_register {rbp}")?;
+        }
+        self.depth = 0;
+        for ins in &function.instructons {
+            self.instruction(ins)?;
+        }
+        if debug {
+            writeln!(self.out, "    .cfi_endproc")?;
+            writeln!(self.out, ".L{name}.end:")?;
+            writeln!(self.out, "    .size {name}, .-{name}")?;
+        }
+        Ok(())
+    }
+
+    fn instruction(&mut self, instruction: &Instruction) -> io::Result<()> {
+        let syntax = self.options.syntax;
+        let out = &mut self.out;
+        let four = |operand| Arg::Operand(operand, RegisterSize::Four);
+        match instruction {
+            Instruction::Mov { src, dst } => syntax.line(
+                out,
+                "mov",
+                Some(RegisterSize::Four),
+                &[four(src), four(dst)],
+            ),
+            Instruction::Return => {
+                let rbp = Operand::Register(Register::BP);
+                let rsp = Operand::Register(Register::SP);
+                let eight = |operand| Arg::Operand(operand, RegisterSize::Eight);
+                let debug = self.options.debug.is_some();
+                // The code after the `ret` still has the frame.
+                if debug {
+                    writeln!(out, "    .cfi_remember_state")?;
+                }
+                syntax.line(
+                    out,
+                    "mov",
+                    Some(RegisterSize::Eight),
+                    &[eight(&rbp), eight(&rsp)],
+                )?;
+                syntax.line(out, "pop", Some(RegisterSize::Eight), &[eight(&rbp)])?;
+                if debug {
+                    writeln!(out, "    .cfi_def_cfa {}, 8", dwarf_register(Register::SP))?;
+                }
+                syntax.line(out, "ret", None, &[])?;
+                if debug {
+                    writeln!(out, "    .cfi_restore_state")?;
+                }
+                Ok(())
+            }
+            Instruction::Unary { operator, operand } => syntax.line(
+                out,
+                emit_unary(*operator),
+                Some(RegisterSize::Four),
+                &[four(operand)],
             ),
-            Instruction::Return => r"movq    %rbp, %rsp
-    popq    %rbp
-    ret"
-            .to_string(),
-            Instruction::Unary { operator, operand } => {
-                format!(
-                    "{operator}    {operand}",
-                    operand = emit_operand(operand, RegisterSize::Four),
-                    operator = emit_unary(operator)
+            Instruction::AllocateStack(bytes) => {
+                self.depth += bytes;
+                let bytes =
+                    Operand::Imm(i32::try_from(*bytes).expect("stack frame fits in 32 bits"));
+                let rsp = Operand::Register(Register::SP);
+                syntax.line(
+                    out,
+                    "sub",
+                    Some(RegisterSize::Eight),
+                    &[
+                        Arg::Operand(&bytes, RegisterSize::Eight),
+                        Arg::Operand(&rsp, RegisterSize::Eight),
+                    ],
                 )
             }
-            Instruction::AllocateStack(bytes) => format!("subq    ${bytes}, %rsp"),
             Instruction::Binary {
                 operator,
                 operand1,
                 operand2,
-            } => format!(
-                "{operator}    {operand1}, {operand2}",
-                operator = emit_binary(operator),
-                operand1 = emit_operand(
-                    operand1,
-                    if matches!(operator, BinaryOp::Shl | BinaryOp::Shr) {
-                        RegisterSize::One
-                    } else {
-                        RegisterSize::Four
-                    }
-                ),
-                operand2 = emit_operand(operand2, RegisterSize::Four)
-            ),
-            Instruction::Idiv(operand) => {
-                format!(
-                    "idivl    {operand}",
-                    operand = emit_operand(operand, RegisterSize::Four)
+            } => {
+                let count_size = if matches!(
+                    operator,
+                    BinaryOp::Shl | BinaryOp::Shr | BinaryOp::ShrLogical
+                ) {
+                    RegisterSize::One
+                } else {
+                    RegisterSize::Four
+                };
+                syntax.line(
+                    out,
+                    emit_binary(*operator),
+                    Some(RegisterSize::Four),
+                    &[Arg::Operand(operand1, count_size), four(operand2)],
                 )
             }
-            Instruction::Cdq => "cdq".to_string(),
-            Instruction::Cmp { operand1, operand2 } => format!(
-                "cmpl    {operand1}, {operand2}",
-                operand1 = emit_operand(operand1, RegisterSize::Four),
-                operand2 = emit_operand(operand2, RegisterSize::Four)
+            Instruction::Idiv(operand) => {
+                syntax.line(out, "idiv", Some(RegisterSize::Four), &[four(operand)])
+            }
+            Instruction::Imul(operand) => {
+                syntax.line(out, "imul", Some(RegisterSize::Four), &[four(operand)])
+            }
+            Instruction::Lea {
+                base,
+                index,
+                scale,
+                displacement,
+                dst,
+            } => syntax.line(
+                out,
+                "lea",
+                Some(RegisterSize::Four),
+                &[
+                    Arg::Address {
+                        base,
+                        index: index.as_ref(),
+                        scale: *scale,
+                        displacement: *displacement,
+                    },
+                    four(dst),
+                ],
+            ),
+            Instruction::Cdq => syntax.line(out, "cdq", None, &[]),
+            Instruction::Cmp { operand1, operand2 } => syntax.line(
+                out,
+                "cmp",
+                Some(RegisterSize::Four),
+                &[four(operand1), four(operand2)],
+            ),
+            Instruction::Test { operand1, operand2 } => syntax.line(
+                out,
+                "test",
+                Some(RegisterSize::Four),
+                &[four(operand1), four(operand2)],
             ),
-            Instruction::Jmp(label) => format!("jmp    .L{label}"),
-            Instruction::JumpCC { cond_code, target } => format!(
-                "j{cond_code}    .L{target}",
-                cond_code = emit_cond_code(cond_code)
+            Instruction::Jmp(label) => syntax.line(out, "jmp", None, &[Arg::Label(label)]),
+            Instruction::JumpCC { cond_code, target } => syntax.line(
+                out,
+                format_args!("j{}", emit_cond_code(*cond_code)),
+                None,
+                &[Arg::Label(target)],
             ),
-            Instruction::SetCC { cond_code, operand } => format!(
-                "set{cond_code}    {operand}",
-                cond_code = emit_cond_code(cond_code),
-                operand = emit_operand(operand, RegisterSize::One)
+            Instruction::SetCC { cond_code, operand } => syntax.line(
+                out,
+                format_args!("set{}", emit_cond_code(*cond_code)),
+                None,
+                &[Arg::Operand(operand, RegisterSize::One)],
             ),
-            Instruction::Label(label) => format!(".L{label}:"),
-        })
-        .collect::<Vec<String>>()
-        .join("\n    ")
-}
-
-fn emit_operand(operand: Operand, size: RegisterSize) -> String {
-    match (operand, size) {
-        (Operand::Register(Register::AX), RegisterSize::Four) => "%eax".to_string(),
-        (Operand::Register(Register::AX), RegisterSize::One) => "%al".to_string(),
-        (Operand::Register(Register::DX), RegisterSize::Four) => "%edx".to_string(),
-        (Operand::Register(Register::DX), RegisterSize::One) => "%dl".to_string(),
-        (Operand::Register(Register::CX), RegisterSize::Four) => "%ecx".to_string(),
-        (Operand::Register(Register::CX), RegisterSize::One) => "%cl".to_string(),
-        (Operand::Register(Register::R10), RegisterSize::Four) => "%r10d".to_string(),
-        (Operand::Register(Register::R10), RegisterSize::One) => "%r10b".to_string(),
-        (Operand::Register(Register::R11), RegisterSize::Four) => "%r11d".to_string(),
-        (Operand::Register(Register::R11), RegisterSize::One) => "%r11b".to_string(),
-        (Operand::Imm(value), _) => format!("${value}"),
-        (Operand::Stack(offset), _) => format!("-{offset}(%rbp)"),
-        (Operand::Pseudo(_), _) => unreachable!(),
-    }
-}
-
-fn emit_unary(operator: UnaryOp) -> String {
+            Instruction::Cmov {
+                cond_code,
+                src,
+                dst,
+            } => syntax.line(
+                out,
+                format_args!("cmov{}", emit_cond_code(*cond_code)),
+                Some(RegisterSize::Four),
+                &[four(src), four(dst)],
+            ),
+            Instruction::Label(label) => writeln!(out, "    .L{label}:"),
+            Instruction::Push(register) => {
+                let operand = Operand::Register(*register);
+                syntax.line(
+                    out,
+                    "push",
+                    Some(RegisterSize::Eight),
+                    &[Arg::Operand(&operand, RegisterSize::Eight)],
+                )?;
+                self.depth += 8;
+                if self.options.debug.is_some() {
+                    writeln!(
+                        self.out,
+                        "    .cfi_offset {}, -{}",
+                        dwarf_register(*register),
+                        self.depth + 16
+                    )?;
+                }
+                Ok(())
+            }
+            Instruction::Pop(register) => {
+                let operand = Operand::Register(*register);
+                syntax.line(
+                    out,
+                    "pop",
+                    Some(RegisterSize::Eight),
+                    &[Arg::Operand(&operand, RegisterSize::Eight)],
+                )
+            }
+            // GAS takes `#` comments in both syntaxes.
+            Instruction::Line(line) => {
+                // Line 0 is code from another file, such as a header.
+                if self.options.source.is_some() && *line != 0 {
+                    let text = line
+                        .checked_sub(1)
+                        .and_then(|index| self.source_lines.get(index))
+                        .map_or("", |text| text.trim());
+                    writeln!(out, "    # {line}: {text}")?;
+                }
+                if self.options.debug.is_some() {
+                    writeln!(out, "    .loc 1 {line}")?;
+                }
+                Ok(())
+            }
+            Instruction::Comment(comment) => {
+                if self.options.source.is_some() {
+                    writeln!(out, "    #   {comment}")?;
+                }
+                Ok(())
+            }
+            Instruction::Variable { .. } => Ok(()),
+        }
+    }
+
+    /// DWARF 4 debug info for the function and its locals, with the frame base
+    /// at the canonical frame address, `16(%rbp)`.
+    fn debug_info(&mut self, function: &Function, debug: DebugInfo) -> io::Result<()> {
+        let name = function.name;
+        let function_line = function
+            .instructons
+            .iter()
+            .find_map(|ins| match ins {
+                Instruction::Line(line) => Some(*line),
+                _ => None,
+            })
+            .unwrap_or_default();
+        write!(
+            self.out,
+            r#"{DEBUG_ABBREVIATIONS}    .section .debug_info,"",@progbits
+.Ldebug_info0:
+    .long .Ldebug_info0.end - .Ldebug_info0 - 4
+    .short 4
+    .long .Ldebug_abbrev0
+    .byte 8
+    .uleb128 1
+    .string {producer}
+    .byte 0x0c    # DW_LANG_C99
+    .string {file}
+    .string {directory}
+    .quad {name}
+    .quad .L{name}.end - {name}
+    .long .Ldebug_line0
+.Ldebug_int:
+    .uleb128 5
+    .byte 4, 0x05    # DW_ATE_signed
+    .string "int"
+    .uleb128 2
+    .string {quoted_name}
+    .byte 1
+    .uleb128 {function_line}
+    .long .Ldebug_int - .Ldebug_info0
+    .quad {name}
+    .quad .L{name}.end - {name}
+    .uleb128 1
+    .byte 0x9c    # DW_OP_call_frame_cfa
+"#,
+            producer = quote(concat!("comp ", env!("CARGO_PKG_VERSION"))),
+            file = quote(debug.file),
+            directory = quote(debug.directory),
+            quoted_name = quote(name),
+        )?;
+        for ins in &function.instructons {
+            if let Instruction::Variable {
+                name,
+                line,
+                location,
+            } = ins
+            {
+                self.debug_variable(name, *line, location)?;
+            }
+        }
+        write!(
+            self.out,
+            r#"    .byte 0, 0
+.Ldebug_info0.end:
+    .section .debug_line,"",@progbits
+.Ldebug_line0:"#
+        )
+    }
+
+    fn debug_variable(&mut self, name: &str, line: usize, location: &Operand) -> io::Result<()> {
+        let expression = match location {
+            // DW_OP_fbreg
+            Operand::Stack(offset) => {
+                let offset = i64::try_from(offset + 16).expect("stack frame fits in 64 bits");
+                let mut expression = vec![0x91];
+                expression.extend(sleb128(-offset));
+                Some(expression)
+            }
+            // DW_OP_reg0 + n
+            Operand::Register(register) => Some(vec![0x50 + dwarf_register(*register)]),
+            Operand::Pseudo(_) | Operand::Imm(_) => None,
+        };
+        let abbreviation = if expression.is_some() { 3 } else { 4 };
+        let source_name = name.rsplit_once('.').map_or(name, |(name, _)| name);
+        write!(
+            self.out,
+            "    .uleb128 {abbreviation}\n    .string {}\n    .byte 1\n    .uleb128 {line}\n    .long .Ldebug_int - .Ldebug_info0\n",
+            quote(source_name)
+        )?;
+        if let Some(expression) = expression {
+            let bytes: Vec<String> = expression.iter().map(u8::to_string).collect();
+            write!(
+                self.out,
+                "    .uleb128 {}\n    .byte {}\n",
+                expression.len(),
+                bytes.join(", ")
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Abbreviations for the entries of the debug info, the line table is generated
+/// by the assembler from the `.loc` directives.
+const DEBUG_ABBREVIATIONS: &str = r#"    .section .debug_abbrev,"",@progbits
+.Ldebug_abbrev0:
+    # 1: DW_TAG_compile_unit, with children
+    .uleb128 1, 0x11
+    .byte 1
+    # DW_AT_producer, DW_AT_language, DW_AT_name, DW_AT_comp_dir,
+    # DW_AT_low_pc, DW_AT_high_pc, DW_AT_stmt_list
+    .uleb128 0x25, 0x08, 0x13, 0x0b, 0x03, 0x08, 0x1b, 0x08
+    .uleb128 0x11, 0x01, 0x12, 0x07, 0x10, 0x17
+    .byte 0, 0
+    # 2: DW_TAG_subprogram, with children
+    .uleb128 2, 0x2e
+    .byte 1
+    # DW_AT_external, DW_AT_name, DW_AT_decl_file, DW_AT_decl_line, DW_AT_type,
+    # DW_AT_low_pc, DW_AT_high_pc, DW_AT_frame_base
+    .uleb128 0x3f, 0x19, 0x03, 0x08, 0x3a, 0x0b, 0x3b, 0x0f, 0x49, 0x13
+    .uleb128 0x11, 0x01, 0x12, 0x07, 0x40, 0x18
+    .byte 0, 0
+    # 3: DW_TAG_variable
+    .uleb128 3, 0x34
+    .byte 0
+    # DW_AT_name, DW_AT_decl_file, DW_AT_decl_line, DW_AT_type, DW_AT_location
+    .uleb128 0x03, 0x08, 0x3a, 0x0b, 0x3b, 0x0f, 0x49, 0x13, 0x02, 0x18
+    .byte 0, 0
+    # 4: DW_TAG_variable, optimized out
+    .uleb128 4, 0x34
+    .byte 0
+    .uleb128 0x03, 0x08, 0x3a, 0x0b, 0x3b, 0x0f, 0x49, 0x13
+    .byte 0, 0
+    # 5: DW_TAG_base_type
+    .uleb128 5, 0x24
+    .byte 0
+    # DW_AT_byte_size, DW_AT_encoding, DW_AT_name
+    .uleb128 0x0b, 0x0b, 0x3e, 0x0b, 0x03, 0x08
+    .byte 0, 0
+    .byte 0
+"#;
+
+fn sleb128(mut value: i64) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    loop {
+        let byte = (value & 0x7f) as u8;
+        value >>= 7;
+        let done = (value == 0 && byte & 0x40 == 0) || (value == -1 && byte & 0x40 != 0);
+        if done {
+            bytes.push(byte);
+            return bytes;
+        }
+        bytes.push(byte | 0x80);
+    }
+}
+
+/// A string literal for the assembler.
+fn quote(string: &str) -> String {
+    format!("\"{}\"", string.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+fn dwarf_register(register: Register) -> u8 {
+    match register {
+        Register::AX => 0,
+        Register::DX => 1,
+        Register::CX => 2,
+        Register::BX => 3,
+        Register::SI => 4,
+        Register::DI => 5,
+        Register::BP => 6,
+        Register::SP => 7,
+        Register::R8 => 8,
+        Register::R9 => 9,
+        Register::R10 => 10,
+        Register::R11 => 11,
+        Register::R12 => 12,
+        Register::R13 => 13,
+        Register::R14 => 14,
+        Register::R15 => 15,
+    }
+}
+
+fn size_suffix(size: RegisterSize) -> &'static str {
+    match size {
+        RegisterSize::One => "b",
+        RegisterSize::Four => "l",
+        RegisterSize::Eight => "q",
+    }
+}
+
+fn size_name(size: RegisterSize) -> &'static str {
+    match size {
+        RegisterSize::One => "BYTE",
+        RegisterSize::Four => "DWORD",
+        RegisterSize::Eight => "QWORD",
+    }
+}
+
+/// The register with the 64 bit name `name`, e.g. `rax`.
+pub fn register_from_name(name: &str) -> Option<Register> {
+    Register::ALL
+        .into_iter()
+        .find(|&register| emit_register(register, RegisterSize::Eight) == name)
+}
+
+fn emit_register(register: Register, size: RegisterSize) -> &'static str {
+    let [byte, double_word, quad_word] = match register {
+        Register::AX => ["al", "eax", "rax"],
+        Register::BX => ["bl", "ebx", "rbx"],
+        Register::CX => ["cl", "ecx", "rcx"],
+        Register::DX => ["dl", "edx", "rdx"],
+        Register::SI => ["sil", "esi", "rsi"],
+        Register::DI => ["dil", "edi", "rdi"],
+        Register::SP => ["spl", "esp", "rsp"],
+        Register::BP => ["bpl", "ebp", "rbp"],
+        Register::R8 => ["r8b", "r8d", "r8"],
+        Register::R9 => ["r9b", "r9d", "r9"],
+        Register::R10 => ["r10b", "r10d", "r10"],
+        Register::R11 => ["r11b", "r11d", "r11"],
+        Register::R12 => ["r12b", "r12d", "r12"],
+        Register::R13 => ["r13b", "r13d", "r13"],
+        Register::R14 => ["r14b", "r14d", "r14"],
+        Register::R15 => ["r15b", "r15d", "r15"],
+    };
+    match size {
+        RegisterSize::One => byte,
+        RegisterSize::Four => double_word,
+        RegisterSize::Eight => quad_word,
+    }
+}
+
+fn emit_unary(operator: UnaryOp) -> &'static str {
     match operator {
-        UnaryOp::Neg => "negl".to_string(),
-        UnaryOp::Not => "notl".to_string(),
+        UnaryOp::Neg => "neg",
+        UnaryOp::Not => "not",
     }
 }
 
-fn emit_binary(operator: BinaryOp) -> String {
+fn emit_binary(operator: BinaryOp) -> &'static str {
     match operator {
-        BinaryOp::Add => "addl".to_string(),
-        BinaryOp::Sub => "subl".to_string(),
-        BinaryOp::Mult => "imull".to_string(),
-        BinaryOp::And => "andl".to_string(),
-        BinaryOp::Or => "orl".to_string(),
-        BinaryOp::Xor => "xorl".to_string(),
-        BinaryOp::Shl => "sall".to_string(),
-        BinaryOp::Shr => "sarl".to_string(),
+        BinaryOp::Add => "add",
+        BinaryOp::Sub => "sub",
+        BinaryOp::Mult => "imul",
+        BinaryOp::And => "and",
+        BinaryOp::Or => "or",
+        BinaryOp::Xor => "xor",
+        BinaryOp::Shl => "sal",
+        BinaryOp::Shr => "sar",
+        BinaryOp::ShrLogical => "shr",
     }
 }
 
-fn emit_cond_code(cond_code: CondCode) -> String {
+fn emit_cond_code(cond_code: CondCode) -> &'static str {
     match cond_code {
-        CondCode::E => "e".to_string(),
-        CondCode::NE => "ne".to_string(),
-        CondCode::G => "g".to_string(),
-        CondCode::GE => "ge".to_string(),
-        CondCode::L => "l".to_string(),
-        CondCode::LE => "le".to_string(),
+        CondCode::E => "e",
+        CondCode::NE => "ne",
+        CondCode::G => "g",
+        CondCode::GE => "ge",
+        CondCode::L => "l",
+        CondCode::LE => "le",
     }
 }
@@ -1,21 +1,50 @@
-use std::iter;
+use std::collections::{HashMap, HashSet};
 
-use crate::{asm_ast, irc};
+use crate::{asm_ast, constant_folding, irc, register_allocation, target::Target, x86_64::X86_64};
 
-pub fn gen_program(program: irc::Program) -> asm_ast::Program {
+/// With `comments`, every irc instruction is kept as a comment before the
+/// instructions selected for it.
+pub fn gen_program(program: irc::Program, comments: bool) -> asm_ast::Program {
     match program {
-        irc::Program::Function(function) => asm_ast::Program::Function(gen_function(function)),
+        irc::Program::Function(function) => {
+            asm_ast::Program::Function(gen_function(function, comments))
+        }
     }
 }
 
-fn gen_function(function: irc::Function) -> asm_ast::Function {
+fn gen_function(function: irc::Function, comments: bool) -> asm_ast::Function {
+    let mut uses: HashMap<&str, usize> = HashMap::new();
+    for ins in &function.instructons {
+        for src in ins.sources() {
+            if let irc::Value::Var(name) = src {
+                *uses.entry(name.as_str()).or_default() += 1;
+            }
+        }
+    }
+    let mut instructions = Vec::new();
+    let mut index = 0;
+    while let Some(ins) = function.instructons.get(index) {
+        let fused = function
+            .instructons
+            .get(index + 1)
+            .and_then(|user| gen_fused_condition(ins, user, &uses));
+        let lowered = if fused.is_some() { 2 } else { 1 };
+        if comments {
+            instructions.extend(
+                function.instructons[index..index + lowered]
+                    .iter()
+                    .filter(|ins| {
+                        !matches!(ins, irc::Instruction::Label(_) | irc::Instruction::Line(_))
+                    })
+                    .map(|ins| asm_ast::Instruction::Comment(ins.to_string().trim().to_string())),
+            );
+        }
+        instructions.extend(fused.unwrap_or_else(|| gen_instruction(ins.clone())));
+        index += lowered;
+    }
     asm_ast::Function {
         name: function.name,
-        instructons: function
-            .instructons
-            .into_iter()
-            .flat_map(gen_instruction)
-            .collect(),
+        instructons: instructions,
     }
 }
 
@@ -28,27 +57,24 @@ fn gen_instruction(instruction: irc::Instruction) -> Vec<asm_ast::Instruction> {
             },
             asm_ast::Instruction::Return,
         ],
+        irc::Instruction::Unary {
+            operator,
+            src: irc::Value::Constant(src),
+            dst,
+        } => vec![asm_ast::Instruction::Mov {
+            src: asm_ast::Operand::Imm(constant_folding::fold_unary(operator, src)),
+            dst: gen_operand(irc::Value::Var(dst)),
+        }],
         irc::Instruction::Unary {
             operator: irc::UnaryOp::Not,
             src,
             dst,
-        } => {
-            let dst = gen_operand(irc::Value::Var(dst));
-            vec![
-                asm_ast::Instruction::Cmp {
-                    operand1: asm_ast::Operand::Imm(0),
-                    operand2: gen_operand(src),
-                },
-                asm_ast::Instruction::Mov {
-                    src: asm_ast::Operand::Imm(0),
-                    dst: dst.clone(),
-                },
-                asm_ast::Instruction::SetCC {
-                    cond_code: asm_ast::CondCode::E,
-                    operand: dst,
-                },
-            ]
-        }
+        } => gen_binary_rel(
+            asm_ast::CondCode::E,
+            src,
+            irc::Value::Constant(0),
+            gen_operand(irc::Value::Var(dst)),
+        ),
         irc::Instruction::Unary { operator, src, dst } => {
             let dst = gen_operand(irc::Value::Var(dst));
             vec![
@@ -69,17 +95,28 @@ fn gen_instruction(instruction: irc::Instruction) -> Vec<asm_ast::Instruction> {
             dst,
         } => {
             let dst = gen_operand(irc::Value::Var(dst));
-            match operator {
-                irc::BinaryOp::Add => gen_binary_ins(asm_ast::BinaryOp::Add, src1, src2, dst),
-                irc::BinaryOp::Subtract => gen_binary_ins(asm_ast::BinaryOp::Sub, src1, src2, dst),
-                irc::BinaryOp::Multiply => gen_binary_ins(asm_ast::BinaryOp::Mult, src1, src2, dst),
-                irc::BinaryOp::LeftShift => gen_binary_ins(asm_ast::BinaryOp::Shl, src1, src2, dst),
-                irc::BinaryOp::RightShift => {
-                    gen_binary_ins(asm_ast::BinaryOp::Shr, src1, src2, dst)
+            if let (irc::Value::Constant(src1), irc::Value::Constant(src2)) = (&src1, &src2) {
+                if let Some(result) = constant_folding::fold_binary(operator, *src1, *src2) {
+                    return vec![asm_ast::Instruction::Mov {
+                        src: asm_ast::Operand::Imm(result),
+                        dst,
+                    }];
                 }
-                irc::BinaryOp::BitAnd => gen_binary_ins(asm_ast::BinaryOp::And, src1, src2, dst),
-                irc::BinaryOp::Xor => gen_binary_ins(asm_ast::BinaryOp::Xor, src1, src2, dst),
-                irc::BinaryOp::BitOr => gen_binary_ins(asm_ast::BinaryOp::Or, src1, src2, dst),
+            }
+            match operator {
+                irc::BinaryOp::Add => gen_add(src1, src2, dst),
+                irc::BinaryOp::Subtract => match src2 {
+                    irc::Value::Constant(src2) if src2 != i32::MIN => {
+                        gen_add(src1, irc::Value::Constant(-src2), dst)
+                    }
+                    _ => gen_binary_ins(asm_ast::BinaryOp::Sub, src1, src2, dst),
+                },
+                irc::BinaryOp::Multiply => gen_multiply(src1, src2, dst),
+                irc::BinaryOp::LeftShift => gen_shift(asm_ast::BinaryOp::Shl, src1, src2, dst),
+                irc::BinaryOp::RightShift => gen_shift(asm_ast::BinaryOp::Shr, src1, src2, dst),
+                irc::BinaryOp::BitAnd => gen_commutative(asm_ast::BinaryOp::And, src1, src2, dst),
+                irc::BinaryOp::Xor => gen_commutative(asm_ast::BinaryOp::Xor, src1, src2, dst),
+                irc::BinaryOp::BitOr => gen_commutative(asm_ast::BinaryOp::Or, src1, src2, dst),
                 irc::BinaryOp::Equal => gen_binary_rel(asm_ast::CondCode::E, src1, src2, dst),
                 irc::BinaryOp::NotEqual => gen_binary_rel(asm_ast::CondCode::NE, src1, src2, dst),
                 irc::BinaryOp::LessThan => gen_binary_rel(asm_ast::CondCode::L, src1, src2, dst),
@@ -90,32 +127,8 @@ fn gen_instruction(instruction: irc::Instruction) -> Vec<asm_ast::Instruction> {
                 irc::BinaryOp::GreaterOrEqual => {
                     gen_binary_rel(asm_ast::CondCode::GE, src1, src2, dst)
                 }
-                irc::BinaryOp::Divide => {
-                    vec![
-                        asm_ast::Instruction::Mov {
-                            src: gen_operand(src1),
-                            dst: asm_ast::Operand::Register(asm_ast::Register::AX),
-                        },
-                        asm_ast::Instruction::Cdq,
-                        asm_ast::Instruction::Idiv(gen_operand(src2)),
-                        asm_ast::Instruction::Mov {
-                            src: asm_ast::Operand::Register(asm_ast::Register::AX),
-                            dst,
-                        },
-                    ]
-                }
-                irc::BinaryOp::Remainder => vec![
-                    asm_ast::Instruction::Mov {
-                        src: gen_operand(src1),
-                        dst: asm_ast::Operand::Register(asm_ast::Register::AX),
-                    },
-                    asm_ast::Instruction::Cdq,
-                    asm_ast::Instruction::Idiv(gen_operand(src2)),
-                    asm_ast::Instruction::Mov {
-                        src: asm_ast::Operand::Register(asm_ast::Register::DX),
-                        dst,
-                    },
-                ],
+                irc::BinaryOp::Divide => gen_division(false, src1, src2, dst),
+                irc::BinaryOp::Remainder => gen_division(true, src1, src2, dst),
             }
         }
         irc::Instruction::Copy { src, dst } => vec![asm_ast::Instruction::Mov {
@@ -124,28 +137,31 @@ fn gen_instruction(instruction: irc::Instruction) -> Vec<asm_ast::Instruction> {
         }],
         irc::Instruction::Jump { target } => vec![asm_ast::Instruction::Jmp(target)],
         irc::Instruction::JumpIfZero { condition, target } => {
-            vec![
-                asm_ast::Instruction::Cmp {
-                    operand1: asm_ast::Operand::Imm(0),
-                    operand2: gen_operand(condition),
-                },
-                asm_ast::Instruction::JumpCC {
-                    cond_code: asm_ast::CondCode::E,
-                    target,
-                },
-            ]
+            gen_conditional_jump(asm_ast::CondCode::E, condition, target)
         }
-        irc::Instruction::JumpIfNotZero { condition, target } => vec![
-            asm_ast::Instruction::Cmp {
-                operand1: asm_ast::Operand::Imm(0),
-                operand2: gen_operand(condition),
-            },
-            asm_ast::Instruction::JumpCC {
-                cond_code: asm_ast::CondCode::NE,
-                target,
-            },
-        ],
+        irc::Instruction::JumpIfNotZero { condition, target } => {
+            gen_conditional_jump(asm_ast::CondCode::NE, condition, target)
+        }
+        irc::Instruction::Select {
+            condition,
+            src1,
+            src2,
+            dst,
+        } => gen_select(
+            asm_ast::CondCode::NE,
+            condition,
+            irc::Value::Constant(0),
+            src1,
+            src2,
+            gen_operand(irc::Value::Var(dst)),
+        ),
         irc::Instruction::Label(target) => vec![asm_ast::Instruction::Label(target)],
+        irc::Instruction::Line(line) => vec![asm_ast::Instruction::Line(line)],
+        irc::Instruction::Variable { name, line } => vec![asm_ast::Instruction::Variable {
+            location: asm_ast::Operand::Pseudo(name.clone()),
+            name,
+            line,
+        }],
     }
 }
 
@@ -170,11 +186,9 @@ fn gen_binary_rel(
     src2: irc::Value,
     dst: asm_ast::Operand,
 ) -> Vec<asm_ast::Instruction> {
+    let (compare, cond_code) = gen_compare(cond_code, src1, src2);
     vec![
-        asm_ast::Instruction::Cmp {
-            operand1: gen_operand(src2),
-            operand2: gen_operand(src1),
-        },
+        compare,
         asm_ast::Instruction::Mov {
             src: asm_ast::Operand::Imm(0),
             dst: dst.clone(),
@@ -186,6 +200,545 @@ fn gen_binary_rel(
     ]
 }
 
+/// Jumps if `condition` compared with zero satisfies `cond_code`.
+fn gen_conditional_jump(
+    cond_code: asm_ast::CondCode,
+    condition: irc::Value,
+    target: String,
+) -> Vec<asm_ast::Instruction> {
+    gen_branch(cond_code, condition, irc::Value::Constant(0), target)
+}
+
+/// Jumps if `src1 {cond_code} src2`, decided at compile time for constants.
+fn gen_branch(
+    cond_code: asm_ast::CondCode,
+    src1: irc::Value,
+    src2: irc::Value,
+    target: String,
+) -> Vec<asm_ast::Instruction> {
+    if let (irc::Value::Constant(src1), irc::Value::Constant(src2)) = (&src1, &src2) {
+        return if evaluate_cond_code(cond_code, *src1, *src2) {
+            vec![asm_ast::Instruction::Jmp(target)]
+        } else {
+            Vec::new()
+        };
+    }
+    let (compare, cond_code) = gen_compare(cond_code, src1, src2);
+    vec![compare, asm_ast::Instruction::JumpCC { cond_code, target }]
+}
+
+/// Lowers a comparison (or `!`) whose result is only read by the conditional
+/// jump or select right after it into a compare followed by a jcc or cmov,
+/// without materializing the result. Returns `None` if the pair can't be fused.
+fn gen_fused_condition(
+    instruction: &irc::Instruction,
+    user: &irc::Instruction,
+    uses: &HashMap<&str, usize>,
+) -> Option<Vec<asm_ast::Instruction>> {
+    let (cond_code, src1, src2, dst) = match instruction {
+        irc::Instruction::Binary {
+            operator,
+            src1,
+            src2,
+            dst,
+        } => (relational_cond_code(*operator)?, src1, src2, dst),
+        irc::Instruction::Unary {
+            operator: irc::UnaryOp::Not,
+            src,
+            dst,
+        } => (asm_ast::CondCode::E, src, &irc::Value::Constant(0), dst),
+        _ => return None,
+    };
+    if uses.get(dst.as_str()) != Some(&1) {
+        return None;
+    }
+    let is_dst = |value: &irc::Value| *value == irc::Value::Var(dst.clone());
+    match user {
+        irc::Instruction::JumpIfZero { condition, target } if is_dst(condition) => {
+            Some(gen_branch(
+                negate_cond_code(cond_code),
+                src1.clone(),
+                src2.clone(),
+                target.clone(),
+            ))
+        }
+        irc::Instruction::JumpIfNotZero { condition, target } if is_dst(condition) => Some(
+            gen_branch(cond_code, src1.clone(), src2.clone(), target.clone()),
+        ),
+        irc::Instruction::Select {
+            condition,
+            src1: then,
+            src2: otherwise,
+            dst: result,
+        } if is_dst(condition) => Some(gen_select(
+            cond_code,
+            src1.clone(),
+            src2.clone(),
+            then.clone(),
+            otherwise.clone(),
+            gen_operand(irc::Value::Var(result.clone())),
+        )),
+        _ => None,
+    }
+}
+
+/// `dst = src1 {cond_code} src2 ? then : otherwise` with a conditional move.
+fn gen_select(
+    cond_code: asm_ast::CondCode,
+    src1: irc::Value,
+    src2: irc::Value,
+    then: irc::Value,
+    otherwise: irc::Value,
+    dst: asm_ast::Operand,
+) -> Vec<asm_ast::Instruction> {
+    if let (irc::Value::Constant(src1), irc::Value::Constant(src2)) = (&src1, &src2) {
+        let src = if evaluate_cond_code(cond_code, *src1, *src2) {
+            then
+        } else {
+            otherwise
+        };
+        return vec![asm_ast::Instruction::Mov {
+            src: gen_operand(src),
+            dst,
+        }];
+    }
+    let (compare, cond_code) = gen_compare(cond_code, src1, src2);
+    // `cmov` can't take an immediate, so a constant goes in with the `mov`.
+    let (cond_code, then, otherwise) = match (&then, &otherwise) {
+        (irc::Value::Constant(_), irc::Value::Var(_)) => {
+            (negate_cond_code(cond_code), otherwise, then)
+        }
+        _ => (cond_code, then, otherwise),
+    };
+    vec![
+        compare,
+        asm_ast::Instruction::Mov {
+            src: gen_operand(otherwise),
+            dst: dst.clone(),
+        },
+        asm_ast::Instruction::Cmov {
+            cond_code,
+            src: gen_operand(then),
+            dst,
+        },
+    ]
+}
+
+fn evaluate_cond_code(cond_code: asm_ast::CondCode, src1: i32, src2: i32) -> bool {
+    match cond_code {
+        asm_ast::CondCode::E => src1 == src2,
+        asm_ast::CondCode::NE => src1 != src2,
+        asm_ast::CondCode::G => src1 > src2,
+        asm_ast::CondCode::GE => src1 >= src2,
+        asm_ast::CondCode::L => src1 < src2,
+        asm_ast::CondCode::LE => src1 <= src2,
+    }
+}
+
+fn relational_cond_code(operator: irc::BinaryOp) -> Option<asm_ast::CondCode> {
+    match operator {
+        irc::BinaryOp::Equal => Some(asm_ast::CondCode::E),
+        irc::BinaryOp::NotEqual => Some(asm_ast::CondCode::NE),
+        irc::BinaryOp::LessThan => Some(asm_ast::CondCode::L),
+        irc::BinaryOp::LessOrEqual => Some(asm_ast::CondCode::LE),
+        irc::BinaryOp::GreaterThan => Some(asm_ast::CondCode::G),
+        irc::BinaryOp::GreaterOrEqual => Some(asm_ast::CondCode::GE),
+        _ => None,
+    }
+}
+
+fn negate_cond_code(cond_code: asm_ast::CondCode) -> asm_ast::CondCode {
+    match cond_code {
+        asm_ast::CondCode::E => asm_ast::CondCode::NE,
+        asm_ast::CondCode::NE => asm_ast::CondCode::E,
+        asm_ast::CondCode::G => asm_ast::CondCode::LE,
+        asm_ast::CondCode::GE => asm_ast::CondCode::L,
+        asm_ast::CondCode::L => asm_ast::CondCode::GE,
+        asm_ast::CondCode::LE => asm_ast::CondCode::G,
+    }
+}
+
+/// Compares `src1` with `src2`, returning the comparison and the condition code
+/// that tests `src1 {cond_code} src2` after it. A constant has to be the first
+/// operand of `cmp`, and comparisons with zero use `test`.
+fn gen_compare(
+    cond_code: asm_ast::CondCode,
+    src1: irc::Value,
+    src2: irc::Value,
+) -> (asm_ast::Instruction, asm_ast::CondCode) {
+    let (cond_code, src1, src2) = match (&src1, &src2) {
+        (irc::Value::Constant(_), irc::Value::Var(_)) => (swap_cond_code(cond_code), src2, src1),
+        _ => (cond_code, src1, src2),
+    };
+    let compare = match (src1, src2) {
+        (src @ irc::Value::Var(_), irc::Value::Constant(0)) => {
+            let operand = gen_operand(src);
+            asm_ast::Instruction::Test {
+                operand1: operand.clone(),
+                operand2: operand,
+            }
+        }
+        (src1, src2) => asm_ast::Instruction::Cmp {
+            operand1: gen_operand(src2),
+            operand2: gen_operand(src1),
+        },
+    };
+    (compare, cond_code)
+}
+
+/// The condition code that gives the same result with the operands swapped.
+fn swap_cond_code(cond_code: asm_ast::CondCode) -> asm_ast::CondCode {
+    match cond_code {
+        asm_ast::CondCode::E => asm_ast::CondCode::E,
+        asm_ast::CondCode::NE => asm_ast::CondCode::NE,
+        asm_ast::CondCode::G => asm_ast::CondCode::L,
+        asm_ast::CondCode::GE => asm_ast::CondCode::LE,
+        asm_ast::CondCode::L => asm_ast::CondCode::G,
+        asm_ast::CondCode::LE => asm_ast::CondCode::GE,
+    }
+}
+
+/// Additions of a variable and a constant or of two variables use `lea`, which
+/// doesn't need `dst` to start out as one of the operands.
+fn gen_add(src1: irc::Value, src2: irc::Value, dst: asm_ast::Operand) -> Vec<asm_ast::Instruction> {
+    match (src1, src2) {
+        (base @ irc::Value::Var(_), irc::Value::Constant(displacement))
+        | (irc::Value::Constant(displacement), base @ irc::Value::Var(_)) => {
+            vec![asm_ast::Instruction::Lea {
+                base: gen_operand(base),
+                index: None,
+                scale: 1,
+                displacement,
+                dst,
+            }]
+        }
+        (base @ irc::Value::Var(_), index @ irc::Value::Var(_)) => {
+            vec![asm_ast::Instruction::Lea {
+                base: gen_operand(base),
+                index: Some(gen_operand(index)),
+                scale: 1,
+                displacement: 0,
+                dst,
+            }]
+        }
+        (src1, src2) => gen_binary_ins(asm_ast::BinaryOp::Add, src1, src2, dst),
+    }
+}
+
+/// Multiplications by a constant use `lea` or shifts where they can.
+fn gen_multiply(
+    src1: irc::Value,
+    src2: irc::Value,
+    dst: asm_ast::Operand,
+) -> Vec<asm_ast::Instruction> {
+    let (src, factor) = match (src1, src2) {
+        (src @ irc::Value::Var(_), irc::Value::Constant(factor))
+        | (irc::Value::Constant(factor), src @ irc::Value::Var(_)) => (gen_operand(src), factor),
+        (src1, src2) => return gen_binary_ins(asm_ast::BinaryOp::Mult, src1, src2, dst),
+    };
+    let mov = asm_ast::Instruction::Mov {
+        src: src.clone(),
+        dst: dst.clone(),
+    };
+    match factor {
+        0 => vec![asm_ast::Instruction::Mov {
+            src: asm_ast::Operand::Imm(0),
+            dst,
+        }],
+        1 => vec![mov],
+        -1 => vec![
+            mov,
+            asm_ast::Instruction::Unary {
+                operator: asm_ast::UnaryOp::Neg,
+                operand: dst,
+            },
+        ],
+        3 | 5 | 9 => vec![asm_ast::Instruction::Lea {
+            base: src.clone(),
+            index: Some(src),
+            scale: (factor - 1) as u8,
+            displacement: 0,
+            dst,
+        }],
+        factor if factor > 0 && factor.count_ones() == 1 => vec![
+            mov,
+            asm_ast::Instruction::Binary {
+                operator: asm_ast::BinaryOp::Shl,
+                operand1: asm_ast::Operand::Imm(factor.trailing_zeros() as i32),
+                operand2: dst,
+            },
+        ],
+        factor => vec![
+            mov,
+            asm_ast::Instruction::Binary {
+                operator: asm_ast::BinaryOp::Mult,
+                operand1: asm_ast::Operand::Imm(factor),
+                operand2: dst,
+            },
+        ],
+    }
+}
+
+/// Divisions by a constant use shifts for powers of two and a multiplication
+/// by a magic number otherwise, the rest go through `idiv`.
+fn gen_division(
+    remainder: bool,
+    src1: irc::Value,
+    src2: irc::Value,
+    dst: asm_ast::Operand,
+) -> Vec<asm_ast::Instruction> {
+    let dividend = gen_operand(src1);
+    let divisor = match src2 {
+        irc::Value::Constant(divisor)
+            if divisor != 0
+                && divisor != i32::MIN
+                && matches!(dividend, asm_ast::Operand::Pseudo(_))
+                && dividend != dst =>
+        {
+            divisor
+        }
+        src2 => return gen_idiv(remainder, dividend, gen_operand(src2), dst),
+    };
+    let mov = asm_ast::Instruction::Mov {
+        src: dividend.clone(),
+        dst: dst.clone(),
+    };
+    match divisor.unsigned_abs() {
+        1 if remainder => vec![asm_ast::Instruction::Mov {
+            src: asm_ast::Operand::Imm(0),
+            dst,
+        }],
+        1 if divisor > 0 => vec![mov],
+        1 => vec![
+            mov,
+            asm_ast::Instruction::Unary {
+                operator: asm_ast::UnaryOp::Neg,
+                operand: dst,
+            },
+        ],
+        magnitude if magnitude.is_power_of_two() => {
+            gen_power_of_two_division(remainder, divisor, dividend, dst)
+        }
+        _ => gen_magic_division(remainder, divisor, dividend, dst),
+    }
+}
+
+fn gen_idiv(
+    remainder: bool,
+    dividend: asm_ast::Operand,
+    divisor: asm_ast::Operand,
+    dst: asm_ast::Operand,
+) -> Vec<asm_ast::Instruction> {
+    let result = if remainder {
+        asm_ast::Register::DX
+    } else {
+        asm_ast::Register::AX
+    };
+    vec![
+        asm_ast::Instruction::Mov {
+            src: dividend,
+            dst: asm_ast::Operand::Register(asm_ast::Register::AX),
+        },
+        asm_ast::Instruction::Cdq,
+        asm_ast::Instruction::Idiv(divisor),
+        asm_ast::Instruction::Mov {
+            src: asm_ast::Operand::Register(result),
+            dst,
+        },
+    ]
+}
+
+/// Shifting right rounds towards negative infinity, so negative dividends are
+/// biased by `2^k - 1` first to round towards zero like `idiv`.
+fn gen_power_of_two_division(
+    remainder: bool,
+    divisor: i32,
+    dividend: asm_ast::Operand,
+    dst: asm_ast::Operand,
+) -> Vec<asm_ast::Instruction> {
+    let shift = divisor.unsigned_abs().trailing_zeros() as i32;
+    let binary = |operator, operand1| asm_ast::Instruction::Binary {
+        operator,
+        operand1,
+        operand2: dst.clone(),
+    };
+    let mut instructions = vec![
+        asm_ast::Instruction::Mov {
+            src: dividend.clone(),
+            dst: dst.clone(),
+        },
+        binary(asm_ast::BinaryOp::Shr, asm_ast::Operand::Imm(31)),
+        binary(
+            asm_ast::BinaryOp::ShrLogical,
+            asm_ast::Operand::Imm(32 - shift),
+        ),
+        binary(asm_ast::BinaryOp::Add, dividend.clone()),
+    ];
+    if remainder {
+        // The remainder takes the sign of the dividend, whatever the divisor's is.
+        instructions.extend([
+            binary(asm_ast::BinaryOp::And, asm_ast::Operand::Imm(-(1 << shift))),
+            asm_ast::Instruction::Unary {
+                operator: asm_ast::UnaryOp::Neg,
+                operand: dst.clone(),
+            },
+            binary(asm_ast::BinaryOp::Add, dividend),
+        ]);
+    } else {
+        instructions.push(binary(asm_ast::BinaryOp::Shr, asm_ast::Operand::Imm(shift)));
+        if divisor < 0 {
+            instructions.push(asm_ast::Instruction::Unary {
+                operator: asm_ast::UnaryOp::Neg,
+                operand: dst,
+            });
+        }
+    }
+    instructions
+}
+
+/// Takes the high half of the dividend times a magic number, see Hacker's
+/// Delight 10-4. The quotient ends up in `EDX`.
+fn gen_magic_division(
+    remainder: bool,
+    divisor: i32,
+    dividend: asm_ast::Operand,
+    dst: asm_ast::Operand,
+) -> Vec<asm_ast::Instruction> {
+    let (magic, shift) = magic_number(divisor);
+    let ax = asm_ast::Operand::Register(asm_ast::Register::AX);
+    let dx = asm_ast::Operand::Register(asm_ast::Register::DX);
+    let binary = |operator, operand1, operand2: &asm_ast::Operand| asm_ast::Instruction::Binary {
+        operator,
+        operand1,
+        operand2: operand2.clone(),
+    };
+    let mut instructions = vec![
+        asm_ast::Instruction::Mov {
+            src: asm_ast::Operand::Imm(magic),
+            dst: ax.clone(),
+        },
+        asm_ast::Instruction::Imul(dividend.clone()),
+    ];
+    if divisor > 0 && magic < 0 {
+        instructions.push(binary(asm_ast::BinaryOp::Add, dividend.clone(), &dx));
+    } else if divisor < 0 && magic > 0 {
+        instructions.push(binary(asm_ast::BinaryOp::Sub, dividend.clone(), &dx));
+    }
+    if shift > 0 {
+        instructions.push(binary(
+            asm_ast::BinaryOp::Shr,
+            asm_ast::Operand::Imm(shift),
+            &dx,
+        ));
+    }
+    // Add one for negative quotients to round towards zero.
+    instructions.extend([
+        asm_ast::Instruction::Mov {
+            src: dx.clone(),
+            dst: ax.clone(),
+        },
+        binary(
+            asm_ast::BinaryOp::ShrLogical,
+            asm_ast::Operand::Imm(31),
+            &ax,
+        ),
+        binary(asm_ast::BinaryOp::Add, ax, &dx),
+    ]);
+    if remainder {
+        instructions.extend([
+            binary(asm_ast::BinaryOp::Mult, asm_ast::Operand::Imm(divisor), &dx),
+            asm_ast::Instruction::Mov {
+                src: dividend,
+                dst: dst.clone(),
+            },
+            binary(asm_ast::BinaryOp::Sub, dx, &dst),
+        ]);
+    } else {
+        instructions.push(asm_ast::Instruction::Mov { src: dx, dst });
+    }
+    instructions
+}
+
+/// The magic number and shift for signed division by `divisor`, which must
+/// not be `0`, `1`, `-1` or `i32::MIN`.
+fn magic_number(divisor: i32) -> (i32, i32) {
+    const TWO_31: u32 = 1 << 31;
+    let magnitude = divisor.unsigned_abs();
+    let t = TWO_31 + (divisor as u32 >> 31);
+    let anc = t - 1 - t % magnitude;
+    let mut p = 31;
+    let (mut q1, mut r1) = (TWO_31 / anc, TWO_31 % anc);
+    let (mut q2, mut r2) = (TWO_31 / magnitude, TWO_31 % magnitude);
+    loop {
+        p += 1;
+        q1 = q1.wrapping_mul(2);
+        r1 = r1.wrapping_mul(2);
+        if r1 >= anc {
+            q1 = q1.wrapping_add(1);
+            r1 = r1.wrapping_sub(anc);
+        }
+        q2 = q2.wrapping_mul(2);
+        r2 = r2.wrapping_mul(2);
+        if r2 >= magnitude {
+            q2 = q2.wrapping_add(1);
+            r2 = r2.wrapping_sub(magnitude);
+        }
+        let delta = magnitude - r2;
+        if q1 > delta || (q1 == delta && r1 != 0) {
+            break;
+        }
+    }
+    let magic = q2.wrapping_add(1) as i32;
+    let magic = if divisor < 0 {
+        magic.wrapping_neg()
+    } else {
+        magic
+    };
+    (magic, p - 32)
+}
+
+/// Operators where the constant can go second.
+fn gen_commutative(
+    operator: asm_ast::BinaryOp,
+    src1: irc::Value,
+    src2: irc::Value,
+    dst: asm_ast::Operand,
+) -> Vec<asm_ast::Instruction> {
+    match (src1, src2) {
+        (src1 @ irc::Value::Constant(_), src2 @ irc::Value::Var(_)) => {
+            gen_binary_ins(operator, src2, src1, dst)
+        }
+        (src1, src2) => gen_binary_ins(operator, src1, src2, dst),
+    }
+}
+
+/// Shifts by a variable amount take their count in `CL`.
+fn gen_shift(
+    operator: asm_ast::BinaryOp,
+    src1: irc::Value,
+    src2: irc::Value,
+    dst: asm_ast::Operand,
+) -> Vec<asm_ast::Instruction> {
+    if let irc::Value::Constant(_) = src2 {
+        return gen_binary_ins(operator, src1, src2, dst);
+    }
+    vec![
+        asm_ast::Instruction::Mov {
+            src: gen_operand(src2),
+            dst: asm_ast::Operand::Register(asm_ast::Register::CX),
+        },
+        asm_ast::Instruction::Mov {
+            src: gen_operand(src1),
+            dst: dst.clone(),
+        },
+        asm_ast::Instruction::Binary {
+            operator,
+            operand1: asm_ast::Operand::Register(asm_ast::Register::CX),
+            operand2: dst,
+        },
+    ]
+}
+
 fn gen_binary_ins(
     operator: asm_ast::BinaryOp,
     src1: irc::Value,
@@ -205,181 +758,286 @@ fn gen_binary_ins(
     ]
 }
 
-pub fn replace_pseudo(program: &mut asm_ast::Program) {
+/// Replaces pseudo registers with stack slots, returning the size of the frame.
+/// Pseudos that are never live at the same time share a slot, and the frame
+/// keeps `%rsp` 16-byte aligned, counting the registers pushed below it.
+pub fn replace_pseudo(program: &mut asm_ast::Program) -> usize {
     match program {
         asm_ast::Program::Function(function) => {
-            function.instructons.iter_mut().for_each(|ins| match ins {
-                asm_ast::Instruction::Mov { src, dst } => {
-                    replace_operand(src);
-                    replace_operand(dst);
-                }
-                asm_ast::Instruction::Binary {
-                    operator: _,
-                    operand1,
-                    operand2,
-                }
-                | asm_ast::Instruction::Cmp { operand1, operand2 } => {
-                    replace_operand(operand1);
-                    replace_operand(operand2);
-                }
-                asm_ast::Instruction::Idiv(operand)
-                | asm_ast::Instruction::SetCC {
-                    cond_code: _,
-                    operand,
-                }
-                | asm_ast::Instruction::Unary {
-                    operator: _,
-                    operand,
-                } => {
-                    replace_operand(operand);
+            let (slots, slots_size) = layout_frame(&function.instructons);
+            for ins in &mut function.instructons {
+                for operand in ins.operands_mut() {
+                    if let asm_ast::Operand::Pseudo(name) = operand {
+                        // Only pseudos that are never computed have no slot, and
+                        // only annotations refer to those.
+                        if let Some(&offset) = slots.get(name.as_str()) {
+                            *operand = asm_ast::Operand::Stack(offset);
+                        }
+                    }
                 }
-                asm_ast::Instruction::Return
-                | asm_ast::Instruction::Cdq
-                | asm_ast::Instruction::AllocateStack(_)
-                | asm_ast::Instruction::Label(_)
-                | asm_ast::Instruction::Jmp(_)
-                | asm_ast::Instruction::JumpCC {
-                    cond_code: _,
-                    target: _,
-                } => {}
-            });
+            }
+            let pushed = function
+                .instructons
+                .iter()
+                .filter(|ins| matches!(ins, asm_ast::Instruction::Push(_)))
+                .count()
+                * 8;
+            (slots_size + pushed).next_multiple_of(X86_64.data_layout().stack_alignment) - pushed
         }
     }
 }
 
+/// Assigns each pseudo the offset of its slot below `%rbp`, first fit over
+/// the slots of pseudos it doesn't interfere with. Returns the offsets and
+/// the bytes the slots take up.
+fn layout_frame(instructions: &[asm_ast::Instruction]) -> (HashMap<String, usize>, usize) {
+    let mut slots: Vec<usize> = Vec::new();
+    let mut offsets: HashMap<String, usize> = HashMap::new();
+    // Every value is an `int` for now.
+    let slot_size = X86_64.data_layout().int_size;
+    let mut size = 0;
+    for (pseudo, neighbors) in register_allocation::pseudo_interference(instructions) {
+        let taken: HashSet<usize> = neighbors
+            .iter()
+            .filter_map(|neighbor| offsets.get(neighbor).copied())
+            .collect();
+        let offset = match slots.iter().find(|offset| !taken.contains(offset)) {
+            Some(&offset) => offset,
+            None => {
+                size = (size + slot_size).next_multiple_of(slot_size);
+                slots.push(size);
+                size
+            }
+        };
+        offsets.insert(pseudo, offset);
+    }
+    (offsets, size)
+}
+
 pub fn fix_instructions(program: &mut asm_ast::Program, stack_allocation: usize) {
     match program {
         asm_ast::Program::Function(function) => {
-            function.instructons =
-                iter::once(asm_ast::Instruction::AllocateStack(stack_allocation))
-                    .chain(function.instructons.iter().flat_map(|ins| match ins {
+            function.instructons = (stack_allocation > 0)
+                .then_some(asm_ast::Instruction::AllocateStack(stack_allocation))
+                .into_iter()
+                .chain(function.instructons.iter().flat_map(|ins| match ins {
+                    asm_ast::Instruction::Mov {
+                        src: asm_ast::Operand::Stack(src),
+                        dst: asm_ast::Operand::Stack(dst),
+                    } => vec![
+                        asm_ast::Instruction::Mov {
+                            src: asm_ast::Operand::Stack(*src),
+                            dst: asm_ast::Operand::Register(asm_ast::Register::R10),
+                        },
                         asm_ast::Instruction::Mov {
-                            src: asm_ast::Operand::Stack(src),
-                            dst: asm_ast::Operand::Stack(dst),
-                        } => vec![
+                            src: asm_ast::Operand::Register(asm_ast::Register::R10),
+                            dst: asm_ast::Operand::Stack(*dst),
+                        },
+                    ],
+                    asm_ast::Instruction::Idiv(src @ asm_ast::Operand::Imm(_)) => {
+                        vec![
+                            asm_ast::Instruction::Mov {
+                                src: src.clone(),
+                                dst: asm_ast::Operand::Register(asm_ast::Register::R10),
+                            },
+                            asm_ast::Instruction::Idiv(asm_ast::Operand::Register(
+                                asm_ast::Register::R10,
+                            )),
+                        ]
+                    }
+                    asm_ast::Instruction::Imul(src @ asm_ast::Operand::Imm(_)) => {
+                        vec![
+                            asm_ast::Instruction::Mov {
+                                src: src.clone(),
+                                dst: asm_ast::Operand::Register(asm_ast::Register::R10),
+                            },
+                            asm_ast::Instruction::Imul(asm_ast::Operand::Register(
+                                asm_ast::Register::R10,
+                            )),
+                        ]
+                    }
+                    asm_ast::Instruction::Test {
+                        operand1: asm_ast::Operand::Stack(operand1),
+                        operand2: asm_ast::Operand::Stack(operand2),
+                    } if operand1 == operand2 => vec![asm_ast::Instruction::Cmp {
+                        operand1: asm_ast::Operand::Imm(0),
+                        operand2: asm_ast::Operand::Stack(*operand2),
+                    }],
+                    asm_ast::Instruction::Lea {
+                        base,
+                        index,
+                        scale,
+                        displacement,
+                        dst,
+                    } => fix_lea(base, index.as_ref(), *scale, *displacement, dst),
+                    asm_ast::Instruction::Cmov {
+                        cond_code,
+                        src,
+                        dst,
+                    } if matches!(src, asm_ast::Operand::Imm(_))
+                        || matches!(dst, asm_ast::Operand::Stack(_)) =>
+                    {
+                        fix_cmov(*cond_code, src, dst)
+                    }
+                    asm_ast::Instruction::Binary {
+                        operator:
+                            operator @ (asm_ast::BinaryOp::Add
+                            | asm_ast::BinaryOp::Sub
+                            | asm_ast::BinaryOp::And
+                            | asm_ast::BinaryOp::Xor
+                            | asm_ast::BinaryOp::Or),
+                        operand1: asm_ast::Operand::Stack(src),
+                        operand2: asm_ast::Operand::Stack(dst),
+                    } => {
+                        vec![
                             asm_ast::Instruction::Mov {
                                 src: asm_ast::Operand::Stack(*src),
                                 dst: asm_ast::Operand::Register(asm_ast::Register::R10),
                             },
+                            asm_ast::Instruction::Binary {
+                                operator: *operator,
+                                operand1: asm_ast::Operand::Register(asm_ast::Register::R10),
+                                operand2: asm_ast::Operand::Stack(*dst),
+                            },
+                        ]
+                    }
+                    asm_ast::Instruction::Binary {
+                        operator: operator @ asm_ast::BinaryOp::Mult,
+                        operand1,
+                        operand2: src @ asm_ast::Operand::Stack(_),
+                    } => {
+                        vec![
                             asm_ast::Instruction::Mov {
-                                src: asm_ast::Operand::Register(asm_ast::Register::R10),
-                                dst: asm_ast::Operand::Stack(*dst),
+                                src: src.clone(),
+                                dst: asm_ast::Operand::Register(asm_ast::Register::R11),
                             },
-                        ],
-                        asm_ast::Instruction::Idiv(src @ asm_ast::Operand::Imm(_)) => {
-                            vec![
-                                asm_ast::Instruction::Mov {
-                                    src: src.clone(),
-                                    dst: asm_ast::Operand::Register(asm_ast::Register::R10),
-                                },
-                                asm_ast::Instruction::Idiv(asm_ast::Operand::Register(
-                                    asm_ast::Register::R10,
-                                )),
-                            ]
-                        }
-                        asm_ast::Instruction::Binary {
-                            operator:
-                                operator @ (asm_ast::BinaryOp::Add
-                                | asm_ast::BinaryOp::Sub
-                                | asm_ast::BinaryOp::And
-                                | asm_ast::BinaryOp::Xor
-                                | asm_ast::BinaryOp::Or),
-                            operand1: asm_ast::Operand::Stack(src),
-                            operand2: asm_ast::Operand::Stack(dst),
-                        } => {
-                            vec![
-                                asm_ast::Instruction::Mov {
-                                    src: asm_ast::Operand::Stack(*src),
-                                    dst: asm_ast::Operand::Register(asm_ast::Register::R10),
-                                },
-                                asm_ast::Instruction::Binary {
-                                    operator: *operator,
-                                    operand1: asm_ast::Operand::Register(asm_ast::Register::R10),
-                                    operand2: asm_ast::Operand::Stack(*dst),
-                                },
-                            ]
-                        }
-                        asm_ast::Instruction::Binary {
-                            operator: operator @ asm_ast::BinaryOp::Mult,
-                            operand1,
-                            operand2: src @ asm_ast::Operand::Stack(_),
-                        } => {
-                            vec![
-                                asm_ast::Instruction::Mov {
-                                    src: src.clone(),
-                                    dst: asm_ast::Operand::Register(asm_ast::Register::R11),
-                                },
-                                asm_ast::Instruction::Binary {
-                                    operator: *operator,
-                                    operand1: operand1.clone(),
-                                    operand2: asm_ast::Operand::Register(asm_ast::Register::R11),
-                                },
-                                asm_ast::Instruction::Mov {
-                                    src: asm_ast::Operand::Register(asm_ast::Register::R11),
-                                    dst: src.clone(),
-                                },
-                            ]
-                        }
-                        asm_ast::Instruction::Binary {
-                            operator: operator @ (asm_ast::BinaryOp::Shr | asm_ast::BinaryOp::Shl),
-                            operand1: src @ asm_ast::Operand::Stack(_),
-                            operand2,
-                        } => {
-                            vec![
-                                asm_ast::Instruction::Mov {
-                                    src: src.clone(),
-                                    dst: asm_ast::Operand::Register(asm_ast::Register::CX),
-                                },
-                                asm_ast::Instruction::Binary {
-                                    operator: *operator,
-                                    operand1: asm_ast::Operand::Register(asm_ast::Register::CX),
-                                    operand2: operand2.clone(),
-                                },
-                            ]
-                        }
-                        asm_ast::Instruction::Cmp {
-                            operand1: asm_ast::Operand::Stack(operand1),
-                            operand2: asm_ast::Operand::Stack(operand2),
-                        } => {
-                            vec![
-                                asm_ast::Instruction::Mov {
-                                    src: asm_ast::Operand::Stack(*operand1),
-                                    dst: asm_ast::Operand::Register(asm_ast::Register::R10),
-                                },
-                                asm_ast::Instruction::Cmp {
-                                    operand1: asm_ast::Operand::Register(asm_ast::Register::R10),
-                                    operand2: asm_ast::Operand::Stack(*operand2),
-                                },
-                            ]
-                        }
-                        asm_ast::Instruction::Cmp {
-                            operand1,
-                            operand2: operand2 @ asm_ast::Operand::Imm(_),
-                        } => {
-                            vec![
-                                asm_ast::Instruction::Mov {
-                                    src: operand2.clone(),
-                                    dst: asm_ast::Operand::Register(asm_ast::Register::R11),
-                                },
-                                asm_ast::Instruction::Cmp {
-                                    operand1: operand1.clone(),
-                                    operand2: asm_ast::Operand::Register(asm_ast::Register::R11),
-                                },
-                            ]
-                        }
-                        _ => vec![ins.clone()],
-                    }))
-                    .collect();
+                            asm_ast::Instruction::Binary {
+                                operator: *operator,
+                                operand1: operand1.clone(),
+                                operand2: asm_ast::Operand::Register(asm_ast::Register::R11),
+                            },
+                            asm_ast::Instruction::Mov {
+                                src: asm_ast::Operand::Register(asm_ast::Register::R11),
+                                dst: src.clone(),
+                            },
+                        ]
+                    }
+                    asm_ast::Instruction::Cmp {
+                        operand1: asm_ast::Operand::Stack(operand1),
+                        operand2: asm_ast::Operand::Stack(operand2),
+                    } => {
+                        vec![
+                            asm_ast::Instruction::Mov {
+                                src: asm_ast::Operand::Stack(*operand1),
+                                dst: asm_ast::Operand::Register(asm_ast::Register::R10),
+                            },
+                            asm_ast::Instruction::Cmp {
+                                operand1: asm_ast::Operand::Register(asm_ast::Register::R10),
+                                operand2: asm_ast::Operand::Stack(*operand2),
+                            },
+                        ]
+                    }
+                    asm_ast::Instruction::Cmp {
+                        operand1,
+                        operand2: operand2 @ asm_ast::Operand::Imm(_),
+                    } => {
+                        vec![
+                            asm_ast::Instruction::Mov {
+                                src: operand2.clone(),
+                                dst: asm_ast::Operand::Register(asm_ast::Register::R11),
+                            },
+                            asm_ast::Instruction::Cmp {
+                                operand1: operand1.clone(),
+                                operand2: asm_ast::Operand::Register(asm_ast::Register::R11),
+                            },
+                        ]
+                    }
+                    _ => vec![ins.clone()],
+                }))
+                .collect();
+        }
+    }
+}
+
+/// `lea` only takes registers, operands on the stack go through `R10` and `R11`.
+fn fix_lea(
+    base: &asm_ast::Operand,
+    index: Option<&asm_ast::Operand>,
+    scale: u8,
+    displacement: i32,
+    dst: &asm_ast::Operand,
+) -> Vec<asm_ast::Instruction> {
+    let mut instructions = Vec::new();
+    let mut load = |operand: &asm_ast::Operand, register| match operand {
+        asm_ast::Operand::Stack(_) => {
+            instructions.push(asm_ast::Instruction::Mov {
+                src: operand.clone(),
+                dst: asm_ast::Operand::Register(register),
+            });
+            asm_ast::Operand::Register(register)
         }
+        _ => operand.clone(),
+    };
+    let base = load(base, asm_ast::Register::R10);
+    let index = index.map(|index| load(index, asm_ast::Register::R11));
+    let lea_dst = match dst {
+        asm_ast::Operand::Stack(_) => asm_ast::Operand::Register(asm_ast::Register::R10),
+        _ => dst.clone(),
+    };
+    instructions.push(asm_ast::Instruction::Lea {
+        base,
+        index,
+        scale,
+        displacement,
+        dst: lea_dst.clone(),
+    });
+    if lea_dst != *dst {
+        instructions.push(asm_ast::Instruction::Mov {
+            src: lea_dst,
+            dst: dst.clone(),
+        });
     }
+    instructions
 }
 
-fn replace_operand(operand: &mut asm_ast::Operand) {
-    if let asm_ast::Operand::Pseudo(counter) = operand {
-        let (_, index) = counter
-            .split_once('.')
-            .expect("format of counter is temp.{index}");
-        let index: usize = index.parse().expect("index is a usize");
-        *operand = asm_ast::Operand::Stack(index * 4);
+/// `cmov` needs a register destination and can't take an immediate.
+fn fix_cmov(
+    cond_code: asm_ast::CondCode,
+    src: &asm_ast::Operand,
+    dst: &asm_ast::Operand,
+) -> Vec<asm_ast::Instruction> {
+    let mut instructions = Vec::new();
+    let src = match src {
+        asm_ast::Operand::Imm(_) => {
+            instructions.push(asm_ast::Instruction::Mov {
+                src: src.clone(),
+                dst: asm_ast::Operand::Register(asm_ast::Register::R10),
+            });
+            asm_ast::Operand::Register(asm_ast::Register::R10)
+        }
+        _ => src.clone(),
+    };
+    match dst {
+        asm_ast::Operand::Stack(_) => instructions.extend([
+            asm_ast::Instruction::Mov {
+                src: dst.clone(),
+                dst: asm_ast::Operand::Register(asm_ast::Register::R11),
+            },
+            asm_ast::Instruction::Cmov {
+                cond_code,
+                src,
+                dst: asm_ast::Operand::Register(asm_ast::Register::R11),
+            },
+            asm_ast::Instruction::Mov {
+                src: asm_ast::Operand::Register(asm_ast::Register::R11),
+                dst: dst.clone(),
+            },
+        ]),
+        _ => instructions.push(asm_ast::Instruction::Cmov {
+            cond_code,
+            src,
+            dst: dst.clone(),
+        }),
     }
+    instructions
 }
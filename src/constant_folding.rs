@@ -0,0 +1,150 @@
+use crate::irc::{BinaryOp, Function, Instruction, Program, UnaryOp, Value};
+
+/// Folds operations on constants and simplifies algebraic identities.
+/// Returns `true` if the program changed.
+pub fn fold_constants(program: &mut Program) -> bool {
+    match program {
+        Program::Function(function) => fold_function(function),
+    }
+}
+
+fn fold_function(function: &mut Function) -> bool {
+    let mut changed = false;
+    function.instructons = std::mem::take(&mut function.instructons)
+        .into_iter()
+        .filter_map(|ins| {
+            let folded = fold_instruction(&ins);
+            if folded.as_ref() != Some(&ins) {
+                changed = true;
+            }
+            folded
+        })
+        .collect();
+    changed
+}
+
+/// Returns the simplified instruction, or `None` if it can be removed.
+fn fold_instruction(instruction: &Instruction) -> Option<Instruction> {
+    match instruction {
+        Instruction::Unary {
+            operator,
+            src: Value::Constant(src),
+            dst,
+        } => Some(Instruction::Copy {
+            src: Value::Constant(fold_unary(*operator, *src)),
+            dst: dst.clone(),
+        }),
+        Instruction::Binary {
+            operator,
+            src1,
+            src2,
+            dst,
+        } => {
+            let folded = match (src1, src2) {
+                (Value::Constant(src1), Value::Constant(src2)) => {
+                    fold_binary(*operator, *src1, *src2).map(Value::Constant)
+                }
+                _ => simplify_binary(*operator, src1, src2),
+            };
+            Some(folded.map_or_else(
+                || instruction.clone(),
+                |src| Instruction::Copy {
+                    src,
+                    dst: dst.clone(),
+                },
+            ))
+        }
+        Instruction::Select {
+            condition,
+            src1,
+            src2,
+            dst,
+        } => {
+            let src = match condition {
+                Value::Constant(0) => src2,
+                Value::Constant(_) => src1,
+                Value::Var(_) if src1 == src2 => src1,
+                Value::Var(_) => return Some(instruction.clone()),
+            };
+            Some(Instruction::Copy {
+                src: src.clone(),
+                dst: dst.clone(),
+            })
+        }
+        Instruction::JumpIfZero {
+            condition: Value::Constant(condition),
+            target,
+        } => (*condition == 0).then(|| Instruction::Jump {
+            target: target.clone(),
+        }),
+        Instruction::JumpIfNotZero {
+            condition: Value::Constant(condition),
+            target,
+        } => (*condition != 0).then(|| Instruction::Jump {
+            target: target.clone(),
+        }),
+        _ => Some(instruction.clone()),
+    }
+}
+
+pub fn fold_unary(operator: UnaryOp, src: i32) -> i32 {
+    match operator {
+        UnaryOp::Complement => !src,
+        UnaryOp::Negate => src.wrapping_neg(),
+        UnaryOp::Not => i32::from(src == 0),
+    }
+}
+
+/// Evaluates `src1 operator src2` the way the generated code would at runtime.
+/// Returns `None` for operations that trap or are undefined (division by zero,
+/// `INT_MIN / -1`, out of range shift counts), those are left for the runtime.
+pub fn fold_binary(operator: BinaryOp, src1: i32, src2: i32) -> Option<i32> {
+    let value = match operator {
+        BinaryOp::Add => src1.wrapping_add(src2),
+        BinaryOp::Subtract => src1.wrapping_sub(src2),
+        BinaryOp::Multiply => src1.wrapping_mul(src2),
+        BinaryOp::Divide => src1.checked_div(src2)?,
+        BinaryOp::Remainder => src1.checked_rem(src2)?,
+        BinaryOp::LeftShift => src1.wrapping_shl(shift_count(src2)?),
+        BinaryOp::RightShift => src1 >> shift_count(src2)?,
+        BinaryOp::BitAnd => src1 & src2,
+        BinaryOp::Xor => src1 ^ src2,
+        BinaryOp::BitOr => src1 | src2,
+        BinaryOp::Equal => i32::from(src1 == src2),
+        BinaryOp::NotEqual => i32::from(src1 != src2),
+        BinaryOp::LessThan => i32::from(src1 < src2),
+        BinaryOp::LessOrEqual => i32::from(src1 <= src2),
+        BinaryOp::GreaterThan => i32::from(src1 > src2),
+        BinaryOp::GreaterOrEqual => i32::from(src1 >= src2),
+    };
+    Some(value)
+}
+
+fn shift_count(count: i32) -> Option<u32> {
+    u32::try_from(count).ok().filter(|&count| count < i32::BITS)
+}
+
+/// Simplifies identities where only one operand is known, e.g. `x + 0` or `x * 0`.
+fn simplify_binary(operator: BinaryOp, src1: &Value, src2: &Value) -> Option<Value> {
+    match (operator, src1, src2) {
+        (
+            BinaryOp::Add
+            | BinaryOp::Subtract
+            | BinaryOp::BitOr
+            | BinaryOp::Xor
+            | BinaryOp::LeftShift
+            | BinaryOp::RightShift,
+            src,
+            Value::Constant(0),
+        )
+        | (BinaryOp::Add | BinaryOp::BitOr | BinaryOp::Xor, Value::Constant(0), src)
+        | (BinaryOp::Multiply | BinaryOp::Divide, src, Value::Constant(1))
+        | (BinaryOp::Multiply, Value::Constant(1), src)
+        | (BinaryOp::BitAnd, src, Value::Constant(-1))
+        | (BinaryOp::BitAnd, Value::Constant(-1), src) => Some(src.clone()),
+        (BinaryOp::Multiply | BinaryOp::BitAnd, _, Value::Constant(0))
+        | (BinaryOp::Multiply | BinaryOp::BitAnd, Value::Constant(0), _)
+        | (BinaryOp::Remainder, _, Value::Constant(1 | -1)) => Some(Value::Constant(0)),
+        _ => None,
+    }
+}
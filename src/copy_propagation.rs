@@ -0,0 +1,121 @@
+use std::collections::HashSet;
+
+use crate::{
+    cfg::Cfg,
+    dataflow::{self, Analysis, Direction},
+    irc::{Function, Instruction, Program, Value},
+};
+
+/// A copy `dst = src` that is known to hold.
+type Copy = (String, Value);
+
+/// Forward analysis of which copies reach each instruction.
+pub struct ReachingCopies {
+    all_copies: HashSet<Copy>,
+}
+
+impl ReachingCopies {
+    pub fn new(cfg: &Cfg<Instruction>) -> Self {
+        let all_copies = cfg
+            .blocks
+            .iter()
+            .flat_map(|block| &block.instructions)
+            .filter_map(|ins| match ins {
+                Instruction::Copy { src, dst } => Some((dst.clone(), src.clone())),
+                _ => None,
+            })
+            .collect();
+        Self { all_copies }
+    }
+}
+
+impl Analysis<Instruction> for ReachingCopies {
+    type Fact = HashSet<Copy>;
+
+    const DIRECTION: Direction = Direction::Forward;
+
+    fn boundary(&self) -> Self::Fact {
+        HashSet::new()
+    }
+
+    fn initial(&self) -> Self::Fact {
+        self.all_copies.clone()
+    }
+
+    fn meet(&self, fact1: &Self::Fact, fact2: &Self::Fact) -> Self::Fact {
+        fact1.intersection(fact2).cloned().collect()
+    }
+
+    fn transfer(&self, instruction: &Instruction, fact: &Self::Fact) -> Self::Fact {
+        let Some(dst) = instruction.dst() else {
+            return fact.clone();
+        };
+        if let Instruction::Copy { src, dst } = instruction {
+            if fact.contains(&(dst.clone(), src.clone())) {
+                return fact.clone();
+            }
+        }
+        let mut fact: Self::Fact = fact
+            .iter()
+            .filter(|(copy_dst, copy_src)| {
+                copy_dst != dst && !matches!(copy_src, Value::Var(name) if name == dst)
+            })
+            .cloned()
+            .collect();
+        if let Instruction::Copy { src, dst } = instruction {
+            if src != &Value::Var(dst.clone()) {
+                fact.insert((dst.clone(), src.clone()));
+            }
+        }
+        fact
+    }
+}
+
+/// Replaces uses of variables with the value they were copied from.
+/// Returns `true` if the program changed.
+pub fn propagate_copies(program: &mut Program) -> bool {
+    match program {
+        Program::Function(function) => propagate_function(function),
+    }
+}
+
+fn propagate_function(function: &mut Function) -> bool {
+    let mut cfg = Cfg::new(std::mem::take(&mut function.instructons));
+    let analysis = ReachingCopies::new(&cfg);
+    let solution = dataflow::solve(&cfg, &analysis);
+    let mut changed = false;
+
+    for index in 0..cfg.blocks.len() {
+        let facts = solution.instruction_facts(&analysis, &cfg, index);
+        let block = &mut cfg.blocks[index];
+        let instructions = std::mem::take(&mut block.instructions);
+        block.instructions = instructions
+            .into_iter()
+            .zip(facts)
+            .filter_map(|(mut ins, copies)| {
+                if let Instruction::Copy { src, dst } = &ins {
+                    let is_redundant = src == &Value::Var(dst.clone())
+                        || copies.contains(&(dst.clone(), src.clone()))
+                        || matches!(src, Value::Var(name)
+                            if copies.contains(&(name.clone(), Value::Var(dst.clone()))));
+                    if is_redundant {
+                        changed = true;
+                        return None;
+                    }
+                }
+                for src in ins.sources_mut() {
+                    let Value::Var(name) = src else {
+                        continue;
+                    };
+                    if let Some((_, value)) = copies.iter().find(|(dst, _)| dst == name) {
+                        *src = value.clone();
+                        changed = true;
+                    }
+                }
+                Some(ins)
+            })
+            .collect();
+    }
+    function.instructons = cfg.into_instructions();
+    changed
+}
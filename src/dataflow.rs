@@ -0,0 +1,126 @@
+use std::collections::VecDeque;
+
+use crate::cfg::Cfg;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    Forward,
+    Backward,
+}
+
+/// A dataflow analysis over the instructions of a [`Cfg`].
+pub trait Analysis<I> {
+    type Fact: Clone + PartialEq;
+
+    const DIRECTION: Direction;
+
+    /// The fact at the function entry (forward) or exit (backward).
+    fn boundary(&self) -> Self::Fact;
+
+    /// The starting fact of every other block, the identity of [`Analysis::meet`].
+    fn initial(&self) -> Self::Fact;
+
+    fn meet(&self, fact1: &Self::Fact, fact2: &Self::Fact) -> Self::Fact;
+
+    /// Computes the fact on the other side of `instruction`.
+    fn transfer(&self, instruction: &I, fact: &Self::Fact) -> Self::Fact;
+}
+
+/// Facts at the start and end of each block.
+#[derive(Debug)]
+pub struct Solution<F> {
+    pub block_in: Vec<F>,
+    pub block_out: Vec<F>,
+}
+
+pub fn solve<I, A: Analysis<I>>(cfg: &Cfg<I>, analysis: &A) -> Solution<A::Fact> {
+    let block_count = cfg.blocks.len();
+    let mut solution = Solution {
+        block_in: vec![analysis.initial(); block_count],
+        block_out: vec![analysis.initial(); block_count],
+    };
+    let mut worklist: VecDeque<usize> = match A::DIRECTION {
+        Direction::Forward => (0..block_count).collect(),
+        Direction::Backward => (0..block_count).rev().collect(),
+    };
+    let mut queued = vec![true; block_count];
+
+    while let Some(index) = worklist.pop_front() {
+        queued[index] = false;
+        let block = &cfg.blocks[index];
+        let (sources, dependents) = match A::DIRECTION {
+            Direction::Forward => (&block.predecessors, &block.successors),
+            Direction::Backward => (&block.successors, &block.predecessors),
+        };
+        let is_boundary = match A::DIRECTION {
+            Direction::Forward => index == 0,
+            Direction::Backward => block.successors.is_empty(),
+        };
+        let incoming = match A::DIRECTION {
+            Direction::Forward => &solution.block_out,
+            Direction::Backward => &solution.block_in,
+        };
+        let start = sources
+            .iter()
+            .map(|&source| &incoming[source])
+            .chain(is_boundary.then(|| analysis.boundary()).as_ref())
+            .fold(analysis.initial(), |acc, fact| analysis.meet(&acc, fact));
+        let end = transfer_block(analysis, &block.instructions, &start);
+
+        let (start_slot, end_slot) = match A::DIRECTION {
+            Direction::Forward => (&mut solution.block_in, &mut solution.block_out),
+            Direction::Backward => (&mut solution.block_out, &mut solution.block_in),
+        };
+        start_slot[index] = start;
+        if end_slot[index] != end {
+            end_slot[index] = end;
+            for &dependent in dependents {
+                if !queued[dependent] {
+                    queued[dependent] = true;
+                    worklist.push_back(dependent);
+                }
+            }
+        }
+    }
+    solution
+}
+
+fn transfer_block<I, A: Analysis<I>>(analysis: &A, instructions: &[I], start: &A::Fact) -> A::Fact {
+    let transfer = |fact: A::Fact, ins: &I| analysis.transfer(ins, &fact);
+    match A::DIRECTION {
+        Direction::Forward => instructions.iter().fold(start.clone(), transfer),
+        Direction::Backward => instructions.iter().rev().fold(start.clone(), transfer),
+    }
+}
+
+impl<F: Clone> Solution<F> {
+    /// Returns the fact on the incoming side of each instruction of `block`:
+    /// before it for forward analyses, after it for backward ones.
+    pub fn instruction_facts<I, A: Analysis<I, Fact = F>>(
+        &self,
+        analysis: &A,
+        cfg: &Cfg<I>,
+        block: usize,
+    ) -> Vec<F> {
+        let instructions = &cfg.blocks[block].instructions;
+        let mut facts = Vec::with_capacity(instructions.len());
+        match A::DIRECTION {
+            Direction::Forward => {
+                let mut fact = self.block_in[block].clone();
+                for ins in instructions {
+                    let next = analysis.transfer(ins, &fact);
+                    facts.push(std::mem::replace(&mut fact, next));
+                }
+            }
+            Direction::Backward => {
+                let mut fact = self.block_out[block].clone();
+                for ins in instructions.iter().rev() {
+                    let next = analysis.transfer(ins, &fact);
+                    facts.push(std::mem::replace(&mut fact, next));
+                }
+                facts.reverse();
+            }
+        }
+        facts
+    }
+}
@@ -0,0 +1,69 @@
+use std::collections::HashSet;
+
+use crate::{
+    cfg::Cfg,
+    dataflow::{self, Analysis, Direction},
+    irc::{Function, Instruction, Program, Value},
+};
+
+/// Backward analysis of which variables may be read later.
+pub struct Liveness;
+
+impl Analysis<Instruction> for Liveness {
+    type Fact = HashSet<String>;
+
+    const DIRECTION: Direction = Direction::Backward;
+
+    fn boundary(&self) -> Self::Fact {
+        HashSet::new()
+    }
+
+    fn initial(&self) -> Self::Fact {
+        HashSet::new()
+    }
+
+    fn meet(&self, fact1: &Self::Fact, fact2: &Self::Fact) -> Self::Fact {
+        fact1.union(fact2).cloned().collect()
+    }
+
+    fn transfer(&self, instruction: &Instruction, fact: &Self::Fact) -> Self::Fact {
+        let mut fact = fact.clone();
+        if let Some(dst) = instruction.dst() {
+            fact.remove(dst);
+        }
+        for src in instruction.sources() {
+            if let Value::Var(name) = src {
+                fact.insert(name.clone());
+            }
+        }
+        fact
+    }
+}
+
+/// Removes instructions whose result is never read.
+/// Returns `true` if the program changed.
+pub fn eliminate_dead_stores(program: &mut Program) -> bool {
+    match program {
+        Program::Function(function) => eliminate_function(function),
+    }
+}
+
+fn eliminate_function(function: &mut Function) -> bool {
+    let instruction_count = function.instructons.len();
+    let mut cfg = Cfg::new(std::mem::take(&mut function.instructons));
+    let solution = dataflow::solve(&cfg, &Liveness);
+
+    for index in 0..cfg.blocks.len() {
+        let live_after = solution.instruction_facts(&Liveness, &cfg, index);
+        let block = &mut cfg.blocks[index];
+        let instructions = std::mem::take(&mut block.instructions);
+        block.instructions = instructions
+            .into_iter()
+            .zip(live_after)
+            .filter(|(ins, live)| ins.dst().is_none_or(|dst| live.contains(dst)))
+            .map(|(ins, _)| ins)
+            .collect();
+    }
+    function.instructons = cfg.into_instructions();
+    function.instructons.len() != instruction_count
+}
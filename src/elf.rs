@@ -0,0 +1,310 @@
+use std::{
+    collections::HashMap,
+    io::{self, Write},
+};
+
+/// The contents of an x86-64 ELF relocatable object.
+#[derive(Debug, Default)]
+pub struct Object<'a> {
+    /// The source file the object was compiled from.
+    pub file: &'a str,
+    pub text: Vec<u8>,
+    pub data: Vec<u8>,
+    pub rodata: Vec<u8>,
+    pub bss_size: usize,
+    pub symbols: Vec<Symbol<'a>>,
+    /// Relocations in `.text`. Symbols they refer to that aren't defined in
+    /// the object are added as undefined globals.
+    pub relocations: Vec<Relocation<'a>>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Section {
+    Text,
+    Data,
+    Rodata,
+    Bss,
+}
+
+#[derive(Debug)]
+pub struct Symbol<'a> {
+    pub name: &'a str,
+    pub section: Section,
+    pub offset: usize,
+    pub size: usize,
+    /// Whether the symbol is a function rather than a variable.
+    pub function: bool,
+    pub global: bool,
+}
+
+#[derive(Debug)]
+pub struct Relocation<'a> {
+    pub offset: usize,
+    pub symbol: &'a str,
+    pub kind: RelocationKind,
+    pub addend: i64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RelocationKind {
+    /// `R_X86_64_PC32`, `symbol + addend - offset`.
+    Pc32,
+    /// `R_X86_64_PLT32`, like `Pc32` but through the PLT for calls to
+    /// functions in shared libraries.
+    Plt32,
+}
+
+const SHT_PROGBITS: u32 = 1;
+const SHT_SYMTAB: u32 = 2;
+const SHT_STRTAB: u32 = 3;
+const SHT_RELA: u32 = 4;
+const SHT_NOBITS: u32 = 8;
+
+const SHF_WRITE: u64 = 0x1;
+const SHF_ALLOC: u64 = 0x2;
+const SHF_EXECINSTR: u64 = 0x4;
+const SHF_INFO_LINK: u64 = 0x40;
+
+const STB_LOCAL: u8 = 0;
+const STB_GLOBAL: u8 = 1;
+const STT_NOTYPE: u8 = 0;
+const STT_OBJECT: u8 = 1;
+const STT_FUNC: u8 = 2;
+const STT_SECTION: u8 = 3;
+const STT_FILE: u8 = 4;
+const SHN_ABS: u16 = 0xfff1;
+
+/// Section header indices, in the order the sections are written.
+const TEXT: u16 = 1;
+const DATA: u16 = 2;
+const BSS: u16 = 3;
+const RODATA: u16 = 4;
+const SYMTAB: u16 = 6;
+const STRTAB: u16 = 7;
+
+impl Section {
+    fn index(self) -> u16 {
+        match self {
+            Self::Text => TEXT,
+            Self::Data => DATA,
+            Self::Bss => BSS,
+            Self::Rodata => RODATA,
+        }
+    }
+}
+
+impl RelocationKind {
+    fn number(self) -> u64 {
+        match self {
+            Self::Pc32 => 2,
+            Self::Plt32 => 4,
+        }
+    }
+}
+
+struct SectionHeader {
+    name: &'static str,
+    kind: u32,
+    flags: u64,
+    contents: Vec<u8>,
+    /// Only differs from the length of `contents` for `.bss`.
+    size: usize,
+    link: u32,
+    info: u32,
+    align: u64,
+    entry_size: u64,
+}
+
+impl SectionHeader {
+    fn new(name: &'static str, kind: u32, flags: u64, contents: Vec<u8>, align: u64) -> Self {
+        Self {
+            name,
+            kind,
+            flags,
+            size: contents.len(),
+            contents,
+            link: 0,
+            info: 0,
+            align,
+            entry_size: 0,
+        }
+    }
+}
+
+/// A string table, starting with the empty string.
+struct Strings(Vec<u8>);
+
+impl Strings {
+    fn add(&mut self, string: &str) -> u32 {
+        let offset = u32::try_from(self.0.len()).expect("string table fits in 32 bits");
+        self.0.extend(string.as_bytes());
+        self.0.push(0);
+        offset
+    }
+}
+
+fn symbol(out: &mut Vec<u8>, name: u32, info: u8, section: u16, value: usize, size: usize) {
+    out.extend(name.to_le_bytes());
+    out.push(info);
+    out.push(0);
+    out.extend(section.to_le_bytes());
+    out.extend((value as u64).to_le_bytes());
+    out.extend((size as u64).to_le_bytes());
+}
+
+/// Writes the object as an ELF64 relocatable file.
+pub fn write_object(mut out: impl Write, object: &Object) -> io::Result<()> {
+    let mut strings = Strings(vec![0]);
+    let mut symbols = vec![0; 24];
+    let file = strings.add(object.file);
+    symbol(&mut symbols, file, STB_LOCAL << 4 | STT_FILE, SHN_ABS, 0, 0);
+    for section in [TEXT, DATA, BSS, RODATA] {
+        symbol(&mut symbols, 0, STB_LOCAL << 4 | STT_SECTION, section, 0, 0);
+    }
+    // Local symbols have to come before the global ones.
+    let mut indices = HashMap::new();
+    let (locals, globals): (Vec<_>, Vec<_>) = object.symbols.iter().partition(|s| !s.global);
+    let first_global = symbols.len() / 24 + locals.len();
+    for defined in locals.iter().chain(&globals) {
+        let kind = if defined.function {
+            STT_FUNC
+        } else {
+            STT_OBJECT
+        };
+        let bind = if defined.global {
+            STB_GLOBAL
+        } else {
+            STB_LOCAL
+        };
+        indices.insert(defined.name, symbols.len() / 24);
+        let name = strings.add(defined.name);
+        symbol(
+            &mut symbols,
+            name,
+            bind << 4 | kind,
+            defined.section.index(),
+            defined.offset,
+            defined.size,
+        );
+    }
+    let mut relocations = Vec::new();
+    for relocation in &object.relocations {
+        let index = *indices.entry(relocation.symbol).or_insert_with(|| {
+            let index = symbols.len() / 24;
+            let name = strings.add(relocation.symbol);
+            symbol(&mut symbols, name, STB_GLOBAL << 4 | STT_NOTYPE, 0, 0, 0);
+            index
+        }) as u64;
+        relocations.extend((relocation.offset as u64).to_le_bytes());
+        relocations.extend((index << 32 | relocation.kind.number()).to_le_bytes());
+        relocations.extend(relocation.addend.to_le_bytes());
+    }
+
+    let mut sections = vec![
+        SectionHeader::new("", 0, 0, Vec::new(), 0),
+        SectionHeader::new(
+            ".text",
+            SHT_PROGBITS,
+            SHF_ALLOC | SHF_EXECINSTR,
+            object.text.clone(),
+            16,
+        ),
+        SectionHeader::new(
+            ".data",
+            SHT_PROGBITS,
+            SHF_WRITE | SHF_ALLOC,
+            object.data.clone(),
+            8,
+        ),
+        SectionHeader {
+            size: object.bss_size,
+            ..SectionHeader::new(".bss", SHT_NOBITS, SHF_WRITE | SHF_ALLOC, Vec::new(), 8)
+        },
+        SectionHeader::new(".rodata", SHT_PROGBITS, SHF_ALLOC, object.rodata.clone(), 8),
+        // Marks the stack as not executable.
+        SectionHeader::new(".note.GNU-stack", SHT_PROGBITS, 0, Vec::new(), 1),
+        SectionHeader {
+            link: u32::from(STRTAB),
+            info: u32::try_from(first_global).expect("symbol table fits in 32 bits"),
+            entry_size: 24,
+            ..SectionHeader::new(".symtab", SHT_SYMTAB, 0, symbols, 8)
+        },
+        SectionHeader::new(".strtab", SHT_STRTAB, 0, strings.0, 1),
+    ];
+    if !relocations.is_empty() {
+        sections.push(SectionHeader {
+            link: u32::from(SYMTAB),
+            info: u32::from(TEXT),
+            entry_size: 24,
+            ..SectionHeader::new(".rela.text", SHT_RELA, SHF_INFO_LINK, relocations, 8)
+        });
+    }
+    let mut section_names = Strings(vec![0]);
+    let mut names = Vec::new();
+    for section in &sections {
+        names.push(if section.name.is_empty() {
+            0
+        } else {
+            section_names.add(section.name)
+        });
+    }
+    let shstrtab = u16::try_from(sections.len()).expect("fewer than 65536 sections");
+    names.push(section_names.add(".shstrtab"));
+    sections.push(SectionHeader::new(
+        ".shstrtab",
+        SHT_STRTAB,
+        0,
+        section_names.0,
+        1,
+    ));
+
+    // The contents follow the file header, and the section headers come last.
+    let mut offsets = Vec::new();
+    let mut offset: usize = 64;
+    for section in &sections {
+        offset = offset.next_multiple_of(section.align.max(1) as usize);
+        offsets.push(offset);
+        offset += section.contents.len();
+    }
+    let headers = offset.next_multiple_of(8);
+
+    out.write_all(b"\x7fELF")?;
+    // 64 bit, little endian, version 1, System V ABI.
+    out.write_all(&[2, 1, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0])?;
+    // Relocatable, x86-64, version 1, no entry point or program headers.
+    out.write_all(&1u16.to_le_bytes())?;
+    out.write_all(&62u16.to_le_bytes())?;
+    out.write_all(&1u32.to_le_bytes())?;
+    out.write_all(&0u64.to_le_bytes())?;
+    out.write_all(&0u64.to_le_bytes())?;
+    out.write_all(&(headers as u64).to_le_bytes())?;
+    out.write_all(&0u32.to_le_bytes())?;
+    out.write_all(&64u16.to_le_bytes())?;
+    out.write_all(&0u16.to_le_bytes())?;
+    out.write_all(&0u16.to_le_bytes())?;
+    out.write_all(&64u16.to_le_bytes())?;
+    out.write_all(&(sections.len() as u16).to_le_bytes())?;
+    out.write_all(&shstrtab.to_le_bytes())?;
+
+    let mut written = 64;
+    for (section, &offset) in sections.iter().zip(&offsets) {
+        out.write_all(&vec![0; offset - written])?;
+        out.write_all(&section.contents)?;
+        written = offset + section.contents.len();
+    }
+    out.write_all(&vec![0; headers - written])?;
+    for ((section, &offset), name) in sections.iter().zip(&offsets).zip(names) {
+        out.write_all(&name.to_le_bytes())?;
+        out.write_all(&section.kind.to_le_bytes())?;
+        out.write_all(&section.flags.to_le_bytes())?;
+        out.write_all(&0u64.to_le_bytes())?;
+        let offset = if section.kind == 0 { 0 } else { offset };
+        out.write_all(&(offset as u64).to_le_bytes())?;
+        out.write_all(&(section.size as u64).to_le_bytes())?;
+        out.write_all(&section.link.to_le_bytes())?;
+        out.write_all(&section.info.to_le_bytes())?;
+        out.write_all(&section.align.to_le_bytes())?;
+        out.write_all(&section.entry_size.to_le_bytes())?;
+    }
+    out.flush()
+}
@@ -0,0 +1,198 @@
+use std::collections::HashMap;
+
+use crate::{
+    cfg::{Flow, FlowInstruction},
+    irc::{BinaryOp, Function, Instruction, Program, Value},
+};
+
+/// Arms with more instructions than this, not counting the final copy, keep
+/// their branches.
+const MAX_ARM_LENGTH: usize = 3;
+
+/// Replaces `condition ? a : b` diamonds whose arms are cheap and can't trap
+/// with a `Select`, evaluating both arms without branching.
+/// Returns `true` if the program changed.
+pub fn convert_to_selects(program: &mut Program) -> bool {
+    match program {
+        Program::Function(function) => convert_function(function),
+    }
+}
+
+fn convert_function(function: &mut Function) -> bool {
+    let mut changed = false;
+    let mut index = 0;
+    while index < function.instructons.len() {
+        let Some(diamond) = Diamond::find(&function.instructons, index) else {
+            index += 1;
+            continue;
+        };
+        // Evaluate the arms before the condition so the comparison stays next
+        // to the select and code_gen can fuse them.
+        let start = match (index.checked_sub(1), &diamond.condition) {
+            (Some(previous), Value::Var(condition))
+                if function.instructons[previous].dst() == Some(condition)
+                    && !diamond
+                        .then_arm
+                        .iter()
+                        .chain(diamond.else_arm.iter())
+                        .flat_map(Instruction::sources)
+                        .any(|src| *src == diamond.condition) =>
+            {
+                previous
+            }
+            _ => index,
+        };
+        let mut replacement: Vec<Instruction> = diamond
+            .then_arm
+            .iter()
+            .chain(diamond.else_arm.iter())
+            .cloned()
+            .collect();
+        replacement.extend(function.instructons[start..index].iter().cloned());
+        replacement.push(Instruction::Select {
+            condition: diamond.condition,
+            src1: diamond.then_value,
+            src2: diamond.else_value,
+            dst: diamond.dst,
+        });
+        function
+            .instructons
+            .splice(start..index + diamond.length, replacement);
+        changed = true;
+        index = start + 1;
+    }
+    changed
+}
+
+/// ```text
+///     jump_if_zero condition, else
+///     <then arm>
+///     dst = then_value
+///     jump end
+///   else:
+///     <else arm>
+///     dst = else_value
+///   end:
+/// ```
+#[derive(Debug)]
+struct Diamond<'a> {
+    condition: Value,
+    then_arm: &'a [Instruction],
+    then_value: Value,
+    else_arm: &'a [Instruction],
+    else_value: Value,
+    dst: String,
+    length: usize,
+}
+
+impl<'a> Diamond<'a> {
+    fn find(instructions: &'a [Instruction], index: usize) -> Option<Self> {
+        let window = &instructions[index..];
+        let (condition, else_label, jump_if_zero) = match window.first()? {
+            Instruction::JumpIfZero { condition, target } => (condition, target, true),
+            Instruction::JumpIfNotZero { condition, target } => (condition, target, false),
+            _ => return None,
+        };
+        let jump = 1 + window[1..]
+            .iter()
+            .take(MAX_ARM_LENGTH + 2)
+            .position(|ins| ins.flow() != Flow::Next)?;
+        let Instruction::Jump { target: end_label } = &window[jump] else {
+            return None;
+        };
+        if window.get(jump + 1) != Some(&Instruction::Label(else_label.clone())) {
+            return None;
+        }
+        let end = jump
+            + 2
+            + window[jump + 2..]
+                .iter()
+                .take(MAX_ARM_LENGTH + 2)
+                .position(|ins| ins.flow() != Flow::Next)?;
+        if window[end] != Instruction::Label(end_label.clone()) || else_label == end_label {
+            return None;
+        }
+
+        let (first_arm, first_value, dst) = split_arm(&window[1..jump])?;
+        let (second_arm, second_value, second_dst) = split_arm(&window[jump + 2..end])?;
+        if dst != second_dst
+            || [condition, first_value, second_value].contains(&&Value::Var(dst.to_string()))
+            || jump_count(instructions, else_label) != 1
+            || jump_count(instructions, end_label) != 1
+            || !is_local(instructions, index + 1..index + jump)
+            || !is_local(instructions, index + jump + 2..index + end)
+        {
+            return None;
+        }
+
+        let (then_arm, then_value, else_arm, else_value) = if jump_if_zero {
+            (first_arm, first_value, second_arm, second_value)
+        } else {
+            (second_arm, second_value, first_arm, first_value)
+        };
+        Some(Self {
+            condition: condition.clone(),
+            then_arm,
+            then_value: then_value.clone(),
+            else_arm,
+            else_value: else_value.clone(),
+            dst: dst.to_string(),
+            length: end + 1,
+        })
+    }
+}
+
+/// Splits an arm into the instructions computing its value and the copy of
+/// that value into the result. The instructions must be safe to run even when
+/// the arm isn't taken.
+fn split_arm(arm: &[Instruction]) -> Option<(&[Instruction], &Value, &str)> {
+    let (Instruction::Copy { src, dst }, body) = arm.split_last()? else {
+        return None;
+    };
+    let can_speculate = body.iter().all(|ins| match ins {
+        Instruction::Binary {
+            operator: BinaryOp::Divide | BinaryOp::Remainder,
+            ..
+        } => false,
+        Instruction::Unary { .. }
+        | Instruction::Binary { .. }
+        | Instruction::Line(_)
+        | Instruction::Variable { .. } => true,
+        _ => false,
+    });
+    can_speculate.then_some((body, src, dst))
+}
+
+fn jump_count(instructions: &[Instruction], label: &str) -> usize {
+    instructions
+        .iter()
+        .filter(|ins| matches!(ins.flow(), Flow::Jump(target) | Flow::ConditionalJump(target) if target == label))
+        .count()
+}
+
+/// Whether every variable written in `range` (except by its last instruction,
+/// the copy into the result) is written only there and read only there, so
+/// running it when the arm isn't taken can't be observed.
+fn is_local(instructions: &[Instruction], range: std::ops::Range<usize>) -> bool {
+    let mut writes: HashMap<&str, usize> = HashMap::new();
+    let mut reads: HashMap<&str, usize> = HashMap::new();
+    for ins in instructions {
+        if let Some(dst) = ins.dst() {
+            *writes.entry(dst).or_default() += 1;
+        }
+        for src in ins.sources() {
+            if let Value::Var(name) = src {
+                *reads.entry(name).or_default() += 1;
+            }
+        }
+    }
+    let arm = &instructions[range.start..range.end - 1];
+    arm.iter().filter_map(Instruction::dst).all(|dst| {
+        let local_reads = instructions[range.clone()]
+            .iter()
+            .flat_map(Instruction::sources)
+            .filter(|src| **src == Value::Var(dst.to_string()))
+            .count();
+        writes[dst] == 1 && reads.get(dst).copied().unwrap_or(0) == local_reads
+    })
+}
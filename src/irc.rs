@@ -1,22 +1,24 @@
-#[derive(Debug)]
+use std::fmt::{self, Display, Formatter};
+
+#[derive(Debug, Clone)]
 pub enum Program<'a> {
     Function(Function<'a>),
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum Value {
     Constant(i32),
     Var(String),
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum UnaryOp {
     Complement,
     Negate,
     Not,
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum BinaryOp {
     Add,
     Subtract,
@@ -36,7 +38,7 @@ pub enum BinaryOp {
     GreaterOrEqual,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Instruction {
     Ret(Value),
     Unary {
@@ -54,6 +56,13 @@ pub enum Instruction {
         src: Value,
         dst: String,
     },
+    /// `dst = condition ? src1 : src2`, without branching.
+    Select {
+        condition: Value,
+        src1: Value,
+        src2: Value,
+        dst: String,
+    },
     Jump {
         target: String,
     },
@@ -66,10 +75,171 @@ pub enum Instruction {
         target: String,
     },
     Label(String),
+    /// The following instructions come from this source line.
+    Line(usize),
+    /// A local variable declared on `line`, kept for the debug info.
+    Variable {
+        name: String,
+        line: usize,
+    },
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Function<'a> {
     pub name: &'a str,
     pub instructons: Vec<Instruction>,
 }
+
+impl Instruction {
+    /// The variable written by this instruction, if any.
+    pub fn dst(&self) -> Option<&str> {
+        match self {
+            Self::Unary { dst, .. }
+            | Self::Binary { dst, .. }
+            | Self::Copy { dst, .. }
+            | Self::Select { dst, .. } => Some(dst),
+            Self::Ret(_)
+            | Self::Jump { .. }
+            | Self::JumpIfZero { .. }
+            | Self::JumpIfNotZero { .. }
+            | Self::Label(_)
+            | Self::Line(_)
+            | Self::Variable { .. } => None,
+        }
+    }
+
+    /// The values read by this instruction.
+    pub fn sources(&self) -> Vec<&Value> {
+        match self {
+            Self::Ret(src)
+            | Self::Unary { src, .. }
+            | Self::Copy { src, .. }
+            | Self::JumpIfZero { condition: src, .. }
+            | Self::JumpIfNotZero { condition: src, .. } => vec![src],
+            Self::Binary { src1, src2, .. } => vec![src1, src2],
+            Self::Select {
+                condition,
+                src1,
+                src2,
+                ..
+            } => vec![condition, src1, src2],
+            Self::Jump { .. } | Self::Label(_) | Self::Line(_) | Self::Variable { .. } => {
+                Vec::new()
+            }
+        }
+    }
+
+    pub fn sources_mut(&mut self) -> Vec<&mut Value> {
+        match self {
+            Self::Ret(src)
+            | Self::Unary { src, .. }
+            | Self::Copy { src, .. }
+            | Self::JumpIfZero { condition: src, .. }
+            | Self::JumpIfNotZero { condition: src, .. } => vec![src],
+            Self::Binary { src1, src2, .. } => vec![src1, src2],
+            Self::Select {
+                condition,
+                src1,
+                src2,
+                ..
+            } => vec![condition, src1, src2],
+            Self::Jump { .. } | Self::Label(_) | Self::Line(_) | Self::Variable { .. } => {
+                Vec::new()
+            }
+        }
+    }
+}
+
+impl Display for Program<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Function(function) => write!(f, "{function}"),
+        }
+    }
+}
+
+impl Display for Function<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{}:", self.name)?;
+        for ins in &self.instructons {
+            writeln!(f, "{ins}")?;
+        }
+        Ok(())
+    }
+}
+
+impl Display for Instruction {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Ret(value) => write!(f, "    return {value}"),
+            Self::Unary { operator, src, dst } => write!(f, "    {dst} = {operator}{src}"),
+            Self::Binary {
+                operator,
+                src1,
+                src2,
+                dst,
+            } => write!(f, "    {dst} = {src1} {operator} {src2}"),
+            Self::Copy { src, dst } => write!(f, "    {dst} = {src}"),
+            Self::Select {
+                condition,
+                src1,
+                src2,
+                dst,
+            } => write!(f, "    {dst} = {condition} ? {src1} : {src2}"),
+            Self::Jump { target } => write!(f, "    jump {target}"),
+            Self::JumpIfZero { condition, target } => {
+                write!(f, "    jump_if_zero {condition}, {target}")
+            }
+            Self::JumpIfNotZero { condition, target } => {
+                write!(f, "    jump_if_not_zero {condition}, {target}")
+            }
+            Self::Label(label) => write!(f, "  {label}:"),
+            Self::Line(line) => write!(f, "    # line {line}"),
+            Self::Variable { name, line } => write!(f, "    # variable {name}, line {line}"),
+        }
+    }
+}
+
+impl Display for Value {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Constant(value) => write!(f, "{value}"),
+            Self::Var(name) => write!(f, "{name}"),
+        }
+    }
+}
+
+impl Display for UnaryOp {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let operator = match self {
+            Self::Complement => "~",
+            Self::Negate => "-",
+            Self::Not => "!",
+        };
+        write!(f, "{operator}")
+    }
+}
+
+impl Display for BinaryOp {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let operator = match self {
+            Self::Add => "+",
+            Self::Subtract => "-",
+            Self::Multiply => "*",
+            Self::Divide => "/",
+            Self::Remainder => "%",
+            Self::LeftShift => "<<",
+            Self::RightShift => ">>",
+            Self::BitAnd => "&",
+            Self::Xor => "^",
+            Self::BitOr => "|",
+            Self::Equal => "==",
+            Self::NotEqual => "!=",
+            Self::LessThan => "<",
+            Self::LessOrEqual => "<=",
+            Self::GreaterThan => ">",
+            Self::GreaterOrEqual => ">=",
+        };
+        write!(f, "{operator}")
+    }
+}
@@ -3,23 +3,36 @@ use crate::{ast, irc};
 #[derive(Default)]
 pub struct IrcGenerator {
     counter: usize,
+    /// Whether to keep the source lines in `Line` instructions.
+    lines: bool,
+    /// Whether to keep the declared variables in `Variable` instructions.
+    variables: bool,
 }
 
 impl IrcGenerator {
-    pub fn gen_program(program: ast::Program<'_>, counter: usize) -> (irc::Program<'_>, usize) {
-        let mut irc_generator = Self { counter };
-        (
-            match program {
-                ast::Program::Function(function) => {
-                    irc::Program::Function(irc_generator.gen_function(function))
-                }
-            },
-            irc_generator.counter * 4,
-        )
+    /// The annotations are only generated when asked for, as most compiles
+    /// don't use them.
+    pub fn gen_program(
+        program: ast::Program<'_>,
+        counter: usize,
+        lines: bool,
+        variables: bool,
+    ) -> irc::Program<'_> {
+        let mut irc_generator = Self {
+            counter,
+            lines,
+            variables,
+        };
+        match program {
+            ast::Program::Function(function) => {
+                irc::Program::Function(irc_generator.gen_function(function))
+            }
+        }
     }
 
     fn gen_function<'a>(&mut self, function: ast::Function<'a>) -> irc::Function<'a> {
-        let mut instructions = self.gen_block(function.body);
+        let mut instructions = self.line(function.line);
+        instructions.extend(self.gen_block(function.body));
         instructions.push(irc::Instruction::Ret(irc::Value::Constant(0)));
         irc::Function {
             name: function.name,
@@ -38,11 +51,18 @@ impl IrcGenerator {
     fn gen_block_item(&mut self, block_item: ast::BlockItem) -> Vec<irc::Instruction> {
         match block_item {
             ast::BlockItem::Statement(stmt) => self.gen_stmt(stmt),
-            ast::BlockItem::Decleration(ast::Decleration::Decleration { name, init }) => {
+            ast::BlockItem::Decleration(ast::Decleration::Decleration { name, init, line }) => {
+                let mut instructions = Vec::new();
+                if self.variables {
+                    instructions.push(irc::Instruction::Variable {
+                        name: name.clone(),
+                        line,
+                    });
+                }
                 let Some(init) = init else {
-                    return Vec::new();
+                    return instructions;
                 };
-                let mut instructions = Vec::new();
+                instructions.extend(self.line(line));
                 let value = self.gen_expr(init, &mut instructions);
                 instructions.push(irc::Instruction::Copy {
                     src: value,
@@ -151,19 +171,11 @@ impl IrcGenerator {
             } => {
                 let result_var = self.gen_temp();
                 let result = irc::Value::Var(result_var.clone());
-                let v1 = self.gen_expr(*left, instructions);
                 let false_label = self.gen_label("and_false");
                 let end_label = self.gen_label("and_end");
 
-                instructions.push(irc::Instruction::JumpIfZero {
-                    condition: v1,
-                    target: false_label.clone(),
-                });
-                let v2 = self.gen_expr(*right, instructions);
-                instructions.push(irc::Instruction::JumpIfZero {
-                    condition: v2,
-                    target: false_label.clone(),
-                });
+                self.gen_condition(*left, false, &false_label, instructions);
+                self.gen_condition(*right, false, &false_label, instructions);
                 instructions.push(irc::Instruction::Copy {
                     src: irc::Value::Constant(1),
                     dst: result_var.clone(),
@@ -186,19 +198,11 @@ impl IrcGenerator {
             } => {
                 let result_var = self.gen_temp();
                 let result = irc::Value::Var(result_var.clone());
-                let v1 = self.gen_expr(*left, instructions);
                 let true_label = self.gen_label("or_true");
                 let end_label = self.gen_label("or_end");
 
-                instructions.push(irc::Instruction::JumpIfNotZero {
-                    condition: v1,
-                    target: true_label.clone(),
-                });
-                let v2 = self.gen_expr(*right, instructions);
-                instructions.push(irc::Instruction::JumpIfNotZero {
-                    condition: v2,
-                    target: true_label.clone(),
-                });
+                self.gen_condition(*left, true, &true_label, instructions);
+                self.gen_condition(*right, true, &true_label, instructions);
                 instructions.push(irc::Instruction::Copy {
                     src: irc::Value::Constant(0),
                     dst: result_var.clone(),
@@ -268,12 +272,7 @@ impl IrcGenerator {
                 let condition_result = self.gen_temp();
                 let end_label = self.gen_label("ter_end");
                 let else_label = self.gen_label("ter_else");
-                let condition = self.gen_expr(*condition, instructions);
-
-                instructions.push(irc::Instruction::JumpIfZero {
-                    condition,
-                    target: else_label.clone(),
-                });
+                self.gen_condition(*condition, false, &else_label, instructions);
                 let v1 = self.gen_expr(*then_branch, instructions);
                 instructions.push(irc::Instruction::Copy {
                     src: v1,
@@ -294,6 +293,50 @@ impl IrcGenerator {
         }
     }
 
+    /// Jumps to `target` if `expr` is nonzero when `jump_if` is `true`, or zero when
+    /// it is `false`, and falls through otherwise. `&&`, `||` and `!` become
+    /// jumps instead of 0/1 temps.
+    fn gen_condition(
+        &mut self,
+        expr: ast::Expr,
+        jump_if: bool,
+        target: &str,
+        instructions: &mut Vec<irc::Instruction>,
+    ) {
+        match expr {
+            ast::Expr::Unary {
+                operator: ast::UnaryOp::Not,
+                right,
+            } => self.gen_condition(*right, !jump_if, target, instructions),
+            ast::Expr::Binary {
+                operator: operator @ (ast::BinaryOp::And | ast::BinaryOp::Or),
+                left,
+                right,
+            } => {
+                // `&&` jumps when either side is false, `||` when either is true.
+                let jumps_on = matches!(operator, ast::BinaryOp::Or);
+                if jump_if == jumps_on {
+                    self.gen_condition(*left, jump_if, target, instructions);
+                    self.gen_condition(*right, jump_if, target, instructions);
+                } else {
+                    let skip_label = self.gen_label(if jumps_on { "or_true" } else { "and_false" });
+                    self.gen_condition(*left, jumps_on, &skip_label, instructions);
+                    self.gen_condition(*right, jump_if, target, instructions);
+                    instructions.push(irc::Instruction::Label(skip_label));
+                }
+            }
+            expr => {
+                let condition = self.gen_expr(expr, instructions);
+                let target = target.to_string();
+                instructions.push(if jump_if {
+                    irc::Instruction::JumpIfNotZero { condition, target }
+                } else {
+                    irc::Instruction::JumpIfZero { condition, target }
+                });
+            }
+        }
+    }
+
     fn gen_stmt(&mut self, stmt: ast::Stmt) -> Vec<irc::Instruction> {
         match stmt {
             ast::Stmt::Return(expr) => {
@@ -315,13 +358,9 @@ impl IrcGenerator {
             } => {
                 let mut instructions = Vec::new();
                 let end_label = self.gen_label("if_end");
-                let condition = self.gen_expr(condition, &mut instructions);
+                self.gen_condition(condition, false, &end_label, &mut instructions);
                 let then_branch = self.gen_stmt(*then_branch);
 
-                instructions.push(irc::Instruction::JumpIfZero {
-                    condition,
-                    target: end_label.clone(),
-                });
                 instructions.extend(then_branch);
                 instructions.push(irc::Instruction::Label(end_label));
                 instructions
@@ -334,14 +373,10 @@ impl IrcGenerator {
                 let mut instructions = Vec::new();
                 let end_label = self.gen_label("if_end");
                 let else_label = self.gen_label("if_else");
-                let condition = self.gen_expr(condition, &mut instructions);
+                self.gen_condition(condition, false, &else_label, &mut instructions);
                 let then_branch = self.gen_stmt(*then_branch);
                 let else_branch = self.gen_stmt(*else_branch);
 
-                instructions.push(irc::Instruction::JumpIfZero {
-                    condition,
-                    target: else_label.clone(),
-                });
                 instructions.extend(then_branch);
                 instructions.push(irc::Instruction::Jump {
                     target: end_label.clone(),
@@ -360,25 +395,24 @@ impl IrcGenerator {
                 instructions
             }
             ast::Stmt::Compound(block) => self.gen_block(block),
-            ast::Stmt::Break { label } => todo!(),
-            ast::Stmt::Continue { label } => todo!(),
-            ast::Stmt::While {
-                condition,
-                body,
-                label,
-            } => todo!(),
-            ast::Stmt::DoWhile {
-                body,
-                condition,
-                label,
-            } => todo!(),
-            ast::Stmt::For {
-                init,
-                condition,
-                post,
-                body,
-                label,
-            } => todo!(),
+            ast::Stmt::Line(line, stmt) => {
+                let mut instructions = self.line(line);
+                instructions.extend(self.gen_stmt(*stmt));
+                instructions
+            }
+            ast::Stmt::Break { .. } => todo!(),
+            ast::Stmt::Continue { .. } => todo!(),
+            ast::Stmt::While { .. } => todo!(),
+            ast::Stmt::DoWhile { .. } => todo!(),
+            ast::Stmt::For { .. } => todo!(),
+        }
+    }
+
+    fn line(&self, line: usize) -> Vec<irc::Instruction> {
+        if self.lines {
+            vec![irc::Instruction::Line(line)]
+        } else {
+            Vec::new()
         }
     }
 
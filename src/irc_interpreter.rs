@@ -0,0 +1,109 @@
+use std::collections::HashMap;
+
+use crate::{
+    constant_folding,
+    irc::{BinaryOp, Function, Instruction, Program, UnaryOp, Value},
+    Error, Result,
+};
+
+/// Runs the program and returns the value returned by its function.
+/// Variables read before they are written are `0`.
+pub fn run(program: &Program) -> Result<i32> {
+    match program {
+        Program::Function(function) => run_function(function),
+    }
+}
+
+fn run_function(function: &Function) -> Result<i32> {
+    let labels: HashMap<&str, usize> = function
+        .instructons
+        .iter()
+        .enumerate()
+        .filter_map(|(index, ins)| match ins {
+            Instruction::Label(label) => Some((label.as_str(), index)),
+            _ => None,
+        })
+        .collect();
+    let jump = |target: &str| {
+        labels
+            .get(target)
+            .copied()
+            .ok_or_else(|| Error::Interpreter(format!("Jump to unknown label '{target}'")))
+    };
+    let mut vars: HashMap<&str, i32> = HashMap::new();
+    let mut pc = 0;
+
+    while let Some(ins) = function.instructons.get(pc) {
+        let value = |value: &Value| match value {
+            Value::Constant(value) => *value,
+            Value::Var(name) => vars.get(name.as_str()).copied().unwrap_or(0),
+        };
+        pc += 1;
+        match ins {
+            Instruction::Ret(src) => return Ok(value(src)),
+            Instruction::Unary { operator, src, dst } => {
+                let src = value(src);
+                let result = match operator {
+                    UnaryOp::Complement => !src,
+                    UnaryOp::Negate => src.wrapping_neg(),
+                    UnaryOp::Not => i32::from(src == 0),
+                };
+                vars.insert(dst, result);
+            }
+            Instruction::Binary {
+                operator,
+                src1,
+                src2,
+                dst,
+            } => {
+                let (src1, src2) = (value(src1), value(src2));
+                let result = constant_folding::fold_binary(*operator, src1, src2)
+                    .or(match operator {
+                        // Match what x86 does for shift counts out of range.
+                        BinaryOp::LeftShift => Some(src1.wrapping_shl(src2 as u32)),
+                        BinaryOp::RightShift => Some(src1.wrapping_shr(src2 as u32)),
+                        _ => None,
+                    })
+                    .ok_or_else(|| {
+                        Error::Interpreter(format!(
+                            "Arithmetic error in '{src1} {operator} {src2}'"
+                        ))
+                    })?;
+                vars.insert(dst, result);
+            }
+            Instruction::Copy { src, dst } => {
+                let src = value(src);
+                vars.insert(dst, src);
+            }
+            Instruction::Select {
+                condition,
+                src1,
+                src2,
+                dst,
+            } => {
+                let src = if value(condition) != 0 {
+                    value(src1)
+                } else {
+                    value(src2)
+                };
+                vars.insert(dst, src);
+            }
+            Instruction::Jump { target } => pc = jump(target)?,
+            Instruction::JumpIfZero { condition, target } => {
+                if value(condition) == 0 {
+                    pc = jump(target)?;
+                }
+            }
+            Instruction::JumpIfNotZero { condition, target } => {
+                if value(condition) != 0 {
+                    pc = jump(target)?;
+                }
+            }
+            Instruction::Label(_) | Instruction::Line(_) | Instruction::Variable { .. } => {}
+        }
+    }
+    Err(Error::Interpreter(format!(
+        "Reached the end of '{}' without returning",
+        function.name
+    )))
+}
@@ -0,0 +1,131 @@
+use std::{collections::HashMap, ptr};
+
+use crate::{
+    elf::{Object, RelocationKind, Section},
+    Error, Result,
+};
+
+/// Bytes of `jmp *0(%rip)` followed by the address to jump to.
+const STUB_SIZE: usize = 14;
+
+/// Functions of the host the generated code can call.
+fn host_symbol(name: &str) -> Option<usize> {
+    match name {
+        "putchar" => Some(libc::putchar as *const () as usize),
+        "exit" => Some(libc::exit as *const () as usize),
+        _ => None,
+    }
+}
+
+/// Loads the object into executable memory, runs its `main` and returns
+/// what it returned.
+pub fn run(object: &Object) -> Result<i32> {
+    let page_size = usize::try_from(unsafe { libc::sysconf(libc::_SC_PAGESIZE) })
+        .map_err(|_| Error::Jit("Couldn't get the page size".to_string()))?;
+
+    // Calls to the host go through stubs after the code, since the host can be
+    // further away than a 32 bit displacement reaches.
+    let mut stubs = HashMap::new();
+    for relocation in &object.relocations {
+        if object.symbols.iter().all(|s| s.name != relocation.symbol) {
+            let address = host_symbol(relocation.symbol)
+                .ok_or_else(|| Error::Jit(format!("Undefined symbol '{}'", relocation.symbol)))?;
+            let offset = object.text.len() + STUB_SIZE * stubs.len();
+            stubs.entry(relocation.symbol).or_insert((offset, address));
+        }
+    }
+    let code_size = object.text.len() + STUB_SIZE * stubs.len();
+    let data = code_size.next_multiple_of(page_size);
+    let rodata = (data + object.data.len()).next_multiple_of(8);
+    let bss = (rodata + object.rodata.len()).next_multiple_of(8);
+    let size = (bss + object.bss_size).next_multiple_of(page_size);
+
+    let memory = Memory::new(size)?;
+    let base = memory.0 as usize;
+    let section_address = |section| {
+        base + match section {
+            Section::Text => 0,
+            Section::Data => data,
+            Section::Rodata => rodata,
+            Section::Bss => bss,
+        }
+    };
+    let mut image = vec![0; size];
+    image[..object.text.len()].copy_from_slice(&object.text);
+    for &(offset, address) in stubs.values() {
+        image[offset..offset + 6].copy_from_slice(&[0xff, 0x25, 0, 0, 0, 0]);
+        image[offset + 6..offset + STUB_SIZE].copy_from_slice(&(address as u64).to_le_bytes());
+    }
+    image[data..data + object.data.len()].copy_from_slice(&object.data);
+    image[rodata..rodata + object.rodata.len()].copy_from_slice(&object.rodata);
+
+    for relocation in &object.relocations {
+        let target = match object.symbols.iter().find(|s| s.name == relocation.symbol) {
+            Some(symbol) => section_address(symbol.section) + symbol.offset,
+            None => base + stubs[relocation.symbol].0,
+        };
+        let value = match relocation.kind {
+            RelocationKind::Pc32 | RelocationKind::Plt32 => {
+                target as i64 + relocation.addend - (base + relocation.offset) as i64
+            }
+        };
+        let value = i32::try_from(value).map_err(|_| {
+            Error::Jit(format!(
+                "Relocation against '{}' out of range",
+                relocation.symbol
+            ))
+        })?;
+        image[relocation.offset..relocation.offset + 4].copy_from_slice(&value.to_le_bytes());
+    }
+
+    let main = object
+        .symbols
+        .iter()
+        .find(|s| s.name == "main" && s.function)
+        .ok_or_else(|| Error::Jit("No main function".to_string()))?;
+    let main = section_address(main.section) + main.offset;
+    unsafe {
+        ptr::copy_nonoverlapping(image.as_ptr(), memory.0.cast(), size);
+        if libc::mprotect(memory.0, data, libc::PROT_READ | libc::PROT_EXEC) != 0 {
+            return Err(Error::Jit(format!(
+                "Couldn't make the code executable:\n - {}",
+                std::io::Error::last_os_error()
+            )));
+        }
+        let main: extern "C" fn() -> i32 = std::mem::transmute(main);
+        Ok(main())
+    }
+}
+
+/// An anonymous mapping, unmapped on drop.
+struct Memory(*mut libc::c_void, usize);
+
+impl Memory {
+    fn new(size: usize) -> Result<Self> {
+        let memory = unsafe {
+            libc::mmap(
+                ptr::null_mut(),
+                size,
+                libc::PROT_READ | libc::PROT_WRITE,
+                libc::MAP_PRIVATE | libc::MAP_ANONYMOUS,
+                -1,
+                0,
+            )
+        };
+        if memory == libc::MAP_FAILED {
+            return Err(Error::Jit(format!(
+                "Couldn't map memory:\n - {}",
+                std::io::Error::last_os_error()
+            )));
+        }
+        Ok(Self(memory, size))
+    }
+}
+
+impl Drop for Memory {
+    fn drop(&mut self) {
+        unsafe {
+            libc::munmap(self.0, self.1);
+        }
+    }
+}
@@ -61,28 +61,15 @@ impl LabelResolver {
                 }
             }
             Stmt::Compound(block) => self.resolve_block(block)?,
+            Stmt::Line(_, stmt) => self.resolve_statement(stmt)?,
             // TODO: actually implement this
             Stmt::Goto(_) => {}
             Stmt::Label(_, _) => {}
-            Stmt::Break { label } => todo!(),
-            Stmt::Continue { label } => todo!(),
-            Stmt::While {
-                condition,
-                body,
-                label,
-            } => todo!(),
-            Stmt::DoWhile {
-                body,
-                condition,
-                label,
-            } => todo!(),
-            Stmt::For {
-                init,
-                condition,
-                post,
-                body,
-                label,
-            } => todo!(),
+            Stmt::Break { .. } => todo!(),
+            Stmt::Continue { .. } => todo!(),
+            Stmt::While { .. } => todo!(),
+            Stmt::DoWhile { .. } => todo!(),
+            Stmt::For { .. } => todo!(),
         };
         Ok(())
     }
@@ -8,6 +8,12 @@ pub struct Lexer<'de> {
     rest: &'de str,
     len: usize,
     line: usize,
+    /// The file being compiled, the first one a line marker names unless
+    /// it's set before lexing. Lines of other files aren't source lines of
+    /// the program.
+    pub main_file: Option<String>,
+    /// Whether the line markers put the lexer in the main file.
+    in_main_file: bool,
 }
 
 impl<'de> Lexer<'de> {
@@ -17,6 +23,8 @@ impl<'de> Lexer<'de> {
             rest: source,
             len: 0,
             line: 1,
+            main_file: None,
+            in_main_file: true,
         }
     }
 
@@ -72,6 +80,7 @@ impl<'de> Lexer<'de> {
             '=' => self.add_token(TokenKind::Equal),
             ' ' | '\t' => {}
             '\n' => self.line += 1,
+            '#' => self.line_marker()?,
             c if c.is_ascii_digit() => self.number()?,
             c if c.is_alphabetic() || c == '_' => self.identifier(),
             _ => {
@@ -110,9 +119,37 @@ impl<'de> Lexer<'de> {
             kind,
             lexeme,
             line: self.line,
+            main_file: self.in_main_file,
         });
     }
 
+    /// `# 12 "file.c" flags`, left by the preprocessor: the next line is line 12
+    /// of the source file.
+    fn line_marker(&mut self) -> Result<(), Error> {
+        let marker = self.rest[self.len..]
+            .split_inclusive('\n')
+            .next()
+            .unwrap_or_default();
+        let invalid = || Error::Lexer(format!("Invalid line marker at line {}", self.line));
+        let marker_text = marker.trim();
+        let (line, file) = marker_text
+            .split_once(char::is_whitespace)
+            .unwrap_or((marker_text, ""));
+        self.line = line.parse().map_err(|_| invalid())?;
+        // Without a file name the marker stays in the same file.
+        if let Some(file) = unescape(file) {
+            self.in_main_file = match &self.main_file {
+                Some(main_file) => *main_file == file,
+                None => {
+                    self.main_file = Some(file);
+                    true
+                }
+            };
+        }
+        self.len += marker.len();
+        Ok(())
+    }
+
     fn number(&mut self) -> Result<(), Error> {
         while let Some(c) = self.rest[self.len..].chars().next() {
             if !c.is_ascii_digit() {
@@ -159,3 +196,17 @@ impl<'de> Lexer<'de> {
         }
     }
 }
+
+/// The file name at the start of `"file.c" flags`, with the backslash escapes
+/// the preprocessor puts in it undone.
+fn unescape(quoted: &str) -> Option<String> {
+    let mut chars = quoted.trim_start().strip_prefix('"')?.chars();
+    let mut file = String::new();
+    loop {
+        match chars.next()? {
+            '"' => return Some(file),
+            '\\' => file.push(chars.next()?),
+            c => file.push(c),
+        }
+    }
+}
@@ -1,16 +1,41 @@
+use std::fmt::{self, Display, Formatter};
+
 use parser::ParseError;
 
+pub mod aarch64;
 pub mod asm_ast;
 pub mod ast;
+pub mod c_emission;
+pub mod cfg;
 pub mod code_emission;
 pub mod code_gen;
+pub mod constant_folding;
+pub mod copy_propagation;
+pub mod dataflow;
+pub mod dead_store_elimination;
+pub mod elf;
+pub mod if_conversion;
 pub mod irc;
 pub mod irc_gen;
+pub mod irc_interpreter;
+pub mod jit;
 pub mod label_resolution;
 pub mod lexer;
+pub mod llvm_emission;
+pub mod machine_code;
 pub mod parser;
+pub mod pass_manager;
+pub mod peephole;
+pub mod register_allocation;
+pub mod riscv64;
+pub mod session;
+pub mod ssa;
+pub mod target;
 pub mod token;
+pub mod unreachable_code;
 pub mod var_resolution;
+pub mod wasm;
+pub mod x86_64;
 
 #[derive(Debug)]
 pub enum Error {
@@ -21,6 +46,24 @@ pub enum Error {
     InvalidToken(String),
     Parser(ParseError),
     Resolver(String),
+    Interpreter(String),
+    Jit(String),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::IO(err) => write!(f, "System IO Error:\n - {err}"),
+            Self::Preprocess(err) => write!(f, "Preprocessor Error:\n - {err}"),
+            Self::Assemble(err) => write!(f, "Assembling Error:\n - {err}"),
+            Self::Lexer(err) => write!(f, "Lexer Error:\n - {err}"),
+            Self::InvalidToken(err) => write!(f, "Invalid Token:\n - {err}"),
+            Self::Parser(parse_error) => write!(f, "Parser Error:\n - {parse_error}"),
+            Self::Resolver(err) => write!(f, "Variable Resolution Error:\n - {err}"),
+            Self::Interpreter(err) => write!(f, "Interpreter Error:\n - {err}"),
+            Self::Jit(err) => write!(f, "JIT Error:\n - {err}"),
+        }
+    }
 }
 
 impl From<ParseError> for Error {
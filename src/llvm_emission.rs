@@ -0,0 +1,255 @@
+use std::{
+    collections::HashSet,
+    io::{self, Write},
+};
+
+use crate::irc::{BinaryOp, Function, Instruction, Program, UnaryOp, Value};
+
+/// Writes the program as textual LLVM IR. Every variable gets an `alloca`
+/// that instructions load from and store to, leaving it to `mem2reg` to
+/// build SSA form.
+pub fn emit_program(mut out: impl Write, program: &Program) -> io::Result<()> {
+    match program {
+        Program::Function(function) => emit_function(&mut out, function)?,
+    }
+    out.flush()
+}
+
+/// Values and blocks made while lowering are named `%.N` and the entry
+/// block `.entry`, irc names always start with a letter or `_`, so they
+/// never clash.
+struct Emitter<W> {
+    out: W,
+    counter: usize,
+    /// Whether the current block already ended with a terminator.
+    terminated: bool,
+}
+
+impl<W: Write> Emitter<W> {
+    fn fresh(&mut self) -> String {
+        let name = format!(".{}", self.counter);
+        self.counter += 1;
+        name
+    }
+
+    /// Starts a block for instructions after a terminator, which nothing
+    /// jumps to.
+    fn ensure_block(&mut self) -> io::Result<()> {
+        if self.terminated {
+            let label = self.fresh();
+            writeln!(self.out, "{label}:")?;
+            self.terminated = false;
+        }
+        Ok(())
+    }
+
+    fn value(&mut self, value: &Value) -> io::Result<String> {
+        match value {
+            Value::Constant(value) => Ok(value.to_string()),
+            Value::Var(name) => {
+                let loaded = self.fresh();
+                writeln!(self.out, "  %{loaded} = load i32, i32* %{name}")?;
+                Ok(format!("%{loaded}"))
+            }
+        }
+    }
+
+    fn store(&mut self, value: &str, dst: &str) -> io::Result<()> {
+        writeln!(self.out, "  store i32 {value}, i32* %{dst}")
+    }
+
+    /// Converts `value` to an `i1` that is true when it isn't zero, returning
+    /// its name.
+    fn is_true(&mut self, value: &Value) -> io::Result<String> {
+        let value = self.value(value)?;
+        let condition = self.fresh();
+        writeln!(self.out, "  %{condition} = icmp ne i32 {value}, 0")?;
+        Ok(condition)
+    }
+
+    fn zext(&mut self, condition: &str) -> io::Result<String> {
+        let result = self.fresh();
+        writeln!(self.out, "  %{result} = zext i1 %{condition} to i32")?;
+        Ok(result)
+    }
+
+    fn terminate(&mut self, terminator: &str) -> io::Result<()> {
+        writeln!(self.out, "  {terminator}")?;
+        self.terminated = true;
+        Ok(())
+    }
+
+    fn instruction(&mut self, instruction: &Instruction) -> io::Result<()> {
+        if let Instruction::Label(label) = instruction {
+            if !self.terminated {
+                writeln!(self.out, "  br label %{label}")?;
+            }
+            writeln!(self.out, "{label}:")?;
+            self.terminated = false;
+            return Ok(());
+        }
+        if matches!(
+            instruction,
+            Instruction::Line(_) | Instruction::Variable { .. }
+        ) {
+            return Ok(());
+        }
+        self.ensure_block()?;
+        match instruction {
+            Instruction::Ret(src) => {
+                let src = self.value(src)?;
+                self.terminate(&format!("ret i32 {src}"))?;
+            }
+            Instruction::Unary { operator, src, dst } => {
+                let src = self.value(src)?;
+                let result = match operator {
+                    UnaryOp::Negate => {
+                        let result = self.fresh();
+                        writeln!(self.out, "  %{result} = sub nsw i32 0, {src}")?;
+                        result
+                    }
+                    UnaryOp::Complement => {
+                        let result = self.fresh();
+                        writeln!(self.out, "  %{result} = xor i32 {src}, -1")?;
+                        result
+                    }
+                    UnaryOp::Not => {
+                        let condition = self.fresh();
+                        writeln!(self.out, "  %{condition} = icmp eq i32 {src}, 0")?;
+                        self.zext(&condition)?
+                    }
+                };
+                self.store(&format!("%{result}"), dst)?;
+            }
+            Instruction::Binary {
+                operator,
+                src1,
+                src2,
+                dst,
+            } => {
+                let src1 = self.value(src1)?;
+                let src2 = self.value(src2)?;
+                let result = match comparison(*operator) {
+                    Some(predicate) => {
+                        let condition = self.fresh();
+                        writeln!(
+                            self.out,
+                            "  %{condition} = icmp {predicate} i32 {src1}, {src2}"
+                        )?;
+                        self.zext(&condition)?
+                    }
+                    None => {
+                        let result = self.fresh();
+                        writeln!(
+                            self.out,
+                            "  %{result} = {} i32 {src1}, {src2}",
+                            arithmetic(*operator)
+                        )?;
+                        result
+                    }
+                };
+                self.store(&format!("%{result}"), dst)?;
+            }
+            Instruction::Copy { src, dst } => {
+                let src = self.value(src)?;
+                self.store(&src, dst)?;
+            }
+            Instruction::Select {
+                condition,
+                src1,
+                src2,
+                dst,
+            } => {
+                let condition = self.is_true(condition)?;
+                let src1 = self.value(src1)?;
+                let src2 = self.value(src2)?;
+                let result = self.fresh();
+                writeln!(
+                    self.out,
+                    "  %{result} = select i1 %{condition}, i32 {src1}, i32 {src2}"
+                )?;
+                self.store(&format!("%{result}"), dst)?;
+            }
+            Instruction::Jump { target } => self.terminate(&format!("br label %{target}"))?,
+            Instruction::JumpIfZero { condition, target }
+            | Instruction::JumpIfNotZero { condition, target } => {
+                let condition = self.is_true(condition)?;
+                let next = self.fresh();
+                let (if_true, if_false) = match instruction {
+                    Instruction::JumpIfZero { .. } => (next.as_str(), target.as_str()),
+                    _ => (target.as_str(), next.as_str()),
+                };
+                writeln!(
+                    self.out,
+                    "  br i1 %{condition}, label %{if_true}, label %{if_false}"
+                )?;
+                writeln!(self.out, "{next}:")?;
+            }
+            Instruction::Label(_) | Instruction::Line(_) | Instruction::Variable { .. } => {}
+        }
+        Ok(())
+    }
+}
+
+fn comparison(operator: BinaryOp) -> Option<&'static str> {
+    match operator {
+        BinaryOp::Equal => Some("eq"),
+        BinaryOp::NotEqual => Some("ne"),
+        BinaryOp::LessThan => Some("slt"),
+        BinaryOp::LessOrEqual => Some("sle"),
+        BinaryOp::GreaterThan => Some("sgt"),
+        BinaryOp::GreaterOrEqual => Some("sge"),
+        _ => None,
+    }
+}
+
+/// Signed overflow is undefined in C, which `nsw` tells LLVM.
+fn arithmetic(operator: BinaryOp) -> &'static str {
+    match operator {
+        BinaryOp::Add => "add nsw",
+        BinaryOp::Subtract => "sub nsw",
+        BinaryOp::Multiply => "mul nsw",
+        BinaryOp::Divide => "sdiv",
+        BinaryOp::Remainder => "srem",
+        BinaryOp::LeftShift => "shl nsw",
+        BinaryOp::RightShift => "ashr",
+        BinaryOp::BitAnd => "and",
+        BinaryOp::Xor => "xor",
+        BinaryOp::BitOr => "or",
+        BinaryOp::Equal
+        | BinaryOp::NotEqual
+        | BinaryOp::LessThan
+        | BinaryOp::LessOrEqual
+        | BinaryOp::GreaterThan
+        | BinaryOp::GreaterOrEqual => unreachable!("comparisons are lowered to icmp"),
+    }
+}
+
+fn emit_function(out: &mut impl Write, function: &Function) -> io::Result<()> {
+    writeln!(out, "define i32 @{}() {{", function.name)?;
+    writeln!(out, ".entry:")?;
+    let mut declared = HashSet::new();
+    for ins in &function.instructons {
+        let sources = ins.sources().into_iter().filter_map(|value| match value {
+            Value::Var(name) => Some(name.as_str()),
+            Value::Constant(_) => None,
+        });
+        for name in sources.chain(ins.dst()) {
+            if declared.insert(name) {
+                writeln!(out, "  %{name} = alloca i32")?;
+            }
+        }
+    }
+    let mut emitter = Emitter {
+        out: &mut *out,
+        counter: 0,
+        terminated: false,
+    };
+    for ins in &function.instructons {
+        emitter.instruction(ins)?;
+    }
+    if !emitter.terminated {
+        writeln!(out, "  unreachable")?;
+    }
+    writeln!(out, "}}")
+}
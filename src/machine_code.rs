@@ -0,0 +1,504 @@
+use crate::{
+    asm_ast::{
+        BinaryOp, CondCode, Function, Instruction, Operand, Program, Register, RegisterSize,
+        UnaryOp,
+    },
+    elf,
+};
+
+/// Encodes the program as the contents of an object file, `file` is the
+/// source it was compiled from.
+pub fn encode_program<'a>(program: &Program<'a>, file: &'a str) -> elf::Object<'a> {
+    match program {
+        Program::Function(function) => {
+            let text = encode_function(function);
+            elf::Object {
+                file,
+                symbols: vec![elf::Symbol {
+                    name: function.name,
+                    section: elf::Section::Text,
+                    offset: 0,
+                    size: text.len(),
+                    function: true,
+                    global: true,
+                }],
+                text,
+                ..Default::default()
+            }
+        }
+    }
+}
+
+/// Encodes the function as x86-64 machine code, starting with the prologue
+/// `code_emission` prints.
+pub fn encode_function(function: &Function) -> Vec<u8> {
+    // push %rbp; mov %rsp, %rbp
+    let mut pieces = vec![Piece::Code(vec![0x55, 0x48, 0x89, 0xe5])];
+    for ins in &function.instructons {
+        pieces.push(match ins {
+            Instruction::Label(label) => Piece::Label(label),
+            Instruction::Jmp(target) => Piece::Jump {
+                cond_code: None,
+                target,
+            },
+            Instruction::JumpCC { cond_code, target } => Piece::Jump {
+                cond_code: Some(*cond_code),
+                target,
+            },
+            ins => {
+                let mut code = Vec::new();
+                encode_instruction(&mut code, ins);
+                Piece::Code(code)
+            }
+        });
+    }
+    link(&pieces)
+}
+
+/// Encoded instructions, with the jumps left until the labels are placed.
+enum Piece<'a> {
+    Code(Vec<u8>),
+    Label(&'a str),
+    Jump {
+        cond_code: Option<CondCode>,
+        target: &'a str,
+    },
+}
+
+impl Piece<'_> {
+    fn size(&self, long: bool) -> usize {
+        match self {
+            Self::Code(code) => code.len(),
+            Self::Label(_) => 0,
+            Self::Jump { .. } if !long => 2,
+            Self::Jump {
+                cond_code: None, ..
+            } => 5,
+            Self::Jump {
+                cond_code: Some(_), ..
+            } => 6,
+        }
+    }
+}
+
+/// Places the labels and gives each jump the shortest encoding that reaches
+/// its target. Jumps start with an 8 bit displacement and only ever grow, so
+/// this settles.
+fn link(pieces: &[Piece]) -> Vec<u8> {
+    let mut long = vec![false; pieces.len()];
+    let (offsets, labels) = loop {
+        let mut offsets = Vec::with_capacity(pieces.len() + 1);
+        let mut labels = std::collections::HashMap::new();
+        let mut offset = 0;
+        for (piece, &long) in pieces.iter().zip(&long) {
+            offsets.push(offset);
+            if let Piece::Label(label) = piece {
+                labels.insert(*label, offset);
+            }
+            offset += piece.size(long);
+        }
+        offsets.push(offset);
+        let mut changed = false;
+        for (index, piece) in pieces.iter().enumerate() {
+            if let Piece::Jump { target, .. } = piece {
+                if !long[index]
+                    && i8::try_from(displacement(labels[target], offsets[index + 1])).is_err()
+                {
+                    long[index] = true;
+                    changed = true;
+                }
+            }
+        }
+        if !changed {
+            break (offsets, labels);
+        }
+    };
+    let mut code = Vec::with_capacity(offsets[pieces.len()]);
+    for (index, piece) in pieces.iter().enumerate() {
+        match piece {
+            Piece::Code(bytes) => code.extend(bytes),
+            Piece::Label(_) => {}
+            Piece::Jump { cond_code, target } => {
+                let displacement = displacement(labels[target], offsets[index + 1]);
+                match (cond_code, long[index]) {
+                    (None, false) => code.push(0xeb),
+                    (None, true) => code.push(0xe9),
+                    (Some(cond_code), false) => code.push(0x70 + condition(*cond_code)),
+                    (Some(cond_code), true) => code.extend([0x0f, 0x80 + condition(*cond_code)]),
+                }
+                if long[index] {
+                    code.extend(displacement.to_le_bytes());
+                } else {
+                    code.push(displacement as u8);
+                }
+            }
+        }
+    }
+    code
+}
+
+/// The displacement of a jump ending at `from` to `to`.
+fn displacement(to: usize, from: usize) -> i32 {
+    let displacement = i64::try_from(to).expect("code fits in 64 bits")
+        - i64::try_from(from).expect("code fits in 64 bits");
+    i32::try_from(displacement).expect("jumps are within 2GB")
+}
+
+/// A register or memory operand, the `r/m` of the ModRM byte.
+enum Rm {
+    Register(Register),
+    Memory {
+        base: Register,
+        index: Option<(Register, u8)>,
+        displacement: i32,
+    },
+}
+
+fn rm(operand: &Operand) -> Rm {
+    match operand {
+        Operand::Register(register) => Rm::Register(*register),
+        Operand::Stack(offset) => Rm::Memory {
+            base: Register::BP,
+            index: None,
+            displacement: -i32::try_from(*offset).expect("stack frame fits in 32 bits"),
+        },
+        Operand::Imm(_) | Operand::Pseudo(_) => {
+            unreachable!("{operand:?} isn't a register or in memory")
+        }
+    }
+}
+
+fn encode_instruction(code: &mut Vec<u8>, instruction: &Instruction) {
+    let four = RegisterSize::Four;
+    match instruction {
+        Instruction::Mov {
+            src: Operand::Imm(value),
+            dst: Operand::Register(dst),
+        } => {
+            if number(*dst) >= 8 {
+                code.push(0x41);
+            }
+            code.push(0xb8 + (number(*dst) & 7));
+            code.extend(value.to_le_bytes());
+        }
+        Instruction::Mov {
+            src: Operand::Imm(value),
+            dst,
+        } => {
+            encode_modrm(code, four, &[0xc7], 0, &rm(dst));
+            code.extend(value.to_le_bytes());
+        }
+        Instruction::Mov {
+            src: Operand::Register(src),
+            dst,
+        } => encode_modrm(code, four, &[0x89], number(*src), &rm(dst)),
+        Instruction::Mov {
+            src,
+            dst: Operand::Register(dst),
+        } => encode_modrm(code, four, &[0x8b], number(*dst), &rm(src)),
+        // mov %rbp, %rsp; pop %rbp; ret
+        Instruction::Return => code.extend([0x48, 0x89, 0xec, 0x5d, 0xc3]),
+        Instruction::Unary { operator, operand } => {
+            let extension = match operator {
+                UnaryOp::Neg => 3,
+                UnaryOp::Not => 2,
+            };
+            encode_modrm(code, four, &[0xf7], extension, &rm(operand));
+        }
+        Instruction::Binary {
+            operator: BinaryOp::Mult,
+            operand1,
+            operand2: Operand::Register(dst),
+        } => match operand1 {
+            Operand::Imm(value) => encode_with_immediate(
+                code,
+                four,
+                [0x6b, 0x69],
+                number(*dst),
+                &Rm::Register(*dst),
+                *value,
+            ),
+            src => encode_modrm(code, four, &[0x0f, 0xaf], number(*dst), &rm(src)),
+        },
+        Instruction::Binary {
+            operator: operator @ (BinaryOp::Shl | BinaryOp::Shr | BinaryOp::ShrLogical),
+            operand1,
+            operand2,
+        } => {
+            let extension = match operator {
+                BinaryOp::Shl => 4,
+                BinaryOp::ShrLogical => 5,
+                _ => 7,
+            };
+            match operand1 {
+                Operand::Imm(1) => encode_modrm(code, four, &[0xd1], extension, &rm(operand2)),
+                Operand::Imm(count) => {
+                    encode_modrm(code, four, &[0xc1], extension, &rm(operand2));
+                    code.push(*count as u8);
+                }
+                Operand::Register(Register::CX) => {
+                    encode_modrm(code, four, &[0xd3], extension, &rm(operand2));
+                }
+                _ => unreachable!("shift counts are immediates or in %cl"),
+            }
+        }
+        Instruction::Binary {
+            operator,
+            operand1,
+            operand2,
+        } => {
+            let (rm_reg, reg_rm, extension) = match operator {
+                BinaryOp::Add => (0x01, 0x03, 0),
+                BinaryOp::Or => (0x09, 0x0b, 1),
+                BinaryOp::And => (0x21, 0x23, 4),
+                BinaryOp::Sub => (0x29, 0x2b, 5),
+                BinaryOp::Xor => (0x31, 0x33, 6),
+                _ => unreachable!("{instruction:?} should have been fixed up"),
+            };
+            encode_arithmetic(code, [rm_reg, reg_rm], extension, operand1, operand2);
+        }
+        Instruction::Cmp { operand1, operand2 } => {
+            encode_arithmetic(code, [0x39, 0x3b], 7, operand1, operand2);
+        }
+        Instruction::Test {
+            operand1: Operand::Imm(value),
+            operand2: Operand::Register(Register::AX),
+        } => {
+            code.push(0xa9);
+            code.extend(value.to_le_bytes());
+        }
+        Instruction::Test {
+            operand1: Operand::Imm(value),
+            operand2,
+        } => {
+            encode_modrm(code, four, &[0xf7], 0, &rm(operand2));
+            code.extend(value.to_le_bytes());
+        }
+        Instruction::Test {
+            operand1: Operand::Register(register),
+            operand2: other,
+        }
+        | Instruction::Test {
+            operand1: other,
+            operand2: Operand::Register(register),
+        } => encode_modrm(code, four, &[0x85], number(*register), &rm(other)),
+        Instruction::SetCC { cond_code, operand } => encode_modrm(
+            code,
+            RegisterSize::One,
+            &[0x0f, 0x90 + condition(*cond_code)],
+            0,
+            &rm(operand),
+        ),
+        Instruction::Cmov {
+            cond_code,
+            src,
+            dst: Operand::Register(dst),
+        } => encode_modrm(
+            code,
+            four,
+            &[0x0f, 0x40 + condition(*cond_code)],
+            number(*dst),
+            &rm(src),
+        ),
+        Instruction::Idiv(operand) => encode_modrm(code, four, &[0xf7], 7, &rm(operand)),
+        Instruction::Imul(operand) => encode_modrm(code, four, &[0xf7], 5, &rm(operand)),
+        Instruction::Lea {
+            base: Operand::Register(base),
+            index,
+            scale,
+            displacement,
+            dst: Operand::Register(dst),
+        } => {
+            let index = index.as_ref().map(|index| match index {
+                Operand::Register(index) => (*index, *scale),
+                _ => unreachable!("lea only takes registers"),
+            });
+            let address = Rm::Memory {
+                base: *base,
+                index,
+                displacement: *displacement,
+            };
+            encode_modrm(code, four, &[0x8d], number(*dst), &address);
+        }
+        Instruction::Cdq => code.push(0x99),
+        Instruction::AllocateStack(bytes) => encode_with_immediate(
+            code,
+            RegisterSize::Eight,
+            [0x83, 0x81],
+            5,
+            &Rm::Register(Register::SP),
+            i32::try_from(*bytes).expect("stack frame fits in 32 bits"),
+        ),
+        Instruction::Push(register) => encode_push_pop(code, 0x50, *register),
+        Instruction::Pop(register) => encode_push_pop(code, 0x58, *register),
+        Instruction::Line(_) | Instruction::Comment(_) | Instruction::Variable { .. } => {}
+        _ => unreachable!("{instruction:?} should have been fixed up"),
+    }
+}
+
+/// `op r/m, reg`, `op reg, r/m` or `op r/m, imm`, given the operands in AT&T
+/// order.
+fn encode_arithmetic(
+    code: &mut Vec<u8>,
+    [rm_reg, reg_rm]: [u8; 2],
+    extension: u8,
+    src: &Operand,
+    dst: &Operand,
+) {
+    let four = RegisterSize::Four;
+    match (src, dst) {
+        // The shorter form for `%eax` doesn't have an 8 bit immediate.
+        (Operand::Imm(value), Operand::Register(Register::AX)) if i8::try_from(*value).is_err() => {
+            code.push(extension << 3 | 0x05);
+            code.extend(value.to_le_bytes());
+        }
+        (Operand::Imm(value), dst) => {
+            encode_with_immediate(code, four, [0x83, 0x81], extension, &rm(dst), *value);
+        }
+        (Operand::Register(src), dst) => {
+            encode_modrm(code, four, &[rm_reg], number(*src), &rm(dst))
+        }
+        (src, Operand::Register(dst)) => {
+            encode_modrm(code, four, &[reg_rm], number(*dst), &rm(src))
+        }
+        _ => unreachable!("at most one operand is in memory"),
+    }
+}
+
+/// Uses the opcode taking an 8 bit immediate if the value fits in one.
+fn encode_with_immediate(
+    code: &mut Vec<u8>,
+    size: RegisterSize,
+    [short, long]: [u8; 2],
+    reg: u8,
+    rm: &Rm,
+    value: i32,
+) {
+    match i8::try_from(value) {
+        Ok(value) => {
+            encode_modrm(code, size, &[short], reg, rm);
+            code.push(value as u8);
+        }
+        Err(_) => {
+            encode_modrm(code, size, &[long], reg, rm);
+            code.extend(value.to_le_bytes());
+        }
+    }
+}
+
+fn encode_push_pop(code: &mut Vec<u8>, opcode: u8, register: Register) {
+    if number(register) >= 8 {
+        code.push(0x41);
+    }
+    code.push(opcode + (number(register) & 7));
+}
+
+/// `[REX] opcode ModRM [SIB] [displacement]`, where `reg` is either a register
+/// or an extension of the opcode.
+fn encode_modrm(code: &mut Vec<u8>, size: RegisterSize, opcode: &[u8], reg: u8, rm: &Rm) {
+    let mut rex = 0;
+    if matches!(size, RegisterSize::Eight) {
+        rex |= 0b1000;
+    }
+    if reg >= 8 {
+        rex |= 0b0100;
+    }
+    // Without a REX prefix these would be %ah, %ch, %dh and %bh.
+    let mut needs_rex = false;
+    match rm {
+        Rm::Register(register) => {
+            rex |= number(*register) >> 3;
+            needs_rex = matches!(size, RegisterSize::One) && (4..8).contains(&number(*register));
+        }
+        Rm::Memory { base, index, .. } => {
+            rex |= number(*base) >> 3;
+            if let Some((index, _)) = index {
+                rex |= (number(*index) >> 3) << 1;
+            }
+        }
+    }
+    if rex != 0 || needs_rex {
+        code.push(0x40 | rex);
+    }
+    code.extend(opcode);
+    let reg = (reg & 7) << 3;
+    match rm {
+        Rm::Register(register) => code.push(0b1100_0000 | reg | (number(*register) & 7)),
+        Rm::Memory {
+            base,
+            index,
+            displacement,
+        } => {
+            let base = number(*base) & 7;
+            // `%rbp` and `%r13` without a displacement mean something else.
+            let displacement_size = if *displacement == 0 && base != 0b101 {
+                0
+            } else if i8::try_from(*displacement).is_ok() {
+                1
+            } else {
+                4
+            };
+            let mode = match displacement_size {
+                0 => 0b00,
+                1 => 0b01,
+                _ => 0b10,
+            };
+            // `%rsp` and `%r12` as the base always take a SIB byte.
+            if index.is_some() || base == 0b100 {
+                code.push(mode << 6 | reg | 0b100);
+                let (index, scale) =
+                    index.map_or((0b100, 1), |(index, scale)| (number(index), scale));
+                let scale = match scale {
+                    1 => 0,
+                    2 => 1,
+                    4 => 2,
+                    8 => 3,
+                    _ => unreachable!("scale is 1, 2, 4 or 8"),
+                };
+                code.push(scale << 6 | (index & 7) << 3 | base);
+            } else {
+                code.push(mode << 6 | reg | base);
+            }
+            match displacement_size {
+                0 => {}
+                1 => code.push(*displacement as u8),
+                _ => code.extend(displacement.to_le_bytes()),
+            }
+        }
+    }
+}
+
+/// The register's number in instruction encodings, the fourth bit goes in
+/// the REX prefix.
+fn number(register: Register) -> u8 {
+    match register {
+        Register::AX => 0,
+        Register::CX => 1,
+        Register::DX => 2,
+        Register::BX => 3,
+        Register::SP => 4,
+        Register::BP => 5,
+        Register::SI => 6,
+        Register::DI => 7,
+        Register::R8 => 8,
+        Register::R9 => 9,
+        Register::R10 => 10,
+        Register::R11 => 11,
+        Register::R12 => 12,
+        Register::R13 => 13,
+        Register::R14 => 14,
+        Register::R15 => 15,
+    }
+}
+
+/// The condition in the low bits of `jcc`, `setcc` and `cmovcc` opcodes.
+fn condition(cond_code: CondCode) -> u8 {
+    match cond_code {
+        CondCode::E => 0x4,
+        CondCode::NE => 0x5,
+        CondCode::L => 0xc,
+        CondCode::GE => 0xd,
+        CondCode::LE => 0xe,
+        CondCode::G => 0xf,
+    }
+}
@@ -1,13 +1,23 @@
 use std::{
+    env,
+    ffi::OsStr,
     fs,
-    path::PathBuf,
-    process::{Command, ExitCode},
+    io::{self, BufWriter, Write},
+    path::Path,
+    process::{self, Command, ExitCode, Stdio},
+    thread,
+    time::Duration,
 };
 
-use clap::Parser;
+use clap::{error::ErrorKind, CommandFactory, FromArgMatches};
 use comp::{
-    code_emission, code_gen, irc_gen::IrcGenerator, label_resolution::LabelResolver, lexer::Lexer,
-    parser, var_resolution::VarResolver, Error, Result,
+    c_emission,
+    code_emission::Syntax,
+    irc_interpreter, llvm_emission,
+    pass_manager::{AsmPass, IrcPass},
+    session::{Diagnostic, Options, Session, Severity, Stage},
+    target::{self, Target},
+    Error, Result,
 };
 
 /// C Compiler
@@ -15,7 +25,7 @@ use comp::{
 #[command(version, about, long_about = None)]
 struct Cli {
     /// Stop after lexing
-    #[arg(short, long)]
+    #[arg(long)]
     lex: bool,
 
     /// Stop after parsing
@@ -23,13 +33,67 @@ struct Cli {
     parse: bool,
 
     /// Stop after code gen
-    #[arg(short, long = "codegen")]
+    #[arg(long = "codegen")]
     code_gen: bool,
 
+    /// Compile and assemble to object files without linking, with the built-in assembler when
+    /// the target has one
+    #[arg(short = 'c')]
+    object: bool,
+
     /// Stop after generating assembly
     #[arg(short = 'S', long)]
     assembly: bool,
 
+    /// Only preprocess, printing the result or writing it to the -o file
+    #[arg(short = 'E')]
+    preprocess: bool,
+
+    /// Write the output to FILE, `-` for stdout. By default the input's file name with the
+    /// output's extension in the current directory, or `a.out` for the executable
+    #[arg(short = 'o', value_name = "FILE")]
+    output: Option<String>,
+
+    /// Add a directory to search for headers
+    #[arg(short = 'I', value_name = "DIR")]
+    include_dirs: Vec<String>,
+
+    /// Define a macro, `NAME` or `NAME=VALUE`
+    #[arg(short = 'D', value_name = "MACRO")]
+    defines: Vec<String>,
+
+    /// Add a directory to search for libraries when linking
+    #[arg(short = 'L', value_name = "DIR")]
+    library_dirs: Vec<String>,
+
+    /// Link with a library, searched when it's reached among the files
+    #[arg(short = 'l', value_name = "LIBRARY")]
+    libraries: Vec<String>,
+
+    /// How many of the files come before each library on the command line.
+    #[arg(skip)]
+    library_positions: Vec<usize>,
+
+    /// Warning option, passed to the C compiler that preprocesses, assembles and links
+    #[arg(short = 'W', value_name = "WARNING")]
+    warnings: Vec<String>,
+
+    /// Code generation option, passed to the C compiler that preprocesses, assembles and links
+    #[arg(short = 'f', value_name = "OPTION")]
+    flags: Vec<String>,
+
+    /// C standard, passed to the C compiler that preprocesses
+    #[arg(long, value_name = "STANDARD")]
+    std: Option<String>,
+
+    /// Keep the preprocessed source and the assembly in the current directory
+    #[arg(long)]
+    save_temps: bool,
+
+    /// Compiler driver used to assemble and link, instead of the target's gcc
+    #[arg(long, value_name = "PROGRAM", env = "COMP_CC")]
+    cc: Option<String>,
+
     /// Stop after generating irc
     #[arg(short, long, alias = "tacky")]
     irc: bool,
@@ -38,108 +102,687 @@ struct Cli {
     #[arg(short, long)]
     validate: bool,
 
-    /// C source code file
-    #[arg(required = true)]
-    file: String,
+    /// Target to generate code for, `x86_64-linux-gnu`, `aarch64-linux-gnu`, `riscv64-linux-gnu`
+    /// or `wasm32-unknown-unknown`, which only writes a `.wat` module with -S
+    #[arg(long, value_name = "TRIPLE", default_value = "x86_64-linux-gnu", value_parser = parse_target)]
+    target: &'static dyn Target,
+
+    /// Assembly syntax to emit, `att` or `intel`, x86-64 only
+    #[arg(long, value_name = "SYNTAX", default_value = "att", value_parser = parse_syntax)]
+    masm: Syntax,
+
+    /// Annotate the assembly with the source lines and irc instructions it came from, x86-64 only
+    #[arg(long)]
+    verbose_asm: bool,
+
+    /// Generate debug info, x86-64 only
+    #[arg(short = 'g')]
+    debug: bool,
+
+    /// Optimization level, 0 to 2. Like gcc, -O is -O1, -Og is -O1, and -O3, -Ofast and -Os are
+    /// -O2
+    #[arg(short = 'O', value_name = "LEVEL", default_value_t = 0, value_parser = parse_opt_level)]
+    opt_level: u8,
+
+    /// Comma separated irc and asm passes to run instead of the ones from the optimization level
+    #[arg(long, value_delimiter = ',', value_parser = parse_pass)]
+    passes: Option<Vec<Pass>>,
+
+    /// Print the program after every run of the given pass
+    #[arg(long, value_name = "PASS", value_parser = parse_print_after)]
+    print_after: Option<String>,
+
+    /// Report the time spent in each pass
+    #[arg(long)]
+    time_passes: bool,
+
+    /// Fold constant expressions in irc, same as adding `fold` to the passes
+    #[arg(long)]
+    fold_constants: bool,
+
+    /// Remove unreachable code, useless jumps and unused labels in irc, same as adding `dce`
+    #[arg(long)]
+    eliminate_unreachable_code: bool,
+
+    /// Propagate copies in irc, same as adding `copyprop`
+    #[arg(long)]
+    propagate_copies: bool,
+
+    /// Remove stores to variables that are never read in irc, same as adding `dse`
+    #[arg(long)]
+    eliminate_dead_stores: bool,
+
+    /// Run the peephole optimizer over the assembly, on by default from -O1
+    #[arg(long)]
+    peephole: bool,
+
+    /// Run the optimized irc in an interpreter and print the value returned by main
+    #[arg(long)]
+    interpret: bool,
+
+    /// Compile in memory and run the program, exiting with the value returned by main
+    #[arg(long)]
+    jit: bool,
+
+    /// Print the optimized irc as `c` or `llvm` IR instead of compiling it, or write it to the -o
+    /// file
+    #[arg(long, value_name = "LANGUAGE", value_parser = parse_emit)]
+    emit: Option<Emit>,
+
+    /// C sources (`.c`), preprocessed sources (`.i`), assembly (`.s`, `.S`) and anything else
+    /// is passed to the linker
+    #[arg(required = true, value_name = "FILES")]
+    files: Vec<String>,
 }
 
-fn preprocess(file: &str) -> Result<()> {
-    let output = Command::new("gcc")
-        .args(["-E", "-P", &format!("{file}.c"), "-o", &format!("{file}.i")])
-        .output()
-        .map_err(|e| Error::IO(format!("Couldn't run gcc to preprocess:\n - {e}")))?;
-    if output.status.code() != Some(0) {
-        return Err(Error::Preprocess(format!(
-            "Error preprocessing file '{file}.c':\n - stderr: '{}'",
-            String::from_utf8_lossy(&output.stderr)
-        )));
+/// Languages `--emit` can print the irc in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Emit {
+    C,
+    Llvm,
+}
+
+impl Emit {
+    const ALL: [Self; 2] = [Self::C, Self::Llvm];
+
+    fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|emit| emit.name() == name)
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Self::C => "c",
+            Self::Llvm => "llvm",
+        }
     }
-    Ok(())
 }
 
-fn compile(file: &str, cli: &Cli) -> Result<()> {
-    let source = fs::read_to_string(format!("{file}.i"))
-        .map_err(|e| Error::IO(format!("Couldn't read file `{file}`:\n - {e}")))?;
-    let mut lexer = Lexer::new(&source);
-    lexer.tokenize()?;
-    if cli.lex {
-        return Ok(());
-    }
-    let mut parser = parser::Parser::new(&lexer);
-    let mut ast = parser.parse()?;
-    if cli.parse {
-        return Ok(());
-    }
-    let mut var_resolver = VarResolver::new(0);
-    var_resolver.resolve_program(&mut ast)?;
-    let mut label_rsolver = LabelResolver::new(var_resolver.counter);
-    label_rsolver.resolve_program(&mut ast)?;
-    if cli.validate {
-        return Ok(());
-    }
-    let (irc, stack_allocation) = IrcGenerator::gen_program(ast, label_rsolver.counter);
-    if cli.irc {
-        return Ok(());
-    }
-    let mut asm_program = code_gen::gen_program(irc);
-    code_gen::replace_pseudo(&mut asm_program);
-    code_gen::fix_instructions(&mut asm_program, stack_allocation);
-    if cli.code_gen {
-        return Ok(());
-    }
-    let assembly = code_emission::emit_program(asm_program);
-    fs::write(format!("{file}.s"), assembly)
-        .map_err(|e| Error::IO(format!("Couldn't write file '{file}.s': - {e}")))?;
-    Ok(())
+/// A pass of either level, by the name `--passes` and `--print-after`
+/// take.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Pass {
+    Irc(IrcPass),
+    Asm(AsmPass),
+}
+
+impl Pass {
+    fn all() -> impl Iterator<Item = Self> {
+        let irc = IrcPass::ALL.into_iter().map(Self::Irc);
+        irc.chain(AsmPass::ALL.into_iter().map(Self::Asm))
+    }
+
+    fn from_name(name: &str) -> Option<Self> {
+        Self::all().find(|pass| pass.name() == name)
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Self::Irc(pass) => pass.name(),
+            Self::Asm(pass) => pass.name(),
+        }
+    }
+}
+
+/// An input file, by what its extension says it holds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Input<'a> {
+    Source(&'a str),
+    Preprocessed(&'a str),
+    Assembly(&'a str),
+    /// Objects and libraries, only passed to the linker.
+    Linker(&'a str),
+}
+
+impl<'a> Input<'a> {
+    fn new(path: &'a str) -> Self {
+        match Path::new(path)
+            .extension()
+            .and_then(|extension| extension.to_str())
+        {
+            Some("c") => Self::Source(path),
+            Some("i") => Self::Preprocessed(path),
+            Some("s" | "S") => Self::Assembly(path),
+            _ => Self::Linker(path),
+        }
+    }
+
+    fn path(self) -> &'a str {
+        match self {
+            Self::Source(path)
+            | Self::Preprocessed(path)
+            | Self::Assembly(path)
+            | Self::Linker(path) => path,
+        }
+    }
+}
+
+/// What the driver still has to do after compiling a source.
+enum Compiled {
+    Done,
+    Failed,
+    /// The program ran in memory and returned this.
+    Exited(i32),
+    Assembly(String),
+}
+
+/// Something to link, generated assembly is written to a temporary file
+/// unless it's the only one and can be piped.
+enum LinkInput {
+    File(String),
+    Assembly { stem: String, assembly: String },
+}
+
+/// How far the session has to go for the flags given.
+fn stage(cli: &Cli) -> Stage {
+    if cli.preprocess {
+        Stage::Preprocess
+    } else if cli.lex {
+        Stage::Lex
+    } else if cli.parse {
+        Stage::Parse
+    } else if cli.validate {
+        Stage::Validate
+    } else if cli.interpret || cli.emit.is_some() || cli.irc {
+        Stage::Irc
+    } else if cli.code_gen {
+        Stage::CodeGen
+    } else if cli.jit {
+        Stage::Run
+    } else if cli.object {
+        Stage::Object
+    } else {
+        Stage::Assembly
+    }
+}
+
+fn options(path: &str, preprocess: bool, cli: &Cli) -> Result<Options> {
+    let (mut irc_passes, mut asm_passes) = match &cli.passes {
+        Some(passes) => (
+            passes
+                .iter()
+                .filter_map(|pass| match pass {
+                    Pass::Irc(pass) => Some(*pass),
+                    Pass::Asm(_) => None,
+                })
+                .collect(),
+            passes
+                .iter()
+                .filter_map(|pass| match pass {
+                    Pass::Asm(pass) => Some(*pass),
+                    Pass::Irc(_) => None,
+                })
+                .collect(),
+        ),
+        None => (
+            IrcPass::for_level(cli.opt_level),
+            AsmPass::for_level(cli.opt_level),
+        ),
+    };
+    for (enabled, pass) in [
+        (cli.fold_constants, IrcPass::ConstantFolding),
+        (cli.eliminate_unreachable_code, IrcPass::UnreachableCode),
+        (cli.propagate_copies, IrcPass::CopyPropagation),
+        (cli.eliminate_dead_stores, IrcPass::DeadStores),
+    ] {
+        if enabled && !irc_passes.contains(&pass) {
+            irc_passes.push(pass);
+        }
+    }
+    if cli.peephole && !asm_passes.contains(&AsmPass::Peephole) {
+        asm_passes.push(AsmPass::Peephole);
+    }
+    let debug_directory = if cli.debug {
+        Some(
+            env::current_dir()
+                .map_err(|e| Error::IO(format!("Couldn't get the current directory:\n - {e}")))?
+                .to_string_lossy()
+                .into_owned(),
+        )
+    } else {
+        None
+    };
+    Ok(Options {
+        target: cli.target,
+        irc_passes,
+        asm_passes,
+        print_after: cli.print_after.clone(),
+        time_passes: cli.time_passes,
+        preprocess,
+        include_dirs: cli.include_dirs.clone(),
+        defines: cli.defines.clone(),
+        // Targets without a compiler can still be preprocessed for.
+        cc: cc(cli).unwrap_or("gcc").to_string(),
+        cc_flags: cc_flags(cli),
+        syntax: cli.masm,
+        verbose_asm: cli.verbose_asm,
+        file_name: path.to_string(),
+        debug_directory,
+    })
+}
+
+fn parse_target(name: &str) -> std::result::Result<&'static dyn Target, String> {
+    target::from_name(name).ok_or_else(|| {
+        let names: Vec<_> = target::ALL.iter().map(|target| target.name()).collect();
+        format!("unknown target, expected one of: {}", names.join(", "))
+    })
+}
+
+fn parse_opt_level(level: &str) -> std::result::Result<u8, String> {
+    match level {
+        "g" => Ok(1),
+        "s" | "z" | "fast" => Ok(2),
+        _ => level
+            .parse::<u8>()
+            .map(|level| level.min(2))
+            .map_err(|_| "expected a number, `g`, `s`, `z` or `fast`".to_string()),
+    }
+}
+
+fn parse_emit(name: &str) -> std::result::Result<Emit, String> {
+    Emit::from_name(name).ok_or_else(|| {
+        let names: Vec<_> = Emit::ALL.iter().map(|emit| emit.name()).collect();
+        format!("unknown language, expected one of: {}", names.join(", "))
+    })
+}
+
+fn parse_syntax(name: &str) -> std::result::Result<Syntax, String> {
+    Syntax::from_name(name).ok_or_else(|| {
+        let names: Vec<_> = Syntax::ALL.iter().map(|syntax| syntax.name()).collect();
+        format!("unknown syntax, expected one of: {}", names.join(", "))
+    })
+}
+
+fn print_timings(cli: &Cli, timings: &[(&'static str, Duration)]) {
+    if cli.time_passes {
+        for (pass, time) in timings {
+            eprintln!("{pass:>10}: {time:?}");
+        }
+    }
+}
+
+fn parse_pass(name: &str) -> std::result::Result<Pass, String> {
+    Pass::from_name(name).ok_or_else(|| {
+        let names: Vec<_> = Pass::all().map(|pass| pass.name()).collect();
+        format!("unknown pass, expected one of: {}", names.join(", "))
+    })
+}
+
+fn parse_print_after(name: &str) -> std::result::Result<String, String> {
+    parse_pass(name).map(|pass| pass.name().to_string())
+}
+
+/// The file name without its directory and extension.
+fn base_name(path: &str) -> String {
+    Path::new(path)
+        .file_stem()
+        .unwrap_or_default()
+        .to_string_lossy()
+        .into_owned()
+}
+
+/// Where the output compiled from `path` goes, in the current directory
+/// like gcc puts it unless `-o` says otherwise.
+fn output_path(cli: &Cli, path: &str, extension: &str) -> String {
+    cli.output
+        .clone()
+        .unwrap_or_else(|| format!("{}.{extension}", base_name(path)))
+}
+
+/// The file compiling `input` writes, besides the temporaries.
+fn input_output(cli: &Cli, input: Input) -> Option<String> {
+    match (stage(cli), input) {
+        (Stage::Preprocess, Input::Source(_)) => cli.output.clone(),
+        (Stage::Irc, Input::Source(_) | Input::Preprocessed(_)) if cli.emit.is_some() => {
+            cli.output.clone()
+        }
+        (
+            Stage::Object,
+            Input::Source(path) | Input::Preprocessed(path) | Input::Assembly(path),
+        ) => Some(output_path(cli, path, "o")),
+        (Stage::Assembly, Input::Source(path) | Input::Preprocessed(path)) if cli.assembly => {
+            Some(output_path(cli, path, cli.target.assembly_extension()))
+        }
+        _ => None,
+    }
+}
+
+/// The files the flags make the driver write for each input.
+fn outputs(cli: &Cli) -> Vec<String> {
+    cli.files
+        .iter()
+        .filter_map(|file| input_output(cli, Input::new(file)))
+        .collect()
+}
+
+/// Removes what a failed build may have left behind.
+fn remove_output(path: &str) {
+    if path != "-" {
+        let _ = fs::remove_file(path);
+    }
+}
+
+fn write_file(path: &str, contents: impl AsRef<[u8]>) -> Result<()> {
+    fs::write(path, contents).map_err(|e| Error::IO(format!("Couldn't write file '{path}': - {e}")))
+}
+
+/// Writes an output the user asked for, `-` being stdout.
+fn write_output(path: &str, contents: impl AsRef<[u8]>) -> Result<()> {
+    if path != "-" {
+        return write_file(path, contents);
+    }
+    io::stdout()
+        .lock()
+        .write_all(contents.as_ref())
+        .map_err(|e| Error::IO(format!("Couldn't write to stdout: - {e}")))
+}
+
+/// Opens an output the user asked for to write as it's generated, `-` being
+/// stdout.
+fn create_output(path: &str) -> Result<Box<dyn Write>> {
+    if path == "-" {
+        return Ok(Box::new(io::stdout().lock()));
+    }
+    let file = fs::File::create(path)
+        .map_err(|e| Error::IO(format!("Couldn't create file '{path}': - {e}")))?;
+    Ok(Box::new(BufWriter::new(file)))
+}
+
+/// The compiler driver that assembles and links.
+fn cc(cli: &Cli) -> Result<&str> {
+    cli.cc.as_deref().or(cli.target.gcc()).ok_or_else(|| {
+        Error::Assemble(format!(
+            "There's no assembler for {}, use -S to only generate the assembly",
+            cli.target.name()
+        ))
+    })
+}
+
+/// The gcc flags the driver only passes on.
+fn cc_flags(cli: &Cli) -> Vec<String> {
+    let warnings = cli.warnings.iter().map(|warning| format!("-W{warning}"));
+    let flags = cli.flags.iter().map(|flag| format!("-f{flag}"));
+    let std = cli.std.iter().map(|std| format!("-std={std}"));
+    warnings.chain(flags).chain(std).collect()
 }
 
-fn assemble(file: &str) -> Result<()> {
-    let output = Command::new("gcc")
-        .args(["-Wa,--fatal-warnings", &format!("{file}.s"), "-o", file])
-        .output()
-        .map_err(|e| Error::IO(format!("Couldn't run gcc to assemble:\n - {e}")))?;
+/// Runs `cc` to build `output`, with `stdin` piped in when it's given.
+fn run_cc<S: AsRef<OsStr>>(cli: &Cli, args: &[S], stdin: Option<&str>, output: &str) -> Result<()> {
+    let cc = cc(cli)?;
+    let mut child = Command::new(cc)
+        .arg("-Wa,--fatal-warnings")
+        .args(cc_flags(cli))
+        .args(args)
+        .args(["-o", output])
+        .stdin(if stdin.is_some() {
+            Stdio::piped()
+        } else {
+            Stdio::null()
+        })
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| Error::IO(format!("Couldn't run {cc}:\n - {e}")))?;
+    let pipe = child.stdin.take();
+    let result = thread::scope(|scope| {
+        if let (Some(mut pipe), Some(stdin)) = (pipe, stdin) {
+            scope.spawn(move || pipe.write_all(stdin.as_bytes()));
+        }
+        child.wait_with_output()
+    })
+    .map_err(|e| Error::IO(format!("Couldn't run {cc}:\n - {e}")))?;
 
-    if output.status.code() != Some(0) {
+    if result.status.code() != Some(0) {
         return Err(Error::Assemble(format!(
-            "Error Assembling file '{file}.s':\n - stderr: '{}'",
-            String::from_utf8_lossy(&output.stderr)
+            "Error building '{output}' with {cc}:\n - stderr: '{}'",
+            String::from_utf8_lossy(&result.stderr)
         )));
     }
     Ok(())
 }
 
-fn run(file: &str, cli: &Cli) -> Result<()> {
-    preprocess(file)?;
-    compile(file, cli)?;
-    let _ = fs::remove_file(format!("{file}.i"));
-    if cli.assembly || cli.lex || cli.parse || cli.code_gen || cli.irc || cli.validate {
-        return Ok(());
+fn link(cli: &Cli, inputs: &[LinkInput], output: &str) -> Result<()> {
+    let directory = env::temp_dir().join(format!("comp-{}", process::id()));
+    let result = link_in(cli, inputs, &directory, output);
+    let _ = fs::remove_dir_all(&directory);
+    result
+}
+
+/// Links, writing the generated assembly to `directory` when there's more
+/// than one to pipe in.
+fn link_in(cli: &Cli, inputs: &[LinkInput], directory: &Path, output: &str) -> Result<()> {
+    let generated = inputs
+        .iter()
+        .filter(|input| matches!(input, LinkInput::Assembly { .. }))
+        .count();
+    let mut args: Vec<_> = cli
+        .library_dirs
+        .iter()
+        .map(|dir| format!("-L{dir}"))
+        .collect();
+    let mut stdin = None;
+    let mut libraries = cli.libraries.iter().zip(&cli.library_positions).peekable();
+    for (index, input) in inputs.iter().enumerate() {
+        while let Some((library, _)) = libraries.next_if(|(_, &position)| position <= index) {
+            args.push(format!("-l{library}"));
+        }
+        match input {
+            LinkInput::File(path) => args.push(path.clone()),
+            LinkInput::Assembly { assembly, .. } if generated == 1 => {
+                args.extend(["-x", "assembler", "-", "-x", "none"].map(String::from));
+                stdin = Some(assembly.as_str());
+            }
+            LinkInput::Assembly { stem, assembly } => {
+                fs::create_dir_all(directory).map_err(|e| {
+                    Error::IO(format!("Couldn't create '{}': - {e}", directory.display()))
+                })?;
+                let name = Path::new(stem).file_name().unwrap_or_default();
+                let path = directory.join(format!("{index}-{}.s", name.to_string_lossy()));
+                let path = path.to_string_lossy().into_owned();
+                write_file(&path, assembly)?;
+                args.push(path);
+            }
+        }
     }
-    assemble(file)?;
-    let _ = fs::remove_file(format!("{file}.s"));
-    Ok(())
+    args.extend(libraries.map(|(library, _)| format!("-l{library}")));
+    run_cc(cli, &args, stdin, output)
+}
+
+/// Compiles a `.c` or `.i` file up to `stage`, writing what doesn't need
+/// the assembler.
+fn compile(path: &str, preprocess: bool, cli: &Cli, stage: Stage) -> Result<Compiled> {
+    let session = Session::open(path, options(path, preprocess, cli)?)?;
+    let output = if stage == Stage::Assembly && cli.assembly {
+        let mut out = create_output(&output_path(cli, path, cli.target.assembly_extension()))?;
+        session.emit_assembly(&mut out)
+    } else {
+        session.compile(stage)
+    };
+    print_timings(cli, &output.timings);
+    for diagnostic in &output.diagnostics {
+        eprintln!("{diagnostic}");
+    }
+    if output.failed() {
+        return Ok(Compiled::Failed);
+    }
+    if let Some(preprocessed) = &output.preprocessed {
+        if stage == Stage::Preprocess {
+            write_output(cli.output.as_deref().unwrap_or("-"), preprocessed)?;
+        } else if cli.save_temps {
+            write_file(&format!("{}.i", base_name(path)), preprocessed)?;
+        }
+    }
+    if let Some(status) = output.status {
+        return Ok(Compiled::Exited(status));
+    }
+    if let Some(irc) = &output.irc {
+        if cli.interpret {
+            println!("{}", irc_interpreter::run(irc)?);
+        } else if let Some(emit) = cli.emit {
+            let out = create_output(cli.output.as_deref().unwrap_or("-"))?;
+            match emit {
+                Emit::C => c_emission::emit_program(out, irc),
+                Emit::Llvm => llvm_emission::emit_program(out, irc),
+            }
+            .map_err(|e| Error::IO(format!("Couldn't print the {} code:\n - {e}", emit.name())))?;
+        }
+    }
+    if let Some(object) = &output.object {
+        write_output(&output_path(cli, path, "o"), object)?;
+    }
+    Ok(output.assembly.map_or(Compiled::Done, Compiled::Assembly))
+}
+
+fn warn_unused(path: &str) {
+    let warning = Diagnostic {
+        severity: Severity::Warning,
+        message: format!("'{path}' is unused, it isn't compiled or linked"),
+    };
+    eprintln!("{warning}");
+}
+
+fn run(cli: &Cli) -> Result<ExitCode> {
+    let stage = stage(cli);
+    let links = stage == Stage::Assembly && !cli.assembly;
+    // The built-in assembler doesn't leave the assembly for --save-temps and
+    // doesn't encode the debug info.
+    let builtin_assembler =
+        cli.target.has_assembler() && !((cli.save_temps || cli.debug) && cc(cli).is_ok());
+    let session_stage = if stage == Stage::Object && !builtin_assembler {
+        Stage::Assembly
+    } else {
+        stage
+    };
+    let mut link_inputs = Vec::new();
+    for input in cli.files.iter().map(|file| Input::new(file)) {
+        let result = build_input(cli, input, stage, session_stage, &mut link_inputs);
+        if !matches!(result, Ok(None)) {
+            // Like gcc, the outputs of the inputs built before are kept.
+            if let Some(output) = input_output(cli, input) {
+                remove_output(&output);
+            }
+            return result.map(|code| code.unwrap_or(ExitCode::FAILURE));
+        }
+    }
+    if links {
+        let output = cli.output.clone().unwrap_or_else(|| "a.out".to_string());
+        if let Err(err) = link(cli, &link_inputs, &output) {
+            remove_output(&output);
+            return Err(err);
+        }
+    }
+    Ok(ExitCode::SUCCESS)
+}
+
+/// Builds one input as far as `stage`, collecting what has to be linked.
+/// Returns the exit code when the driver should stop there.
+fn build_input(
+    cli: &Cli,
+    input: Input,
+    stage: Stage,
+    session_stage: Stage,
+    link_inputs: &mut Vec<LinkInput>,
+) -> Result<Option<ExitCode>> {
+    let links = stage == Stage::Assembly && !cli.assembly;
+    let path = input.path();
+    let stem = base_name(path);
+    match input {
+        Input::Preprocessed(_) if stage == Stage::Preprocess => warn_unused(path),
+        Input::Source(_) | Input::Preprocessed(_) => {
+            let assembly =
+                match compile(path, matches!(input, Input::Source(_)), cli, session_stage)? {
+                    Compiled::Done => return Ok(None),
+                    Compiled::Failed => return Ok(Some(ExitCode::FAILURE)),
+                    Compiled::Exited(status) => return Ok(Some(ExitCode::from(status as u8))),
+                    Compiled::Assembly(assembly) => assembly,
+                };
+            if cli.save_temps {
+                let temporary = format!("{stem}.{}", cli.target.assembly_extension());
+                write_file(&temporary, assembly)?;
+                if stage == Stage::Object {
+                    run_cc(cli, &["-c", &temporary], None, &output_path(cli, path, "o"))?;
+                } else {
+                    link_inputs.push(LinkInput::File(temporary));
+                }
+            } else if stage == Stage::Object {
+                run_cc(
+                    cli,
+                    &["-c", "-x", "assembler", "-"],
+                    Some(&assembly),
+                    &output_path(cli, path, "o"),
+                )?;
+            } else {
+                link_inputs.push(LinkInput::Assembly { stem, assembly });
+            }
+        }
+        Input::Assembly(_) if stage == Stage::Object => {
+            run_cc(cli, &["-c", path], None, &output_path(cli, path, "o"))?;
+        }
+        Input::Assembly(_) | Input::Linker(_) if links => {
+            link_inputs.push(LinkInput::File(path.to_string()));
+        }
+        Input::Assembly(_) | Input::Linker(_) => warn_unused(path),
+    }
+    Ok(None)
+}
+
+/// The arguments, with the single dash options of gcc that clap can't
+/// parse rewritten to ones it can.
+fn gcc_args() -> impl Iterator<Item = String> {
+    env::args().map(|arg| match arg.as_str() {
+        "-O" => "-O1".to_string(),
+        _ if arg.starts_with("-std=") => format!("-{arg}"),
+        _ => arg,
+    })
+}
+
+fn parse_cli() -> Cli {
+    let matches = Cli::command().get_matches_from(gcc_args());
+    let mut cli = Cli::from_arg_matches(&matches).unwrap_or_else(|e| e.exit());
+    let files: Vec<_> = matches.indices_of("files").into_iter().flatten().collect();
+    cli.library_positions = matches
+        .indices_of("libraries")
+        .into_iter()
+        .flatten()
+        .map(|library| files.iter().filter(|&&file| file < library).count())
+        .collect();
+    cli
 }
 
 fn main() -> ExitCode {
-    let cli = Cli::parse();
-    let mut file = PathBuf::from(&cli.file);
-    file.set_extension("");
-    let file = file.to_string_lossy();
-    match run(&file, &cli) {
-        Ok(()) => ExitCode::SUCCESS,
+    let cli = parse_cli();
+    if cli.output.is_some() && outputs(&cli).len() > 1 {
+        Cli::command()
+            .error(
+                ErrorKind::ArgumentConflict,
+                "-o can't be used with -c, -S, -E or --emit and several inputs",
+            )
+            .exit();
+    }
+    if cli.jit && cli.files.len() > 1 {
+        Cli::command()
+            .error(
+                ErrorKind::ArgumentConflict,
+                "--jit runs a single source file",
+            )
+            .exit();
+    }
+    if !cli.target.has_emission_options()
+        && (cli.masm != Syntax::Att || cli.verbose_asm || cli.debug)
+    {
+        Cli::command()
+            .error(
+                ErrorKind::ArgumentConflict,
+                format!(
+                    "--masm, --verbose-asm and -g aren't supported for {}",
+                    cli.target.name()
+                ),
+            )
+            .exit();
+    }
+    match run(&cli) {
+        Ok(code) => code,
         Err(err) => {
-            match err {
-                Error::IO(err) => eprintln!("System IO Error:\n - {err}"),
-                Error::Preprocess(err) => eprintln!("Preprocessor Error:\n - {err}"),
-                Error::Assemble(err) => eprintln!("Assembling Error:\n - {err}"),
-                Error::Lexer(err) => eprintln!("Lexer Error:\n - {err}"),
-                Error::InvalidToken(err) => eprintln!("Invalid Token:\n - {err}"),
-                Error::Parser(parse_error) => eprintln!("Parser Error:\n - {parse_error}"),
-                Error::Resolver(err) => eprintln!("Variable Resolution Error:\n - {err}"),
-            };
-            let _ = fs::remove_file(format!("{file}.i"));
-            let _ = fs::remove_file(format!("{file}.s"));
-            let _ = fs::remove_file(format!("{file}"));
+            eprintln!("{err}");
             ExitCode::FAILURE
         }
     }
@@ -45,13 +45,13 @@ impl Display for ParseError {
     }
 }
 
-pub struct Parser<'de> {
-    lexer: &'de Lexer<'de>,
-    tokens: &'de [Token<'de>],
+pub struct Parser<'t, 'de> {
+    lexer: &'t Lexer<'de>,
+    tokens: &'t [Token<'de>],
 }
 
-impl<'de> Parser<'de> {
-    pub fn new(lexer: &'de Lexer) -> Self {
+impl<'t, 'de> Parser<'t, 'de> {
+    pub fn new(lexer: &'t Lexer<'de>) -> Self {
         Self {
             lexer,
             tokens: &lexer.tokens,
@@ -76,13 +76,13 @@ impl<'de> Parser<'de> {
     }
 
     fn function(&mut self) -> Result<Function<'de>, ParseError> {
-        self.expect(TokenKind::Int)?;
+        let line = source_line(&self.expect(TokenKind::Int)?);
         let name = self.expect(TokenKind::Identifier)?.lexeme;
         self.expect(TokenKind::LeftParen)?;
         self.expect(TokenKind::Void)?;
         self.expect(TokenKind::RightParen)?;
         let body = self.block()?;
-        Ok(Function { name, body })
+        Ok(Function { name, body, line })
     }
 
     fn block(&mut self) -> Result<Block, ParseError> {
@@ -108,7 +108,7 @@ impl<'de> Parser<'de> {
     }
 
     fn decleration(&mut self) -> Result<Decleration, ParseError> {
-        self.expect(TokenKind::Int)?;
+        let line = source_line(&self.expect(TokenKind::Int)?);
         let name = self.expect(TokenKind::Identifier)?.lexeme.to_string();
         let init = if self.try_consume(TokenKind::Equal).is_some() {
             Some(self.expression(0)?)
@@ -116,7 +116,7 @@ impl<'de> Parser<'de> {
             None
         };
         self.expect(TokenKind::Semicolon)?;
-        Ok(Decleration::Decleration { name, init })
+        Ok(Decleration::Decleration { name, init, line })
     }
 
     fn for_init(&mut self) -> Result<ForInit, ParseError> {
@@ -169,6 +169,19 @@ impl<'de> Parser<'de> {
     }
 
     fn statement(&mut self) -> Result<Stmt, ParseError> {
+        let line = self
+            .tokens
+            .first()
+            .or(self.lexer.tokens.last())
+            .map_or(0, source_line);
+        match self.unlocated_statement()? {
+            // The statements inside them carry their own lines.
+            stmt @ (Stmt::Label(..) | Stmt::Compound(_)) => Ok(stmt),
+            stmt => Ok(Stmt::Line(line, stmt.into())),
+        }
+    }
+
+    fn unlocated_statement(&mut self) -> Result<Stmt, ParseError> {
         if self.try_consume(TokenKind::Return).is_some() {
             let return_value = self.expression(0)?;
             self.expect(TokenKind::Semicolon)?;
@@ -424,3 +437,13 @@ impl<'de> Parser<'de> {
         self.lexer.tokens.last().map_or(0, |token| token.line)
     }
 }
+
+/// The line a token is on for the annotations and debug info, 0 when it's
+/// from another file than the one being compiled.
+fn source_line(token: &Token) -> usize {
+    if token.main_file {
+        token.line
+    } else {
+        0
+    }
+}
@@ -0,0 +1,229 @@
+use std::{
+    fmt::Display,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    asm_ast, constant_folding, copy_propagation, dead_store_elimination, if_conversion, irc,
+    peephole, register_allocation, ssa, unreachable_code,
+};
+
+pub trait Pass<P> {
+    fn name(&self) -> &'static str;
+
+    /// Runs the pass, returning `true` if it changed the program.
+    fn run(&self, program: &mut P) -> bool;
+
+    /// Whether the pass only runs in the first iteration, for passes that
+    /// would keep undoing the work of the others.
+    fn run_once(&self) -> bool {
+        false
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IrcPass {
+    ConstantFolding,
+    UnreachableCode,
+    CopyPropagation,
+    DeadStores,
+    IfConversion,
+    /// Converts into SSA form and back out, not part of any optimization level.
+    SsaRoundTrip,
+}
+
+impl IrcPass {
+    pub const ALL: [Self; 6] = [
+        Self::ConstantFolding,
+        Self::UnreachableCode,
+        Self::CopyPropagation,
+        Self::DeadStores,
+        Self::IfConversion,
+        Self::SsaRoundTrip,
+    ];
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|pass| pass.name() == name)
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::ConstantFolding => "fold",
+            Self::UnreachableCode => "dce",
+            Self::CopyPropagation => "copyprop",
+            Self::DeadStores => "dse",
+            Self::IfConversion => "select",
+            Self::SsaRoundTrip => "ssa",
+        }
+    }
+
+    /// The passes enabled at optimization level `-O{level}`.
+    pub fn for_level(level: u8) -> Vec<Self> {
+        match level {
+            0 => Vec::new(),
+            1 => vec![Self::ConstantFolding, Self::UnreachableCode],
+            _ => vec![
+                Self::ConstantFolding,
+                Self::UnreachableCode,
+                Self::CopyPropagation,
+                Self::DeadStores,
+                Self::IfConversion,
+            ],
+        }
+    }
+}
+
+impl<'a> Pass<irc::Program<'a>> for IrcPass {
+    fn name(&self) -> &'static str {
+        IrcPass::name(*self)
+    }
+
+    fn run_once(&self) -> bool {
+        matches!(self, Self::SsaRoundTrip)
+    }
+
+    fn run(&self, program: &mut irc::Program<'a>) -> bool {
+        match self {
+            Self::ConstantFolding => constant_folding::fold_constants(program),
+            Self::UnreachableCode => unreachable_code::eliminate_unreachable_code(program),
+            Self::CopyPropagation => copy_propagation::propagate_copies(program),
+            Self::DeadStores => dead_store_elimination::eliminate_dead_stores(program),
+            Self::IfConversion => if_conversion::convert_to_selects(program),
+            Self::SsaRoundTrip => ssa::round_trip(program),
+        }
+    }
+}
+
+/// Passes over the assembly, after `fix_instructions` unless they run
+/// before the stack frame is laid out.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AsmPass {
+    /// Runs on the pseudos, without it every pseudo gets a stack slot.
+    RegisterAllocation,
+    Peephole,
+}
+
+impl AsmPass {
+    pub const ALL: [Self; 2] = [Self::RegisterAllocation, Self::Peephole];
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|pass| pass.name() == name)
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::RegisterAllocation => "regalloc",
+            Self::Peephole => "peephole",
+        }
+    }
+
+    /// The passes enabled at optimization level `-O{level}`.
+    pub fn for_level(level: u8) -> Vec<Self> {
+        match level {
+            0 => Vec::new(),
+            _ => vec![Self::RegisterAllocation, Self::Peephole],
+        }
+    }
+
+    pub fn before_frame(self) -> bool {
+        matches!(self, Self::RegisterAllocation)
+    }
+}
+
+impl<'a> Pass<asm_ast::Program<'a>> for AsmPass {
+    fn name(&self) -> &'static str {
+        AsmPass::name(*self)
+    }
+
+    fn run_once(&self) -> bool {
+        matches!(self, Self::RegisterAllocation)
+    }
+
+    fn run(&self, program: &mut asm_ast::Program<'a>) -> bool {
+        match self {
+            Self::RegisterAllocation => register_allocation::allocate_registers(program),
+            Self::Peephole => peephole::optimize(program),
+        }
+    }
+}
+
+/// Runs the enabled passes over a program until none of them changes it.
+#[derive(Debug, Default)]
+pub struct PassManager {
+    pub irc_passes: Vec<IrcPass>,
+    pub asm_passes: Vec<AsmPass>,
+    /// Print the program to stderr after every run of this pass.
+    pub print_after: Option<String>,
+    pub time_passes: bool,
+    timings: Vec<(&'static str, Duration)>,
+}
+
+impl PassManager {
+    pub fn new(irc_passes: Vec<IrcPass>, asm_passes: Vec<AsmPass>) -> Self {
+        Self {
+            irc_passes,
+            asm_passes,
+            ..Self::default()
+        }
+    }
+
+    pub fn run_irc(&mut self, program: &mut irc::Program) {
+        let passes = self.irc_passes.clone();
+        self.run_to_fixed_point(&passes, program);
+    }
+
+    /// Runs the asm passes that work on pseudos.
+    pub fn run_asm_before_frame(&mut self, program: &mut asm_ast::Program) {
+        let passes: Vec<_> = self
+            .asm_passes
+            .iter()
+            .copied()
+            .filter(|pass| pass.before_frame())
+            .collect();
+        self.run_to_fixed_point(&passes, program);
+    }
+
+    pub fn run_asm(&mut self, program: &mut asm_ast::Program) {
+        let passes: Vec<_> = self
+            .asm_passes
+            .iter()
+            .copied()
+            .filter(|pass| !pass.before_frame())
+            .collect();
+        self.run_to_fixed_point(&passes, program);
+    }
+
+    fn run_to_fixed_point<P: Display, T: Pass<P>>(&mut self, passes: &[T], program: &mut P) {
+        for iteration in 0.. {
+            let mut changed = false;
+            for pass in passes {
+                if iteration > 0 && pass.run_once() {
+                    continue;
+                }
+                let start = Instant::now();
+                changed |= pass.run(program);
+                if self.time_passes {
+                    self.record_time(pass.name(), start.elapsed());
+                }
+                if self.print_after.as_deref() == Some(pass.name()) {
+                    eprintln!("; after {}\n{program}", pass.name());
+                }
+            }
+            if !changed {
+                break;
+            }
+        }
+    }
+
+    fn record_time(&mut self, name: &'static str, elapsed: Duration) {
+        match self.timings.iter_mut().find(|(pass, _)| *pass == name) {
+            Some((_, total)) => *total += elapsed,
+            None => self.timings.push((name, elapsed)),
+        }
+    }
+
+    /// Total time spent in each pass, in the order the passes first ran.
+    pub fn timings(&self) -> &[(&'static str, Duration)] {
+        &self.timings
+    }
+}
@@ -0,0 +1,100 @@
+use crate::asm_ast::{BinaryOp, Function, Instruction, Operand, Program};
+
+/// Looks at the instructions starting at some position and, if they match,
+/// returns how many of them to replace and what to replace them with.
+type Rule = fn(&[Instruction]) -> Option<(usize, Vec<Instruction>)>;
+
+/// Tried in order at every position, the first one that matches is applied.
+const RULES: [Rule; 2] = [move_back, zero_before_setcc];
+
+/// Rewrites short sequences of instructions into cheaper ones, meant to run
+/// after `fix_instructions`. Returns `true` if the program changed.
+pub fn optimize(program: &mut Program) -> bool {
+    match program {
+        Program::Function(function) => optimize_function(function),
+    }
+}
+
+fn optimize_function(function: &mut Function) -> bool {
+    // Annotations don't generate code, so the rules shouldn't see them. Each
+    // instruction keeps the annotations before it, a replacement gets the
+    // annotations of everything it replaces.
+    let mut instructions = Vec::with_capacity(function.instructons.len());
+    let mut annotations = Vec::with_capacity(function.instructons.len());
+    let mut pending = Vec::new();
+    for ins in std::mem::take(&mut function.instructons) {
+        if ins.is_annotation() {
+            pending.push(ins);
+        } else {
+            annotations.push(std::mem::take(&mut pending));
+            instructions.push(ins);
+        }
+    }
+    let mut optimized = Vec::with_capacity(instructions.len());
+    let mut changed = false;
+    let mut index = 0;
+    while index < instructions.len() {
+        let window = &instructions[index..];
+        match RULES.iter().find_map(|rule| rule(window)) {
+            Some((length, replacement)) => {
+                optimized.extend(annotations[index..index + length].concat());
+                optimized.extend(replacement);
+                index += length;
+                changed = true;
+            }
+            None => {
+                optimized.extend(std::mem::take(&mut annotations[index]));
+                optimized.push(window[0].clone());
+                index += 1;
+            }
+        }
+    }
+    optimized.extend(pending);
+    function.instructons = optimized;
+    changed
+}
+
+/// `mov x, y; mov y, x`, the second move doesn't change anything. This is
+/// what removes the reloads in the `mov a, %r10; mov %r10, b; mov b, %r10`
+/// chains `fix_instructions` leaves between stack slots.
+fn move_back(window: &[Instruction]) -> Option<(usize, Vec<Instruction>)> {
+    match window {
+        [first @ Instruction::Mov { src, dst }, Instruction::Mov {
+            src: src2,
+            dst: dst2,
+        }, ..]
+            if src == dst2 && dst == src2 =>
+        {
+            Some((2, vec![first.clone()]))
+        }
+        _ => None,
+    }
+}
+
+/// `cmp a, b; mov $0, %reg; setcc %reg` becomes `xor %reg, %reg; cmp a, b; setcc %reg`.
+/// The `xor` clobbers the flags so it has to go before the comparison.
+fn zero_before_setcc(window: &[Instruction]) -> Option<(usize, Vec<Instruction>)> {
+    match window {
+        [compare @ (Instruction::Cmp { operand1, operand2 }
+        | Instruction::Test { operand1, operand2 }), Instruction::Mov {
+            src: Operand::Imm(0),
+            dst: dst @ Operand::Register(_),
+        }, set @ Instruction::SetCC { operand, .. }, ..]
+            if operand == dst && operand1 != dst && operand2 != dst =>
+        {
+            Some((
+                3,
+                vec![
+                    Instruction::Binary {
+                        operator: BinaryOp::Xor,
+                        operand1: dst.clone(),
+                        operand2: dst.clone(),
+                    },
+                    compare.clone(),
+                    set.clone(),
+                ],
+            ))
+        }
+        _ => None,
+    }
+}
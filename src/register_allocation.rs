@@ -0,0 +1,535 @@
+use std::{
+    cmp::{Ordering, Reverse},
+    collections::{BTreeSet, BinaryHeap, HashMap, HashSet},
+    iter,
+    sync::LazyLock,
+};
+
+use crate::{
+    asm_ast::{Function, Instruction, Operand, Program, Register},
+    cfg::Cfg,
+    code_emission,
+    dataflow::{self, Analysis, Direction},
+    target::Target,
+    x86_64::X86_64,
+};
+
+/// Registers pseudos can be assigned to, in the order the target lists them.
+/// It leaves out `R10` and `R11`, the scratch registers of `fix_instructions`.
+static ALLOCATABLE: LazyLock<Vec<Register>> =
+    LazyLock::new(|| registers(X86_64.registers().allocatable));
+
+/// Registers a function has to preserve for its caller.
+static CALLEE_SAVED: LazyLock<Vec<Register>> =
+    LazyLock::new(|| registers(X86_64.abi().callee_saved));
+
+fn registers(names: &[&str]) -> Vec<Register> {
+    names
+        .iter()
+        .map(|name| code_emission::register_from_name(name).expect("x86-64 names its registers"))
+        .collect()
+}
+
+/// The graph is rebuilt after every round of coalescing, later rounds rarely
+/// find much so they stop here.
+const COALESCE_ROUNDS: usize = 4;
+
+/// Assigns registers to pseudos with Chaitin-Briggs graph coloring, coalescing
+/// moves where it is safe to. Pseudos that don't get a register are left for
+/// `replace_pseudo` to put on the stack. Returns whether any pseudo got a
+/// register.
+pub fn allocate_registers(program: &mut Program) -> bool {
+    match program {
+        Program::Function(function) => allocate_function(function),
+    }
+}
+
+fn allocate_function(function: &mut Function) -> bool {
+    for _ in 0..COALESCE_ROUNDS {
+        let mut graph = InterferenceGraph::new(&function.instructons);
+        let coalesced = graph.coalesce(&function.instructons);
+        if coalesced.is_empty() {
+            break;
+        }
+        rewrite(&mut function.instructons, |name| {
+            let mut operand = Operand::Pseudo(name.to_string());
+            while let Some(replacement) = coalesced.get(&operand) {
+                operand = replacement.clone();
+            }
+            operand
+        });
+    }
+
+    let colors = InterferenceGraph::new(&function.instructons).color();
+    rewrite(&mut function.instructons, |name| {
+        colors.get(name).map_or_else(
+            || Operand::Pseudo(name.to_string()),
+            |&register| Operand::Register(register),
+        )
+    });
+    save_callee_saved(function);
+    !colors.is_empty()
+}
+
+fn rewrite(instructions: &mut Vec<Instruction>, replace: impl Fn(&str) -> Operand) {
+    for ins in instructions.iter_mut() {
+        for operand in ins.operands_mut() {
+            if let Operand::Pseudo(name) = operand {
+                *operand = replace(name);
+            }
+        }
+    }
+    instructions.retain(|ins| !matches!(ins, Instruction::Mov { src, dst } if src == dst));
+}
+
+fn save_callee_saved(function: &mut Function) {
+    let mut used: Vec<Register> = CALLEE_SAVED
+        .iter()
+        .copied()
+        .filter(|register| {
+            function.instructons.iter_mut().any(|ins| {
+                ins.operands_mut()
+                    .into_iter()
+                    .any(|operand| *operand == Operand::Register(*register))
+            })
+        })
+        .collect();
+    if used.is_empty() {
+        return;
+    }
+    let mut instructions: Vec<Instruction> = used.iter().copied().map(Instruction::Push).collect();
+    used.reverse();
+    for ins in std::mem::take(&mut function.instructons) {
+        if ins == Instruction::Return {
+            instructions.extend(used.iter().copied().map(Instruction::Pop));
+        }
+        instructions.push(ins);
+    }
+    function.instructons = instructions;
+}
+
+/// The registers and pseudos read and written by an instruction, including
+/// the ones it uses implicitly.
+fn uses_and_defs(instruction: &Instruction) -> (Vec<Operand>, Vec<Operand>) {
+    let register = Operand::Register;
+    let (uses, defs) = match instruction {
+        Instruction::Mov { src, dst } => (vec![src.clone()], vec![dst.clone()]),
+        // `dst` keeps its value when the condition doesn't hold.
+        Instruction::Cmov { src, dst, .. } => (vec![src.clone(), dst.clone()], vec![dst.clone()]),
+        Instruction::Binary {
+            operand1, operand2, ..
+        } => (
+            vec![operand1.clone(), operand2.clone()],
+            vec![operand2.clone()],
+        ),
+        Instruction::Unary { operand, .. } | Instruction::SetCC { operand, .. } => {
+            (vec![operand.clone()], vec![operand.clone()])
+        }
+        Instruction::Cmp { operand1, operand2 } | Instruction::Test { operand1, operand2 } => {
+            (vec![operand1.clone(), operand2.clone()], Vec::new())
+        }
+        Instruction::Idiv(operand) => (
+            vec![
+                operand.clone(),
+                register(Register::AX),
+                register(Register::DX),
+            ],
+            vec![register(Register::AX), register(Register::DX)],
+        ),
+        Instruction::Imul(operand) => (
+            vec![operand.clone(), register(Register::AX)],
+            vec![register(Register::AX), register(Register::DX)],
+        ),
+        Instruction::Lea {
+            base, index, dst, ..
+        } => (
+            iter::once(base.clone()).chain(index.clone()).collect(),
+            vec![dst.clone()],
+        ),
+        Instruction::Cdq => (vec![register(Register::AX)], vec![register(Register::DX)]),
+        Instruction::Return => (vec![register(Register::AX)], Vec::new()),
+        Instruction::Push(pushed) => (vec![register(*pushed)], Vec::new()),
+        Instruction::Pop(popped) => (Vec::new(), vec![register(*popped)]),
+        Instruction::Jmp(_)
+        | Instruction::JumpCC { .. }
+        | Instruction::Label(_)
+        | Instruction::AllocateStack(_)
+        | Instruction::Line(_)
+        | Instruction::Comment(_)
+        | Instruction::Variable { .. } => (Vec::new(), Vec::new()),
+    };
+    (
+        uses.into_iter().filter(is_node).collect(),
+        defs.into_iter().filter(is_node).collect(),
+    )
+}
+
+fn is_node(operand: &Operand) -> bool {
+    match operand {
+        Operand::Pseudo(_) => true,
+        Operand::Register(register) => ALLOCATABLE.contains(register),
+        Operand::Imm(_) | Operand::Stack(_) => false,
+    }
+}
+
+struct Liveness;
+
+impl Analysis<Instruction> for Liveness {
+    type Fact = HashSet<Operand>;
+
+    const DIRECTION: Direction = Direction::Backward;
+
+    fn boundary(&self) -> Self::Fact {
+        HashSet::new()
+    }
+
+    fn initial(&self) -> Self::Fact {
+        HashSet::new()
+    }
+
+    fn meet(&self, fact1: &Self::Fact, fact2: &Self::Fact) -> Self::Fact {
+        fact1.union(fact2).cloned().collect()
+    }
+
+    fn transfer(&self, instruction: &Instruction, fact: &Self::Fact) -> Self::Fact {
+        let (uses, defs) = uses_and_defs(instruction);
+        let mut fact = fact.clone();
+        for def in &defs {
+            fact.remove(def);
+        }
+        fact.extend(uses);
+        fact
+    }
+}
+
+#[derive(Debug, Default)]
+struct Node {
+    neighbors: HashSet<Operand>,
+    spill_cost: usize,
+}
+
+#[derive(Debug)]
+struct InterferenceGraph {
+    nodes: HashMap<Operand, Node>,
+    /// Pseudos in order of first appearance, for deterministic allocation.
+    /// Pseudos merged away by coalescing stay in the list but aren't nodes.
+    pseudos: Vec<Operand>,
+    /// Pseudos of the variables the debug info describes. They stay in a
+    /// stack slot of their own, the one location the debug info gives them
+    /// for the whole function.
+    described: HashSet<Operand>,
+}
+
+/// A node that can be spilled, cheaper ones first. The cost is weighed
+/// against the degree, removing a node of high degree helps its neighbors
+/// the most.
+#[derive(Debug, PartialEq, Eq)]
+struct SpillCandidate<'a> {
+    spill_cost: usize,
+    degree: usize,
+    /// Position in `InterferenceGraph::pseudos`, to break ties.
+    order: usize,
+    operand: &'a Operand,
+}
+
+impl Ord for SpillCandidate<'_> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.spill_cost * other.degree)
+            .cmp(&(other.spill_cost * self.degree))
+            .then(self.order.cmp(&other.order))
+    }
+}
+
+impl PartialOrd for SpillCandidate<'_> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl InterferenceGraph {
+    fn new(instructions: &[Instruction]) -> Self {
+        let mut graph = Self {
+            nodes: HashMap::new(),
+            pseudos: Vec::new(),
+            described: instructions
+                .iter()
+                .filter_map(|ins| match ins {
+                    Instruction::Variable {
+                        location: location @ Operand::Pseudo(_),
+                        ..
+                    } => Some(location.clone()),
+                    _ => None,
+                })
+                .collect(),
+        };
+        for (index, &register) in ALLOCATABLE.iter().enumerate() {
+            for &other in &ALLOCATABLE[index + 1..] {
+                graph.add_edge(&Operand::Register(register), &Operand::Register(other));
+            }
+        }
+        for ins in instructions {
+            let (uses, defs) = uses_and_defs(ins);
+            for operand in uses.into_iter().chain(defs) {
+                if let Operand::Pseudo(_) = operand {
+                    if !graph.nodes.contains_key(&operand) {
+                        graph.pseudos.push(operand.clone());
+                    }
+                    graph.nodes.entry(operand).or_default().spill_cost += 1;
+                }
+            }
+        }
+
+        let cfg = Cfg::new(instructions.to_vec());
+        let solution = dataflow::solve(&cfg, &Liveness);
+        for index in 0..cfg.blocks.len() {
+            let live_after = solution.instruction_facts(&Liveness, &cfg, index);
+            for (ins, live) in cfg.blocks[index].instructions.iter().zip(live_after) {
+                let (_, defs) = uses_and_defs(ins);
+                for def in &defs {
+                    for operand in &live {
+                        let is_move_source =
+                            matches!(ins, Instruction::Mov { src, .. } if src == operand);
+                        if operand != def && !is_move_source {
+                            graph.add_edge(def, operand);
+                        }
+                    }
+                }
+            }
+        }
+        graph
+    }
+
+    fn add_edge(&mut self, operand1: &Operand, operand2: &Operand) {
+        self.nodes
+            .entry(operand1.clone())
+            .or_default()
+            .neighbors
+            .insert(operand2.clone());
+        self.nodes
+            .entry(operand2.clone())
+            .or_default()
+            .neighbors
+            .insert(operand1.clone());
+    }
+
+    fn degree(&self, operand: &Operand) -> usize {
+        self.nodes[operand].neighbors.len()
+    }
+
+    fn is_significant(&self, operand: &Operand) -> bool {
+        matches!(operand, Operand::Register(_)) || self.degree(operand) >= ALLOCATABLE.len()
+    }
+
+    /// Merges the operands of moves that don't interfere, as long as it can't
+    /// make the graph uncolorable. Returns what each merged pseudo became.
+    fn coalesce(&mut self, instructions: &[Instruction]) -> HashMap<Operand, Operand> {
+        let mut merged: HashMap<Operand, Operand> = HashMap::new();
+        let find = |merged: &HashMap<Operand, Operand>, operand: &Operand| {
+            let mut operand = operand.clone();
+            while let Some(replacement) = merged.get(&operand) {
+                operand = replacement.clone();
+            }
+            operand
+        };
+        for ins in instructions {
+            let Instruction::Mov { src, dst } = ins else {
+                continue;
+            };
+            if !is_node(src) || !is_node(dst) {
+                continue;
+            }
+            let (src, dst) = (find(&merged, src), find(&merged, dst));
+            if self.described.contains(&src) || self.described.contains(&dst) {
+                continue;
+            }
+            // The node with fewer neighbors is merged into the other one, so
+            // merging and testing only walk the smaller neighborhood.
+            let (keep, remove) = match (&src, &dst) {
+                (Operand::Register(_), Operand::Register(_)) => continue,
+                (_, Operand::Register(_)) => (dst, src),
+                (Operand::Register(_), _) => (src, dst),
+                _ if self.degree(&dst) > self.degree(&src) => (dst, src),
+                _ => (src, dst),
+            };
+            if keep == remove || self.nodes[&keep].neighbors.contains(&remove) {
+                continue;
+            }
+            // Briggs' test walks both neighborhoods, George's only the removed
+            // node's, which is much cheaper next to a long lived pseudo.
+            let is_safe = if self.is_significant(&keep) {
+                self.george_test(&keep, &remove)
+            } else {
+                self.briggs_test(&keep, &remove)
+            };
+            if is_safe {
+                self.merge(&keep, &remove);
+                merged.insert(remove, keep);
+            }
+        }
+        merged
+    }
+
+    /// Merging is safe if the merged node has fewer than K significant neighbors.
+    fn briggs_test(&self, operand1: &Operand, operand2: &Operand) -> bool {
+        let neighbors1 = &self.nodes[operand1].neighbors;
+        let neighbors2 = &self.nodes[operand2].neighbors;
+        let significant = neighbors1
+            .union(neighbors2)
+            .filter(|neighbor| {
+                let shared = neighbors1.contains(*neighbor) && neighbors2.contains(*neighbor);
+                matches!(neighbor, Operand::Register(_))
+                    || self.degree(neighbor) - usize::from(shared) >= ALLOCATABLE.len()
+            })
+            .count();
+        significant < ALLOCATABLE.len()
+    }
+
+    /// Merging `remove` into `keep` is safe if each of its neighbors already
+    /// interferes with `keep` or is insignificant.
+    fn george_test(&self, keep: &Operand, remove: &Operand) -> bool {
+        self.nodes[remove].neighbors.iter().all(|neighbor| {
+            self.nodes[keep].neighbors.contains(neighbor) || !self.is_significant(neighbor)
+        })
+    }
+
+    fn merge(&mut self, keep: &Operand, remove: &Operand) {
+        let node = self.nodes.remove(remove).expect("merged nodes exist");
+        for neighbor in &node.neighbors {
+            if let Some(neighbor) = self.nodes.get_mut(neighbor) {
+                neighbor.neighbors.remove(remove);
+            }
+            self.add_edge(keep, neighbor);
+        }
+        self.nodes.entry(keep.clone()).or_default().spill_cost += node.spill_cost;
+    }
+
+    /// Colors the pseudos, simplifying nodes with fewer than K neighbors first
+    /// and optimistically pushing the cheapest spill candidate when stuck.
+    /// Degrees are kept up to date as nodes are removed, spill candidates
+    /// whose degree went down are put back with their new cost when they
+    /// come up.
+    fn color(&self) -> HashMap<String, Register> {
+        let pseudos: Vec<&Operand> = self
+            .pseudos
+            .iter()
+            .filter(|pseudo| self.nodes.contains_key(*pseudo) && !self.described.contains(*pseudo))
+            .collect();
+        let mut degrees: HashMap<&Operand, usize> = pseudos
+            .iter()
+            .map(|&pseudo| (pseudo, self.degree(pseudo)))
+            .collect();
+        let order: HashMap<&Operand, usize> = pseudos
+            .iter()
+            .enumerate()
+            .map(|(order, &pseudo)| (pseudo, order))
+            .collect();
+        // By position, so the first pseudo that can be simplified goes first.
+        let mut simplify: BTreeSet<usize> = BTreeSet::new();
+        let mut spill = BinaryHeap::new();
+        for (order, &pseudo) in pseudos.iter().enumerate() {
+            if degrees[pseudo] < ALLOCATABLE.len() {
+                simplify.insert(order);
+            } else {
+                spill.push(Reverse(SpillCandidate {
+                    spill_cost: self.nodes[pseudo].spill_cost,
+                    degree: degrees[pseudo],
+                    order,
+                    operand: pseudo,
+                }));
+            }
+        }
+
+        let mut removed: HashSet<&Operand> = HashSet::new();
+        let mut stack = Vec::with_capacity(pseudos.len());
+        while stack.len() < pseudos.len() {
+            let operand = match simplify.pop_first() {
+                Some(index) => pseudos[index],
+                None => loop {
+                    let Reverse(candidate) = spill.pop().expect("uncolored nodes are candidates");
+                    if removed.contains(candidate.operand) {
+                        continue;
+                    }
+                    let degree = degrees[candidate.operand];
+                    if degree != candidate.degree {
+                        spill.push(Reverse(SpillCandidate {
+                            degree,
+                            ..candidate
+                        }));
+                        continue;
+                    }
+                    break candidate.operand;
+                },
+            };
+            removed.insert(operand);
+            stack.push(operand);
+            for neighbor in &self.nodes[operand].neighbors {
+                if removed.contains(neighbor) {
+                    continue;
+                }
+                if let Some(degree) = degrees.get_mut(neighbor) {
+                    *degree -= 1;
+                    if *degree == ALLOCATABLE.len() - 1 {
+                        simplify.insert(order[neighbor]);
+                    }
+                }
+            }
+        }
+
+        let mut colors: HashMap<String, Register> = HashMap::new();
+        while let Some(operand) = stack.pop() {
+            let Operand::Pseudo(name) = operand else {
+                unreachable!("only pseudos are colored");
+            };
+            let taken: HashSet<Register> = self.nodes[operand]
+                .neighbors
+                .iter()
+                .filter_map(|neighbor| match neighbor {
+                    Operand::Register(register) => Some(*register),
+                    Operand::Pseudo(neighbor) => colors.get(neighbor).copied(),
+                    Operand::Imm(_) | Operand::Stack(_) => None,
+                })
+                .collect();
+            if let Some(&register) = ALLOCATABLE.iter().find(|reg| !taken.contains(reg)) {
+                colors.insert(name.clone(), register);
+            }
+        }
+        colors
+    }
+}
+
+/// Each pseudo, in order of first appearance, with the pseudos it interferes
+/// with. Pseudos that don't interfere can share a stack slot, the ones the
+/// debug info describes interfere with all the others.
+pub fn pseudo_interference(instructions: &[Instruction]) -> Vec<(String, HashSet<String>)> {
+    let graph = InterferenceGraph::new(instructions);
+    graph
+        .pseudos
+        .iter()
+        .map(|pseudo| {
+            let Operand::Pseudo(name) = pseudo else {
+                unreachable!("only pseudos are in the list");
+            };
+            let neighbors = if graph.described.contains(pseudo) {
+                graph
+                    .pseudos
+                    .iter()
+                    .filter(|&other| other != pseudo)
+                    .collect()
+            } else {
+                graph.nodes[pseudo]
+                    .neighbors
+                    .iter()
+                    .filter(|neighbor| matches!(neighbor, Operand::Pseudo(_)))
+                    .chain(&graph.described)
+                    .collect::<Vec<_>>()
+            }
+            .into_iter()
+            .filter_map(|neighbor| match neighbor {
+                Operand::Pseudo(neighbor) => Some(neighbor.clone()),
+                Operand::Register(_) | Operand::Imm(_) | Operand::Stack(_) => None,
+            })
+            .collect();
+            (name.clone(), neighbors)
+        })
+        .collect()
+}
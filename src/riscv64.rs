@@ -0,0 +1,772 @@
+use std::{
+    collections::HashMap,
+    io::{self, Write},
+};
+
+use crate::{
+    code_emission, constant_folding, irc,
+    pass_manager::PassManager,
+    target::{Abi, Assembly, DataLayout, RegisterInfo, Target},
+};
+
+#[derive(Debug, Clone)]
+pub enum Program<'a> {
+    Function(Function<'a>),
+}
+
+#[derive(Debug, Clone)]
+pub struct Function<'a> {
+    pub name: &'a str,
+    pub instructons: Vec<Instruction>,
+    /// Bytes of stack below the saved `ra` and `s0` for the variables, a
+    /// multiple of 16.
+    pub frame_size: usize,
+}
+
+/// An integer register, `x0` to `x31`.
+pub type Register = u8;
+
+const ZERO: Register = 0;
+const A0: Register = 10;
+/// Registers used while lowering, all of them temporaries in the LP64 ABI.
+const SRC1: Register = 5;
+const SRC2: Register = 6;
+const DST: Register = 7;
+/// Holds the address of stack slots out of reach of a 12 bit offset.
+const ADDRESS: Register = 28;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BinaryOp {
+    Addw,
+    Subw,
+    Mulw,
+    Divw,
+    Remw,
+    Sllw,
+    Sraw,
+    And,
+    Or,
+    Xor,
+    Slt,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UnaryOp {
+    Negw,
+    Not,
+    Seqz,
+    Snez,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CondCode {
+    Eq,
+    Ne,
+    Lt,
+    Ge,
+    Le,
+    Gt,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Instruction {
+    Li {
+        dst: Register,
+        value: i32,
+    },
+    /// Loads the variable `slot` bytes above the stack pointer.
+    Lw {
+        dst: Register,
+        slot: usize,
+    },
+    Sw {
+        src: Register,
+        slot: usize,
+    },
+    Unary {
+        operator: UnaryOp,
+        src: Register,
+        dst: Register,
+    },
+    Binary {
+        operator: BinaryOp,
+        src1: Register,
+        src2: Register,
+        dst: Register,
+    },
+    /// The immediate form of `operator`, with a 12 bit signed immediate.
+    BinaryImm {
+        operator: BinaryOp,
+        src: Register,
+        value: i32,
+        dst: Register,
+    },
+    Branch {
+        cond_code: CondCode,
+        src1: Register,
+        src2: Register,
+        target: String,
+    },
+    J(String),
+    Label(String),
+    /// Returns the value in `a0`, tearing down the frame.
+    Ret,
+}
+
+impl CondCode {
+    fn negate(self) -> Self {
+        match self {
+            Self::Eq => Self::Ne,
+            Self::Ne => Self::Eq,
+            Self::Lt => Self::Ge,
+            Self::Ge => Self::Lt,
+            Self::Le => Self::Gt,
+            Self::Gt => Self::Le,
+        }
+    }
+
+    fn from_operator(operator: irc::BinaryOp) -> Option<Self> {
+        match operator {
+            irc::BinaryOp::Equal => Some(Self::Eq),
+            irc::BinaryOp::NotEqual => Some(Self::Ne),
+            irc::BinaryOp::LessThan => Some(Self::Lt),
+            irc::BinaryOp::LessOrEqual => Some(Self::Le),
+            irc::BinaryOp::GreaterThan => Some(Self::Gt),
+            irc::BinaryOp::GreaterOrEqual => Some(Self::Ge),
+            _ => None,
+        }
+    }
+}
+
+fn is_imm12(value: i32) -> bool {
+    (-2048..2048).contains(&value)
+}
+
+/// RV64IM Linux with the LP64 ABI.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Riscv64;
+
+impl Target for Riscv64 {
+    fn name(&self) -> &'static str {
+        "riscv64-linux-gnu"
+    }
+
+    fn architecture(&self) -> &'static str {
+        "riscv64"
+    }
+
+    fn data_layout(&self) -> DataLayout {
+        DataLayout {
+            int_size: 4,
+            pointer_size: 8,
+            stack_alignment: 16,
+        }
+    }
+
+    fn abi(&self) -> Abi {
+        Abi {
+            return_register: Some("a0"),
+            argument_registers: &["a0", "a1", "a2", "a3", "a4", "a5", "a6", "a7"],
+            callee_saved: &[
+                "s0", "s1", "s2", "s3", "s4", "s5", "s6", "s7", "s8", "s9", "s10", "s11",
+            ],
+            red_zone: 0,
+        }
+    }
+
+    fn registers(&self) -> RegisterInfo {
+        RegisterInfo {
+            allocatable: &[],
+            scratch: &["t0", "t1", "t2", "t3"],
+            stack_pointer: Some("sp"),
+            frame_pointer: Some("s0"),
+        }
+    }
+
+    fn gcc(&self) -> Option<&'static str> {
+        Some("riscv64-linux-gnu-gcc")
+    }
+
+    fn select_instructions<'a>(
+        &self,
+        program: irc::Program<'a>,
+        _pass_manager: &mut PassManager,
+        _verbose: bool,
+    ) -> Box<dyn Assembly + 'a> {
+        Box::new(gen_program(program))
+    }
+}
+
+impl Assembly for Program<'_> {
+    fn emit(&self, out: &mut dyn Write, _options: code_emission::Options) -> io::Result<()> {
+        emit_program(out, self)
+    }
+}
+
+pub fn gen_program(program: irc::Program) -> Program {
+    match program {
+        irc::Program::Function(function) => Program::Function(gen_function(function)),
+    }
+}
+
+/// Every variable lives in a stack slot, instructions load their operands
+/// into scratch registers and store the result back. Values are kept sign
+/// extended to 64 bits, which the `w` instructions and `lw` do.
+fn gen_function(function: irc::Function) -> Function {
+    let mut uses: HashMap<&str, usize> = HashMap::new();
+    for ins in &function.instructons {
+        for src in ins.sources() {
+            if let irc::Value::Var(name) = src {
+                *uses.entry(name.as_str()).or_default() += 1;
+            }
+        }
+    }
+    let layout = Riscv64.data_layout();
+    let mut generator = Generator::default();
+    let mut index = 0;
+    while let Some(ins) = function.instructons.get(index) {
+        let fused = function
+            .instructons
+            .get(index + 1)
+            .is_some_and(|user| generator.gen_fused_branch(ins, user, &uses));
+        if !fused {
+            generator.gen_instruction(ins);
+        }
+        index += if fused { 2 } else { 1 };
+    }
+    Function {
+        name: function.name,
+        frame_size: (generator.slots.len() * layout.int_size)
+            .next_multiple_of(layout.stack_alignment),
+        instructons: generator.instructions,
+    }
+}
+
+#[derive(Default)]
+struct Generator {
+    instructions: Vec<Instruction>,
+    slots: HashMap<String, usize>,
+    /// Labels made up for selects.
+    labels: usize,
+}
+
+impl Generator {
+    fn slot(&mut self, name: &str) -> usize {
+        let next = self.slots.len() * Riscv64.data_layout().int_size;
+        *self.slots.entry(name.to_string()).or_insert(next)
+    }
+
+    fn load(&mut self, value: &irc::Value, dst: Register) {
+        let ins = match value {
+            irc::Value::Constant(value) => Instruction::Li { dst, value: *value },
+            irc::Value::Var(name) => Instruction::Lw {
+                dst,
+                slot: self.slot(name),
+            },
+        };
+        self.instructions.push(ins);
+    }
+
+    fn store(&mut self, dst: &str) {
+        let slot = self.slot(dst);
+        self.instructions.push(Instruction::Sw { src: DST, slot });
+    }
+
+    fn binary(&mut self, operator: BinaryOp, src1: Register, src2: Register, dst: Register) {
+        self.instructions.push(Instruction::Binary {
+            operator,
+            src1,
+            src2,
+            dst,
+        });
+    }
+
+    fn unary(&mut self, operator: UnaryOp, src: Register, dst: Register) {
+        self.instructions
+            .push(Instruction::Unary { operator, src, dst });
+    }
+
+    /// `slt`, `seqz` and `snez` sequences computing `src1 cond_code src2`
+    /// into `DST`.
+    fn compare(&mut self, cond_code: CondCode, src1: &irc::Value, src2: &irc::Value) {
+        let (src1, src2) = match cond_code {
+            CondCode::Gt | CondCode::Le => (src2, src1),
+            _ => (src1, src2),
+        };
+        self.load(src1, SRC1);
+        match (cond_code, src2) {
+            (CondCode::Lt | CondCode::Ge, irc::Value::Constant(value)) if is_imm12(*value) => {
+                self.instructions.push(Instruction::BinaryImm {
+                    operator: BinaryOp::Slt,
+                    src: SRC1,
+                    value: *value,
+                    dst: DST,
+                });
+            }
+            (CondCode::Eq | CondCode::Ne, irc::Value::Constant(value)) if is_imm12(*value) => {
+                self.instructions.push(Instruction::BinaryImm {
+                    operator: BinaryOp::Xor,
+                    src: SRC1,
+                    value: *value,
+                    dst: DST,
+                });
+            }
+            (CondCode::Eq | CondCode::Ne, _) => {
+                self.load(src2, SRC2);
+                self.binary(BinaryOp::Xor, SRC1, SRC2, DST);
+            }
+            _ => {
+                self.load(src2, SRC2);
+                self.binary(BinaryOp::Slt, SRC1, SRC2, DST);
+            }
+        }
+        match cond_code {
+            CondCode::Eq => self.unary(UnaryOp::Seqz, DST, DST),
+            CondCode::Ne => self.unary(UnaryOp::Snez, DST, DST),
+            CondCode::Ge | CondCode::Le => self.instructions.push(Instruction::BinaryImm {
+                operator: BinaryOp::Xor,
+                src: DST,
+                value: 1,
+                dst: DST,
+            }),
+            CondCode::Lt | CondCode::Gt => {}
+        }
+    }
+
+    /// A single branch for a comparison only used by the jump after it.
+    fn gen_fused_branch(
+        &mut self,
+        instruction: &irc::Instruction,
+        user: &irc::Instruction,
+        uses: &HashMap<&str, usize>,
+    ) -> bool {
+        let irc::Instruction::Binary {
+            operator,
+            src1,
+            src2,
+            dst,
+        } = instruction
+        else {
+            return false;
+        };
+        let Some(cond_code) = CondCode::from_operator(*operator) else {
+            return false;
+        };
+        let (cond_code, condition, target) = match user {
+            irc::Instruction::JumpIfZero { condition, target } => {
+                (cond_code.negate(), condition, target)
+            }
+            irc::Instruction::JumpIfNotZero { condition, target } => (cond_code, condition, target),
+            _ => return false,
+        };
+        if *condition != irc::Value::Var(dst.clone()) || uses.get(dst.as_str()) != Some(&1) {
+            return false;
+        }
+        self.load(src1, SRC1);
+        let src2 = match src2 {
+            irc::Value::Constant(0) => ZERO,
+            src2 => {
+                self.load(src2, SRC2);
+                SRC2
+            }
+        };
+        self.instructions.push(Instruction::Branch {
+            cond_code,
+            src1: SRC1,
+            src2,
+            target: target.clone(),
+        });
+        true
+    }
+
+    fn gen_instruction(&mut self, instruction: &irc::Instruction) {
+        match instruction {
+            irc::Instruction::Ret(value) => {
+                self.load(value, A0);
+                self.instructions.push(Instruction::Ret);
+            }
+            irc::Instruction::Unary { operator, src, dst } => {
+                self.load(src, SRC1);
+                let operator = match operator {
+                    irc::UnaryOp::Negate => UnaryOp::Negw,
+                    irc::UnaryOp::Complement => UnaryOp::Not,
+                    irc::UnaryOp::Not => UnaryOp::Seqz,
+                };
+                self.unary(operator, SRC1, DST);
+                self.store(dst);
+            }
+            irc::Instruction::Binary {
+                operator,
+                src1,
+                src2,
+                dst,
+            } => {
+                self.gen_binary(*operator, src1, src2);
+                self.store(dst);
+            }
+            irc::Instruction::Copy { src, dst } => {
+                self.load(src, DST);
+                self.store(dst);
+            }
+            irc::Instruction::Select {
+                condition,
+                src1,
+                src2,
+                dst,
+            } => {
+                // There is no conditional move in RV64IM.
+                let skip = format!(".select.{}", self.labels);
+                self.labels += 1;
+                self.load(condition, SRC1);
+                self.load(src1, DST);
+                self.instructions.push(Instruction::Branch {
+                    cond_code: CondCode::Ne,
+                    src1: SRC1,
+                    src2: ZERO,
+                    target: skip.clone(),
+                });
+                self.load(src2, DST);
+                self.instructions.push(Instruction::Label(skip));
+                self.store(dst);
+            }
+            irc::Instruction::Jump { target } => {
+                self.instructions.push(Instruction::J(target.clone()));
+            }
+            irc::Instruction::JumpIfZero { condition, target }
+            | irc::Instruction::JumpIfNotZero { condition, target } => {
+                self.load(condition, SRC1);
+                let cond_code = match instruction {
+                    irc::Instruction::JumpIfZero { .. } => CondCode::Eq,
+                    _ => CondCode::Ne,
+                };
+                self.instructions.push(Instruction::Branch {
+                    cond_code,
+                    src1: SRC1,
+                    src2: ZERO,
+                    target: target.clone(),
+                });
+            }
+            irc::Instruction::Label(label) => {
+                self.instructions.push(Instruction::Label(label.clone()));
+            }
+            irc::Instruction::Line(_) | irc::Instruction::Variable { .. } => {}
+        }
+    }
+
+    /// Computes `src1 operator src2` into `DST`.
+    fn gen_binary(&mut self, operator: irc::BinaryOp, src1: &irc::Value, src2: &irc::Value) {
+        if let (irc::Value::Constant(src1), irc::Value::Constant(src2)) = (src1, src2) {
+            if let Some(value) = constant_folding::fold_binary(operator, *src1, *src2) {
+                self.instructions.push(Instruction::Li { dst: DST, value });
+                return;
+            }
+        }
+        if let Some(cond_code) = CondCode::from_operator(operator) {
+            self.compare(cond_code, src1, src2);
+            return;
+        }
+        let operator = match operator {
+            irc::BinaryOp::Add => BinaryOp::Addw,
+            irc::BinaryOp::Subtract => BinaryOp::Subw,
+            irc::BinaryOp::Multiply => BinaryOp::Mulw,
+            irc::BinaryOp::Divide => BinaryOp::Divw,
+            irc::BinaryOp::Remainder => BinaryOp::Remw,
+            irc::BinaryOp::LeftShift => BinaryOp::Sllw,
+            irc::BinaryOp::RightShift => BinaryOp::Sraw,
+            irc::BinaryOp::BitAnd => BinaryOp::And,
+            irc::BinaryOp::BitOr => BinaryOp::Or,
+            irc::BinaryOp::Xor => BinaryOp::Xor,
+            _ => unreachable!("{operator:?} is a comparison"),
+        };
+        self.load(src1, SRC1);
+        let immediate = match (operator, src2) {
+            (BinaryOp::Subw, irc::Value::Constant(value)) if is_imm12(value.wrapping_neg()) => {
+                Some((BinaryOp::Addw, -value))
+            }
+            (BinaryOp::Sllw | BinaryOp::Sraw, irc::Value::Constant(value))
+                if (0..32).contains(value) =>
+            {
+                Some((operator, *value))
+            }
+            (
+                BinaryOp::Addw | BinaryOp::And | BinaryOp::Or | BinaryOp::Xor,
+                irc::Value::Constant(value),
+            ) if is_imm12(*value) => Some((operator, *value)),
+            _ => None,
+        };
+        match immediate {
+            Some((operator, value)) => self.instructions.push(Instruction::BinaryImm {
+                operator,
+                src: SRC1,
+                value,
+                dst: DST,
+            }),
+            None => {
+                self.load(src2, SRC2);
+                self.binary(operator, SRC1, SRC2, DST);
+            }
+        }
+    }
+}
+
+/// How far a branch reaches, relative to the start of its instruction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Reach {
+    /// A conditional branch reaches 4 KiB, `j` 1 MiB.
+    Short,
+    /// The inverted branch skipping a `j`.
+    Near,
+    /// Through `auipc`, anywhere.
+    Far,
+}
+
+impl Reach {
+    fn fits(distance: i64, bits: u32) -> bool {
+        (-(1 << (bits - 1))..1 << (bits - 1)).contains(&distance)
+    }
+}
+
+/// Upper bound of the bytes the instruction takes, the assembler may pick
+/// shorter forms.
+fn size(instruction: &Instruction, reach: Reach) -> i64 {
+    match (instruction, reach) {
+        (Instruction::Label(_), _) => 0,
+        (Instruction::Li { .. }, _) => 8,
+        (Instruction::Lw { slot, .. } | Instruction::Sw { slot, .. }, _) if *slot >= 2048 => 16,
+        (Instruction::Branch { .. }, Reach::Near) | (Instruction::J(_), Reach::Far) => 8,
+        (Instruction::Branch { .. }, Reach::Far) => 12,
+        (Instruction::Ret, _) => 20,
+        _ => 4,
+    }
+}
+
+/// Picks the shortest form of each branch that reaches its target. Branches
+/// start short and only ever grow, so this settles.
+fn relax(function: &Function) -> Vec<Reach> {
+    let instructions = &function.instructons;
+    let mut reach = vec![Reach::Short; instructions.len()];
+    loop {
+        let mut offsets = Vec::with_capacity(instructions.len());
+        let mut labels = HashMap::new();
+        let mut offset = 0;
+        for (ins, &reach) in instructions.iter().zip(&reach) {
+            offsets.push(offset);
+            if let Instruction::Label(label) = ins {
+                labels.insert(label.as_str(), offset);
+            }
+            offset += size(ins, reach);
+        }
+        let mut changed = false;
+        for (index, ins) in instructions.iter().enumerate() {
+            let (target, bits) = match ins {
+                Instruction::Branch { target, .. } => (target, 13),
+                Instruction::J(target) => (target, 21),
+                _ => continue,
+            };
+            let distance = labels[target.as_str()] - offsets[index];
+            let needed = if Reach::fits(distance, bits) {
+                Reach::Short
+            } else if matches!(ins, Instruction::Branch { .. }) && Reach::fits(distance - 4, 21) {
+                Reach::Near
+            } else {
+                Reach::Far
+            };
+            if needed > reach[index] {
+                reach[index] = needed;
+                changed = true;
+            }
+        }
+        if !changed {
+            return reach;
+        }
+    }
+}
+
+/// Writes the program as GNU assembler source.
+pub fn emit_program(mut out: impl Write, program: &Program) -> io::Result<()> {
+    match program {
+        Program::Function(function) => emit_function(&mut out, function)?,
+    }
+    writeln!(out, "\n.section .note.GNU-stack,\"\",@progbits")?;
+    out.flush()
+}
+
+fn emit_function(out: &mut impl Write, function: &Function) -> io::Result<()> {
+    let name = function.name;
+    writeln!(out, "    .text")?;
+    writeln!(out, "    .globl {name}")?;
+    writeln!(out, "    .type {name}, @function")?;
+    writeln!(out, "    .p2align 2")?;
+    writeln!(out, "{name}:")?;
+    writeln!(out, "    addi sp, sp, -16")?;
+    writeln!(out, "    sd ra, 8(sp)")?;
+    writeln!(out, "    sd s0, 0(sp)")?;
+    writeln!(out, "    addi s0, sp, 16")?;
+    match function.frame_size {
+        0 => {}
+        bytes @ ..=2048 => writeln!(out, "    addi sp, sp, -{bytes}")?,
+        bytes => {
+            writeln!(out, "    li {}, {bytes}", register(ADDRESS))?;
+            writeln!(out, "    sub sp, sp, {}", register(ADDRESS))?;
+        }
+    }
+    let reach = relax(function);
+    for (index, (ins, reach)) in function.instructons.iter().zip(reach).enumerate() {
+        emit_instruction(out, ins, reach, index)?;
+    }
+    writeln!(out, "    .size {name}, .-{name}")
+}
+
+fn emit_instruction(
+    out: &mut impl Write,
+    instruction: &Instruction,
+    reach: Reach,
+    index: usize,
+) -> io::Result<()> {
+    match instruction {
+        Instruction::Li { dst, value } => writeln!(out, "    li {}, {value}", register(*dst)),
+        Instruction::Lw { dst, slot } => emit_memory(out, "lw", *dst, *slot),
+        Instruction::Sw { src, slot } => emit_memory(out, "sw", *src, *slot),
+        Instruction::Unary { operator, src, dst } => {
+            let mnemonic = match operator {
+                UnaryOp::Negw => "negw",
+                UnaryOp::Not => "not",
+                UnaryOp::Seqz => "seqz",
+                UnaryOp::Snez => "snez",
+            };
+            writeln!(out, "    {mnemonic} {}, {}", register(*dst), register(*src))
+        }
+        Instruction::Binary {
+            operator,
+            src1,
+            src2,
+            dst,
+        } => writeln!(
+            out,
+            "    {} {}, {}, {}",
+            emit_binary_op(*operator),
+            register(*dst),
+            register(*src1),
+            register(*src2)
+        ),
+        Instruction::BinaryImm {
+            operator,
+            src,
+            value,
+            dst,
+        } => {
+            let mnemonic = match operator {
+                BinaryOp::Addw => "addiw",
+                BinaryOp::Sllw => "slliw",
+                BinaryOp::Sraw => "sraiw",
+                BinaryOp::And => "andi",
+                BinaryOp::Or => "ori",
+                BinaryOp::Xor => "xori",
+                BinaryOp::Slt => "slti",
+                _ => unreachable!("{operator:?} has no immediate form"),
+            };
+            writeln!(
+                out,
+                "    {mnemonic} {}, {}, {value}",
+                register(*dst),
+                register(*src)
+            )
+        }
+        Instruction::Branch {
+            cond_code,
+            src1,
+            src2,
+            target,
+        } => {
+            let branch = |out: &mut dyn Write, cond_code, target: &str| {
+                writeln!(
+                    out,
+                    "    {} {}, {}, {target}",
+                    emit_branch(cond_code),
+                    register(*src1),
+                    register(*src2)
+                )
+            };
+            match reach {
+                Reach::Short => branch(out, *cond_code, &format!(".L{target}")),
+                _ => {
+                    let skip = format!(".L.branch.{index}");
+                    branch(out, cond_code.negate(), &skip)?;
+                    emit_jump(out, target, reach)?;
+                    writeln!(out, "{skip}:")
+                }
+            }
+        }
+        Instruction::J(target) => emit_jump(out, target, reach),
+        Instruction::Label(label) => writeln!(out, ".L{label}:"),
+        Instruction::Ret => {
+            writeln!(out, "    addi sp, s0, -16")?;
+            writeln!(out, "    ld ra, 8(sp)")?;
+            writeln!(out, "    ld s0, 0(sp)")?;
+            writeln!(out, "    addi sp, sp, 16")?;
+            writeln!(out, "    ret")
+        }
+    }
+}
+
+fn emit_jump(out: &mut impl Write, target: &str, reach: Reach) -> io::Result<()> {
+    match reach {
+        Reach::Far => writeln!(out, "    tail .L{target}"),
+        _ => writeln!(out, "    j .L{target}"),
+    }
+}
+
+/// Slots past the 12 bit offset are addressed through `ADDRESS`.
+fn emit_memory(
+    out: &mut impl Write,
+    mnemonic: &str,
+    value: Register,
+    slot: usize,
+) -> io::Result<()> {
+    let value = register(value);
+    if slot < 2048 {
+        writeln!(out, "    {mnemonic} {value}, {slot}(sp)")
+    } else {
+        let address = register(ADDRESS);
+        writeln!(out, "    li {address}, {slot}")?;
+        writeln!(out, "    add {address}, sp, {address}")?;
+        writeln!(out, "    {mnemonic} {value}, 0({address})")
+    }
+}
+
+fn emit_binary_op(operator: BinaryOp) -> &'static str {
+    match operator {
+        BinaryOp::Addw => "addw",
+        BinaryOp::Subw => "subw",
+        BinaryOp::Mulw => "mulw",
+        BinaryOp::Divw => "divw",
+        BinaryOp::Remw => "remw",
+        BinaryOp::Sllw => "sllw",
+        BinaryOp::Sraw => "sraw",
+        BinaryOp::And => "and",
+        BinaryOp::Or => "or",
+        BinaryOp::Xor => "xor",
+        BinaryOp::Slt => "slt",
+    }
+}
+
+fn emit_branch(cond_code: CondCode) -> &'static str {
+    match cond_code {
+        CondCode::Eq => "beq",
+        CondCode::Ne => "bne",
+        CondCode::Lt => "blt",
+        CondCode::Ge => "bge",
+        CondCode::Le => "ble",
+        CondCode::Gt => "bgt",
+    }
+}
+
+/// The register's ABI name.
+fn register(register: Register) -> &'static str {
+    const NAMES: [&str; 32] = [
+        "zero", "ra", "sp", "gp", "tp", "t0", "t1", "t2", "s0", "s1", "a0", "a1", "a2", "a3", "a4",
+        "a5", "a6", "a7", "s2", "s3", "s4", "s5", "s6", "s7", "s8", "s9", "s10", "s11", "t3", "t4",
+        "t5", "t6",
+    ];
+    NAMES[usize::from(register)]
+}
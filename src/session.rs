@@ -0,0 +1,413 @@
+use std::{
+    cell::OnceCell,
+    fmt::{self, Display, Formatter},
+    fs,
+    io::Write,
+    process::{Command, Stdio},
+    thread,
+    time::Duration,
+};
+
+use crate::{
+    ast,
+    code_emission::{self, DebugInfo, Syntax},
+    elf, irc,
+    irc_gen::IrcGenerator,
+    jit,
+    label_resolution::LabelResolver,
+    lexer::Lexer,
+    parser::Parser,
+    pass_manager::{AsmPass, IrcPass, PassManager},
+    target::Target,
+    token::Token,
+    var_resolution::VarResolver,
+    x86_64::X86_64,
+    Error, Result,
+};
+
+/// How a `Session` compiles.
+#[derive(Debug, Clone)]
+pub struct Options {
+    pub target: &'static dyn Target,
+    pub irc_passes: Vec<IrcPass>,
+    pub asm_passes: Vec<AsmPass>,
+    /// Print the program to stderr after every run of this pass.
+    pub print_after: Option<String>,
+    pub time_passes: bool,
+    /// Run the source through `cc -E` first. Only the preprocessor reads
+    /// files, the headers it includes and the source when the session was
+    /// opened from one.
+    pub preprocess: bool,
+    /// Directories the preprocessor searches for headers.
+    pub include_dirs: Vec<String>,
+    /// Macros to define, `NAME` or `NAME=VALUE`.
+    pub defines: Vec<String>,
+    /// The C compiler that preprocesses.
+    pub cc: String,
+    /// Other flags it's given, like `-std=c99`.
+    pub cc_flags: Vec<String>,
+    pub syntax: Syntax,
+    /// Annotate the assembly with the source lines it was compiled from.
+    pub verbose_asm: bool,
+    /// Name of the source file, for the debug info and the object file.
+    pub file_name: String,
+    /// Directory the source was compiled in, debug info is generated when
+    /// it's set.
+    pub debug_directory: Option<String>,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self::for_level(0)
+    }
+}
+
+impl Options {
+    /// The passes of the optimization level, for x86-64 and without
+    /// preprocessing.
+    pub fn for_level(level: u8) -> Self {
+        Self {
+            target: &X86_64,
+            irc_passes: IrcPass::for_level(level),
+            asm_passes: AsmPass::for_level(level),
+            print_after: None,
+            time_passes: false,
+            preprocess: false,
+            include_dirs: Vec::new(),
+            defines: Vec::new(),
+            cc: "gcc".to_string(),
+            cc_flags: Vec::new(),
+            syntax: Syntax::default(),
+            verbose_asm: false,
+            file_name: String::new(),
+            debug_directory: None,
+        }
+    }
+}
+
+/// How far `Session::compile` goes. The output has what every stage up
+/// to it produced, `Assembly`, `Object` and `Run` are alternatives for what
+/// to do with the generated code.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Stage {
+    /// Only preprocess, when `Options::preprocess` is set.
+    Preprocess,
+    Lex,
+    Parse,
+    /// Resolve variables and labels.
+    Validate,
+    Irc,
+    CodeGen,
+    Assembly,
+    Object,
+    /// Run the program in memory, only for targets with a built-in
+    /// assembler.
+    Run,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    Warning,
+    Error,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    pub message: String,
+}
+
+impl Display for Diagnostic {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self.severity {
+            Severity::Warning => write!(f, "Warning:\n - {}", self.message),
+            Severity::Error => write!(f, "{}", self.message),
+        }
+    }
+}
+
+/// What a `Session::compile` produced, borrowing names from the session's
+/// source.
+#[derive(Debug, Default)]
+pub struct Output<'s> {
+    /// The source after preprocessing, when it was preprocessed.
+    pub preprocessed: Option<String>,
+    pub tokens: Option<Vec<Token<'s>>>,
+    /// The program as parsed, with variables and labels resolved from
+    /// `Stage::Validate` on.
+    pub ast: Option<ast::Program<'s>>,
+    /// The irc after the passes ran.
+    pub irc: Option<irc::Program<'s>>,
+    /// The assembly, unless it was written out by `Session::emit_assembly`.
+    pub assembly: Option<String>,
+    /// An ELF relocatable object.
+    pub object: Option<Vec<u8>>,
+    /// What `main` returned, for `Stage::Run`.
+    pub status: Option<i32>,
+    pub timings: Vec<(&'static str, Duration)>,
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+impl Output<'_> {
+    pub fn failed(&self) -> bool {
+        self.diagnostics
+            .iter()
+            .any(|diagnostic| diagnostic.severity == Severity::Error)
+    }
+}
+
+/// Compiles one translation unit in memory.
+#[derive(Debug)]
+pub struct Session {
+    source: String,
+    /// The file the source was read from, which the preprocessor reads
+    /// again so headers next to it are found.
+    path: Option<String>,
+    options: Options,
+    /// The preprocessed source and the warnings the preprocessor gave.
+    preprocessed: OnceCell<(String, Vec<Diagnostic>)>,
+}
+
+impl Session {
+    pub fn new(source: impl Into<String>, options: Options) -> Self {
+        Self {
+            source: source.into(),
+            path: None,
+            options,
+            preprocessed: OnceCell::new(),
+        }
+    }
+
+    /// Reads the source from `path`.
+    pub fn open(path: &str, options: Options) -> Result<Self> {
+        let source = fs::read_to_string(path)
+            .map_err(|e| Error::IO(format!("Couldn't read file `{path}`:\n - {e}")))?;
+        Ok(Self {
+            path: Some(path.to_string()),
+            ..Self::new(source, options)
+        })
+    }
+
+    pub fn options(&self) -> &Options {
+        &self.options
+    }
+
+    pub fn compile(&self, stage: Stage) -> Output<'_> {
+        self.compile_to(stage, None)
+    }
+
+    /// Compiles up to `Stage::Assembly`, writing the assembly to `out` as it's
+    /// generated instead of keeping it in the output.
+    pub fn emit_assembly(&self, out: &mut dyn Write) -> Output<'_> {
+        self.compile_to(Stage::Assembly, Some(out))
+    }
+
+    fn compile_to(&self, stage: Stage, out: Option<&mut dyn Write>) -> Output<'_> {
+        let mut output = Output::default();
+        if let Err(error) = self.run(stage, &mut output, out) {
+            output.diagnostics.push(Diagnostic {
+                severity: Severity::Error,
+                message: error.to_string(),
+            });
+        }
+        output
+    }
+
+    fn run<'s>(
+        &'s self,
+        stage: Stage,
+        output: &mut Output<'s>,
+        out: Option<&mut dyn Write>,
+    ) -> Result<()> {
+        let options = &self.options;
+        let source = if options.preprocess {
+            if self.preprocessed.get().is_none() {
+                let preprocessed = preprocess(&self.source, self.path.as_deref(), options)?;
+                let _ = self.preprocessed.set(preprocessed);
+            }
+            let (source, warnings) = self.preprocessed.get().expect("preprocessed above");
+            output.diagnostics.extend(warnings.iter().cloned());
+            output.preprocessed = Some(source.clone());
+            source
+        } else {
+            &self.source
+        };
+        if stage == Stage::Preprocess {
+            return Ok(());
+        }
+
+        let mut lexer = Lexer::new(source);
+        if options.preprocess {
+            // How the preprocessor names the file in its line markers.
+            lexer.main_file = Some(match (&self.path, options.file_name.as_str()) {
+                (Some(path), _) => path.clone(),
+                (None, "") => "<stdin>".to_string(),
+                (None, name) => name.to_string(),
+            });
+        }
+        lexer.tokenize()?;
+        output.tokens = Some(lexer.tokens.clone());
+        if stage == Stage::Lex {
+            return Ok(());
+        }
+        let mut ast = Parser::new(&lexer).parse()?;
+        if stage == Stage::Parse {
+            output.ast = Some(ast);
+            return Ok(());
+        }
+        let mut var_resolver = VarResolver::new(0);
+        var_resolver.resolve_program(&mut ast)?;
+        let mut label_resolver = LabelResolver::new(var_resolver.counter);
+        label_resolver.resolve_program(&mut ast)?;
+        if stage == Stage::Validate {
+            output.ast = Some(ast);
+            return Ok(());
+        }
+        output.ast = Some(ast.clone());
+
+        let debug = options.debug_directory.is_some();
+        let mut irc = IrcGenerator::gen_program(
+            ast,
+            label_resolver.counter,
+            options.verbose_asm || debug,
+            debug,
+        );
+        let mut pass_manager =
+            PassManager::new(options.irc_passes.clone(), options.asm_passes.clone());
+        pass_manager.print_after.clone_from(&options.print_after);
+        pass_manager.time_passes = options.time_passes;
+        pass_manager.run_irc(&mut irc);
+        output.timings = pass_manager.timings().to_vec();
+        if stage == Stage::Irc {
+            output.irc = Some(irc);
+            return Ok(());
+        }
+        output.irc = Some(irc.clone());
+
+        let assembly =
+            options
+                .target
+                .select_instructions(irc, &mut pass_manager, options.verbose_asm);
+        output.timings = pass_manager.timings().to_vec();
+        match stage {
+            Stage::Preprocess
+            | Stage::Lex
+            | Stage::Parse
+            | Stage::Validate
+            | Stage::Irc
+            | Stage::CodeGen => {}
+            Stage::Assembly => {
+                let emission = code_emission::Options {
+                    syntax: options.syntax,
+                    source: options.verbose_asm.then_some(self.source.as_str()),
+                    debug: options
+                        .debug_directory
+                        .as_deref()
+                        .map(|directory| DebugInfo {
+                            file: &options.file_name,
+                            directory,
+                        }),
+                };
+                let error = |e| Error::IO(format!("Couldn't emit the assembly:\n - {e}"));
+                if let Some(out) = out {
+                    assembly.emit(out, emission).map_err(error)?;
+                } else {
+                    let mut text = Vec::new();
+                    assembly.emit(&mut text, emission).map_err(error)?;
+                    output.assembly = Some(String::from_utf8_lossy(&text).into_owned());
+                }
+            }
+            Stage::Object | Stage::Run => {
+                let object = assembly.encode(&options.file_name).ok_or_else(|| {
+                    Error::Assemble(format!(
+                        "The built-in assembler doesn't support {}",
+                        options.target.name()
+                    ))
+                })?;
+                if options.debug_directory.is_some() {
+                    output.diagnostics.push(Diagnostic {
+                        severity: Severity::Warning,
+                        message: "The built-in assembler doesn't generate debug info".to_string(),
+                    });
+                }
+                if stage == Stage::Run {
+                    output.status = Some(jit::run(&object)?);
+                } else {
+                    let mut bytes = Vec::new();
+                    elf::write_object(&mut bytes, &object)
+                        .map_err(|e| Error::IO(format!("Couldn't write the object:\n - {e}")))?;
+                    output.object = Some(bytes);
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Runs the file at `path` through `cc -E`, or `source` over a pipe when
+/// there's no file, returning the output and any warnings.
+fn preprocess(
+    source: &str,
+    path: Option<&str>,
+    options: &Options,
+) -> Result<(String, Vec<Diagnostic>)> {
+    let cc = &options.cc;
+    let mut command = Command::new(cc);
+    command
+        .args(["-E", "-x", "c"])
+        .args(options.include_dirs.iter().map(|dir| format!("-I{dir}")))
+        .args(options.defines.iter().map(|define| format!("-D{define}")))
+        .args(&options.cc_flags)
+        .arg(path.unwrap_or("-"))
+        .stdin(if path.is_some() {
+            Stdio::null()
+        } else {
+            Stdio::piped()
+        })
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+    let mut child = command
+        .spawn()
+        .map_err(|e| Error::IO(format!("Couldn't run {cc} to preprocess:\n - {e}")))?;
+    let stdin = child.stdin.take();
+    // A line marker names the piped source in the output and `__FILE__`,
+    // which would otherwise be `<stdin>`.
+    let marker = match &options.file_name {
+        name if name.is_empty() => String::new(),
+        name => format!(
+            "# 1 \"{}\"\n",
+            name.replace('\\', "\\\\").replace('"', "\\\"")
+        ),
+    };
+    // Writing on another thread keeps the preprocessor from blocking on a
+    // full stdout while the source is still being written.
+    let output = thread::scope(|scope| {
+        if let Some(mut stdin) = stdin {
+            scope.spawn(move || {
+                stdin.write_all(marker.as_bytes())?;
+                stdin.write_all(source.as_bytes())
+            });
+        }
+        child.wait_with_output()
+    })
+    .map_err(|e| Error::IO(format!("Couldn't run {cc} to preprocess:\n - {e}")))?;
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    if output.status.code() != Some(0) {
+        return Err(Error::Preprocess(format!(
+            "Error preprocessing '{}':\n - stderr: '{stderr}'",
+            options.file_name
+        )));
+    }
+    let warnings = if stderr.trim().is_empty() {
+        Vec::new()
+    } else {
+        vec![Diagnostic {
+            severity: Severity::Warning,
+            message: stderr.trim_end().to_string(),
+        }]
+    };
+    Ok((
+        String::from_utf8_lossy(&output.stdout).into_owned(),
+        warnings,
+    ))
+}
@@ -0,0 +1,454 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::{
+    cfg::{Cfg, Flow, FlowInstruction},
+    dataflow,
+    dead_store_elimination::Liveness,
+    irc::{Function, Instruction, Program, Value},
+};
+
+/// `dst = phi(args)`, with one argument per predecessor of the block, in the
+/// order of [`crate::cfg::BasicBlock::predecessors`].
+#[derive(Debug, Clone)]
+pub struct Phi {
+    pub var: String,
+    pub dst: String,
+    pub args: Vec<Value>,
+}
+
+/// A function in SSA form: every variable is assigned once, and values merging
+/// at join points are selected by the phis at the start of each block.
+#[derive(Debug)]
+pub struct SsaFunction<'a> {
+    pub name: &'a str,
+    pub cfg: Cfg<Instruction>,
+    pub phis: Vec<Vec<Phi>>,
+    next_id: usize,
+}
+
+/// Converts the program into SSA form and back, to exercise both directions.
+/// Only renames variables and moves copies around, so it never reports a change
+/// that would keep the pass manager iterating.
+pub fn round_trip(program: &mut Program) -> bool {
+    match program {
+        Program::Function(function) => {
+            let placeholder = Function {
+                name: function.name,
+                instructons: Vec::new(),
+            };
+            let ssa = SsaFunction::new(std::mem::replace(function, placeholder));
+            *function = ssa.into_function();
+            false
+        }
+    }
+}
+
+impl<'a> SsaFunction<'a> {
+    pub fn new(function: Function<'a>) -> Self {
+        let mut next_id = function
+            .instructons
+            .iter()
+            .flat_map(|ins| ins.dst().into_iter().chain(var_names(ins)))
+            .filter_map(|name| name.split_once('.')?.1.parse::<usize>().ok())
+            .max()
+            .map_or(0, |id| id + 1);
+
+        // A fresh entry label keeps block 0 free of predecessors, even when the
+        // function starts with a label that is jumped to.
+        let entry = Instruction::Label(format!("ssa_entry.{next_id}"));
+        next_id += 1;
+        let cfg = Cfg::new(std::iter::once(entry).chain(function.instructons).collect());
+        let reachable = cfg.reachable();
+        let cfg = Cfg::new(
+            cfg.blocks
+                .into_iter()
+                .zip(reachable)
+                .filter(|(_, reachable)| *reachable)
+                .flat_map(|(block, _)| block.instructions)
+                .collect(),
+        );
+
+        let mut ssa = Self {
+            name: function.name,
+            phis: vec![Vec::new(); cfg.blocks.len()],
+            cfg,
+            next_id,
+        };
+        let dominators = Dominators::new(&ssa.cfg);
+        ssa.insert_phis(&dominators);
+        ssa.rename(&dominators);
+        ssa
+    }
+
+    fn insert_phis(&mut self, dominators: &Dominators) {
+        let live_in = dataflow::solve(&self.cfg, &Liveness).block_in;
+        let mut def_blocks: HashMap<String, Vec<usize>> = HashMap::new();
+        for (index, block) in self.cfg.blocks.iter().enumerate() {
+            for dst in block.instructions.iter().filter_map(Instruction::dst) {
+                let blocks = def_blocks.entry(dst.to_string()).or_default();
+                if !blocks.contains(&index) {
+                    blocks.push(index);
+                }
+            }
+        }
+
+        let mut vars: Vec<_> = def_blocks.into_iter().collect();
+        vars.sort();
+        for (var, mut worklist) in vars {
+            let mut has_phi = HashSet::new();
+            while let Some(block) = worklist.pop() {
+                for &frontier in &dominators.frontiers[block] {
+                    if !live_in[frontier].contains(&var) || !has_phi.insert(frontier) {
+                        continue;
+                    }
+                    let arg_count = self.cfg.blocks[frontier].predecessors.len();
+                    self.phis[frontier].push(Phi {
+                        var: var.clone(),
+                        dst: var.clone(),
+                        args: vec![Value::Var(var.clone()); arg_count],
+                    });
+                    worklist.push(frontier);
+                }
+            }
+        }
+    }
+
+    fn rename(&mut self, dominators: &Dominators) {
+        let mut stacks: HashMap<String, Vec<String>> = HashMap::new();
+        self.rename_block(0, dominators, &mut stacks);
+    }
+
+    fn rename_block(
+        &mut self,
+        index: usize,
+        dominators: &Dominators,
+        stacks: &mut HashMap<String, Vec<String>>,
+    ) {
+        let mut defined = Vec::new();
+        for phi_index in 0..self.phis[index].len() {
+            let var = self.phis[index][phi_index].var.clone();
+            let name = self.fresh_var(&var);
+            self.phis[index][phi_index].dst = name.clone();
+            stacks.entry(var.clone()).or_default().push(name);
+            defined.push(var);
+        }
+
+        let mut instructions = std::mem::take(&mut self.cfg.blocks[index].instructions);
+        for ins in &mut instructions {
+            for src in ins.sources_mut() {
+                if let Value::Var(name) = src {
+                    if let Some(current) = stacks.get(name.as_str()).and_then(|stack| stack.last())
+                    {
+                        *name = current.clone();
+                    }
+                }
+            }
+            if let Some(var) = ins.dst().map(str::to_string) {
+                let name = self.fresh_var(&var);
+                set_dst(ins, name.clone());
+                stacks.entry(var.clone()).or_default().push(name);
+                defined.push(var);
+            }
+        }
+        self.cfg.blocks[index].instructions = instructions;
+
+        for successor in self.cfg.blocks[index].successors.clone() {
+            let position = self.cfg.blocks[successor]
+                .predecessors
+                .iter()
+                .position(|&pred| pred == index)
+                .expect("edges are recorded on both ends");
+            for phi in &mut self.phis[successor] {
+                if let Some(current) = stacks.get(&phi.var).and_then(|stack| stack.last()) {
+                    phi.args[position] = Value::Var(current.clone());
+                }
+            }
+        }
+
+        for &child in &dominators.children[index] {
+            self.rename_block(child, dominators, stacks);
+        }
+
+        for var in defined {
+            stacks.get_mut(&var).and_then(Vec::pop);
+        }
+    }
+
+    fn fresh_var(&mut self, var: &str) -> String {
+        let base = var.split_once('.').map_or(var, |(base, _)| base);
+        let name = format!("{base}.{id}", id = self.next_id);
+        self.next_id += 1;
+        name
+    }
+
+    /// Leaves SSA form by replacing the phis with parallel copies on the
+    /// incoming edges, splitting edges where the copies can't be placed in the
+    /// predecessor.
+    pub fn into_function(mut self) -> Function<'a> {
+        let block_count = self.cfg.blocks.len();
+        let mut appended: Vec<Vec<Instruction>> = vec![Vec::new(); block_count];
+        let mut edge_blocks: Vec<Vec<Instruction>> = Vec::new();
+
+        for index in 0..block_count {
+            let phis = std::mem::take(&mut self.phis[index]);
+            if phis.is_empty() {
+                continue;
+            }
+            let label = self.block_label(index);
+            for (position, pred) in self.cfg.blocks[index]
+                .predecessors
+                .clone()
+                .into_iter()
+                .enumerate()
+            {
+                let copies: Vec<(String, Value)> = phis
+                    .iter()
+                    .map(|phi| (phi.dst.clone(), phi.args[position].clone()))
+                    .collect();
+                let copies = self.sequentialize(copies);
+                let pred_block = &mut self.cfg.blocks[pred];
+                let terminator = pred_block.instructions.last().map(FlowInstruction::flow);
+                match terminator {
+                    Some(Flow::Jump(_)) => {
+                        let jump = pred_block.instructions.pop();
+                        pred_block.instructions.extend(copies);
+                        pred_block.instructions.extend(jump);
+                    }
+                    Some(Flow::ConditionalJump(target)) => {
+                        if target == label {
+                            let edge_label = format!("ssa_edge.{id}", id = self.next_id);
+                            self.next_id += 1;
+                            retarget(
+                                pred_block
+                                    .instructions
+                                    .last_mut()
+                                    .expect("terminator exists"),
+                                edge_label.clone(),
+                            );
+                            let mut edge = vec![Instruction::Label(edge_label)];
+                            edge.extend(copies.iter().cloned());
+                            edge.push(Instruction::Jump {
+                                target: label.clone(),
+                            });
+                            edge_blocks.push(edge);
+                        }
+                        if pred + 1 == index {
+                            // Falls through into this block: the copies go between the two.
+                            appended[pred].extend(copies);
+                            appended[pred].push(Instruction::Jump {
+                                target: label.clone(),
+                            });
+                        }
+                    }
+                    _ => pred_block.instructions.extend(copies),
+                }
+            }
+        }
+
+        let instructions = self
+            .cfg
+            .blocks
+            .into_iter()
+            .zip(appended)
+            .flat_map(|(block, appended)| block.instructions.into_iter().chain(appended))
+            .chain(edge_blocks.into_iter().flatten())
+            .skip(1)
+            .collect();
+        Function {
+            name: self.name,
+            instructons: instructions,
+        }
+    }
+
+    /// Returns the label of a block, adding one if it has none.
+    fn block_label(&mut self, index: usize) -> String {
+        let instructions = &mut self.cfg.blocks[index].instructions;
+        if let Some(Instruction::Label(label)) = instructions.first() {
+            return label.clone();
+        }
+        let label = format!("ssa_block.{id}", id = self.next_id);
+        self.next_id += 1;
+        instructions.insert(0, Instruction::Label(label.clone()));
+        label
+    }
+
+    /// Orders parallel copies so that no copy overwrites a value another one
+    /// still has to read, breaking cycles with a temporary.
+    fn sequentialize(&mut self, copies: Vec<(String, Value)>) -> Vec<Instruction> {
+        let mut pending: Vec<(String, Value)> = copies
+            .into_iter()
+            .filter(|(dst, src)| src != &Value::Var(dst.clone()))
+            .collect();
+        let mut instructions = Vec::new();
+        while !pending.is_empty() {
+            let ready = pending.iter().position(|(dst, _)| {
+                !pending
+                    .iter()
+                    .any(|(_, src)| matches!(src, Value::Var(name) if name == dst))
+            });
+            match ready {
+                Some(index) => {
+                    let (dst, src) = pending.remove(index);
+                    instructions.push(Instruction::Copy { src, dst });
+                }
+                None => {
+                    let blocked = pending[0].0.clone();
+                    let temp = self.fresh_var("ssa_temp");
+                    instructions.push(Instruction::Copy {
+                        src: Value::Var(blocked.clone()),
+                        dst: temp.clone(),
+                    });
+                    for (_, src) in &mut pending {
+                        if matches!(src, Value::Var(name) if *name == blocked) {
+                            *src = Value::Var(temp.clone());
+                        }
+                    }
+                }
+            }
+        }
+        instructions
+    }
+}
+
+fn var_names(instruction: &Instruction) -> impl Iterator<Item = &str> {
+    instruction
+        .sources()
+        .into_iter()
+        .filter_map(|src| match src {
+            Value::Var(name) => Some(name.as_str()),
+            Value::Constant(_) => None,
+        })
+}
+
+fn set_dst(instruction: &mut Instruction, name: String) {
+    match instruction {
+        Instruction::Unary { dst, .. }
+        | Instruction::Binary { dst, .. }
+        | Instruction::Copy { dst, .. }
+        | Instruction::Select { dst, .. } => *dst = name,
+        _ => unreachable!("only called on instructions with a destination"),
+    }
+}
+
+fn retarget(instruction: &mut Instruction, label: String) {
+    match instruction {
+        Instruction::Jump { target }
+        | Instruction::JumpIfZero { target, .. }
+        | Instruction::JumpIfNotZero { target, .. } => *target = label,
+        _ => unreachable!("only called on jumps"),
+    }
+}
+
+/// Dominator tree and dominance frontiers, computed with the Cooper, Harvey and
+/// Kennedy iterative algorithm. Assumes every block is reachable from block `0`.
+#[derive(Debug)]
+pub struct Dominators {
+    pub idom: Vec<usize>,
+    pub children: Vec<Vec<usize>>,
+    pub frontiers: Vec<Vec<usize>>,
+}
+
+impl Dominators {
+    pub fn new<I>(cfg: &Cfg<I>) -> Self {
+        let block_count = cfg.blocks.len();
+        let order = reverse_postorder(cfg);
+        let mut rpo_index = vec![usize::MAX; block_count];
+        for (position, &block) in order.iter().enumerate() {
+            rpo_index[block] = position;
+        }
+
+        let mut idom: Vec<Option<usize>> = vec![None; block_count];
+        if block_count > 0 {
+            idom[0] = Some(0);
+        }
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for &block in order.iter().skip(1) {
+                let new_idom = cfg.blocks[block]
+                    .predecessors
+                    .iter()
+                    .copied()
+                    .filter(|&pred| idom[pred].is_some())
+                    .reduce(|finger1, finger2| intersect(&idom, &rpo_index, finger1, finger2));
+                if new_idom.is_some() && idom[block] != new_idom {
+                    idom[block] = new_idom;
+                    changed = true;
+                }
+            }
+        }
+        let idom: Vec<usize> = idom
+            .into_iter()
+            .map(|idom| idom.expect("every block is reachable"))
+            .collect();
+
+        let mut children = vec![Vec::new(); block_count];
+        for &block in order.iter().skip(1) {
+            children[idom[block]].push(block);
+        }
+
+        let mut frontiers: Vec<Vec<usize>> = vec![Vec::new(); block_count];
+        for (block, basic_block) in cfg.blocks.iter().enumerate() {
+            if basic_block.predecessors.len() < 2 {
+                continue;
+            }
+            for &pred in &basic_block.predecessors {
+                let mut runner = pred;
+                while runner != idom[block] {
+                    if !frontiers[runner].contains(&block) {
+                        frontiers[runner].push(block);
+                    }
+                    runner = idom[runner];
+                }
+            }
+        }
+
+        Self {
+            idom,
+            children,
+            frontiers,
+        }
+    }
+}
+
+fn intersect(
+    idom: &[Option<usize>],
+    rpo_index: &[usize],
+    mut finger1: usize,
+    mut finger2: usize,
+) -> usize {
+    while finger1 != finger2 {
+        while rpo_index[finger1] > rpo_index[finger2] {
+            finger1 = idom[finger1].expect("processed blocks have a dominator");
+        }
+        while rpo_index[finger2] > rpo_index[finger1] {
+            finger2 = idom[finger2].expect("processed blocks have a dominator");
+        }
+    }
+    finger1
+}
+
+fn reverse_postorder<I>(cfg: &Cfg<I>) -> Vec<usize> {
+    let mut visited = vec![false; cfg.blocks.len()];
+    let mut postorder = Vec::with_capacity(cfg.blocks.len());
+    let mut stack = Vec::new();
+    if !cfg.blocks.is_empty() {
+        visited[0] = true;
+        stack.push((0, 0));
+    }
+    while let Some((block, next)) = stack.last_mut() {
+        let block = *block;
+        if let Some(&successor) = cfg.blocks[block].successors.get(*next) {
+            *next += 1;
+            if !visited[successor] {
+                visited[successor] = true;
+                stack.push((successor, 0));
+            }
+        } else {
+            postorder.push(block);
+            stack.pop();
+        }
+    }
+    postorder.reverse();
+    postorder
+}
@@ -0,0 +1,106 @@
+use std::{
+    fmt,
+    io::{self, Write},
+};
+
+use crate::{
+    aarch64::Aarch64, code_emission, elf, irc, pass_manager::PassManager, riscv64::Riscv64,
+    wasm::Wasm32, x86_64::X86_64,
+};
+
+/// A machine code can be generated for. The driver only goes through this
+/// trait, so a new backend or ABI is a new implementation added to `ALL`.
+pub trait Target: fmt::Debug + Sync {
+    /// The target triple.
+    fn name(&self) -> &'static str;
+
+    fn architecture(&self) -> &'static str;
+
+    /// The sizes the backend lays out the stack frame with.
+    fn data_layout(&self) -> DataLayout;
+
+    fn abi(&self) -> Abi;
+
+    /// The registers the backend allocates and lowers instructions with.
+    fn registers(&self) -> RegisterInfo;
+
+    /// The gcc that assembles and links for the target, if there is one.
+    fn gcc(&self) -> Option<&'static str>;
+
+    /// Whether the built-in assembler can encode the target's programs.
+    fn has_assembler(&self) -> bool {
+        false
+    }
+
+    /// Whether the assembly honors the syntax, annotation and debug info
+    /// options of [`code_emission::Options`], which only x86-64's does.
+    fn has_emission_options(&self) -> bool {
+        false
+    }
+
+    /// Extension of the file the assembly is written to.
+    fn assembly_extension(&self) -> &'static str {
+        "s"
+    }
+
+    /// Selects instructions for the program and gives its variables
+    /// registers or stack slots, running the target's passes on the way.
+    /// With `verbose`, the irc is kept for the annotations of the assembly.
+    fn select_instructions<'a>(
+        &self,
+        program: irc::Program<'a>,
+        pass_manager: &mut PassManager,
+        verbose: bool,
+    ) -> Box<dyn Assembly + 'a>;
+}
+
+/// A program lowered for a target, ready to be written out.
+pub trait Assembly {
+    fn emit(&self, out: &mut dyn Write, options: code_emission::Options) -> io::Result<()>;
+
+    /// Encodes the program with the built-in assembler, `None` if the target
+    /// doesn't have one.
+    fn encode<'a>(&'a self, _file: &'a str) -> Option<elf::Object<'a>> {
+        None
+    }
+}
+
+/// Sizes and alignments, in bytes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DataLayout {
+    pub int_size: usize,
+    pub pointer_size: usize,
+    /// Alignment of the stack pointer at calls.
+    pub stack_alignment: usize,
+}
+
+/// The calling convention.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Abi {
+    /// Where an `int` is returned, `None` when it isn't returned in a register.
+    pub return_register: Option<&'static str>,
+    pub argument_registers: &'static [&'static str],
+    /// Registers a function has to preserve for its caller.
+    pub callee_saved: &'static [&'static str],
+    /// Bytes below the stack pointer a function may use without moving it.
+    pub red_zone: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RegisterInfo {
+    /// Registers variables can be assigned to, empty when they all live on
+    /// the stack.
+    pub allocatable: &'static [&'static str],
+    /// Registers kept free for lowering instructions.
+    pub scratch: &'static [&'static str],
+    pub stack_pointer: Option<&'static str>,
+    pub frame_pointer: Option<&'static str>,
+}
+
+pub const ALL: [&dyn Target; 4] = [&X86_64, &Aarch64, &Riscv64, &Wasm32];
+
+/// Also accepts the architecture on its own, e.g. `aarch64`.
+pub fn from_name(name: &str) -> Option<&'static dyn Target> {
+    ALL.into_iter()
+        .find(|target| target.name() == name || target.architecture() == name)
+}
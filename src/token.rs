@@ -66,11 +66,19 @@ pub struct Token<'de> {
     pub kind: TokenKind,
     pub lexeme: &'de str,
     pub line: usize,
+    /// Whether the token is from the file being compiled rather than a
+    /// header it includes.
+    pub main_file: bool,
 }
 
 impl<'de> Token<'de> {
     pub fn new(kind: TokenKind, lexeme: &'de str, line: usize) -> Self {
-        Token { kind, lexeme, line }
+        Token {
+            kind,
+            lexeme,
+            line,
+            main_file: true,
+        }
     }
 }
 
@@ -0,0 +1,68 @@
+use std::collections::HashSet;
+
+use crate::{
+    cfg::{Cfg, Flow, FlowInstruction},
+    irc::{Function, Program},
+};
+
+/// Removes unreachable blocks, jumps to the next instruction and labels nothing jumps to.
+/// Returns `true` if the program changed.
+pub fn eliminate_unreachable_code(program: &mut Program) -> bool {
+    match program {
+        Program::Function(function) => eliminate_function(function),
+    }
+}
+
+fn eliminate_function(function: &mut Function) -> bool {
+    let instruction_count = function.instructons.len();
+    let cfg = Cfg::new(std::mem::take(&mut function.instructons));
+    let reachable = cfg.reachable();
+    let mut instructions: Vec<_> = cfg
+        .blocks
+        .into_iter()
+        .zip(reachable)
+        .filter(|(_, reachable)| *reachable)
+        .flat_map(|(block, _)| block.instructions)
+        .collect();
+    remove_useless_jumps(&mut instructions);
+    remove_useless_labels(&mut instructions);
+    function.instructons = instructions;
+    function.instructons.len() != instruction_count
+}
+
+fn remove_useless_jumps<I: FlowInstruction>(instructions: &mut Vec<I>) {
+    let useless: HashSet<usize> = (0..instructions.len())
+        .filter(|&index| {
+            let (Flow::Jump(target) | Flow::ConditionalJump(target)) = instructions[index].flow()
+            else {
+                return false;
+            };
+            instructions[index + 1..]
+                .iter()
+                .map_while(|ins| match ins.flow() {
+                    Flow::Label(label) => Some(label),
+                    _ => None,
+                })
+                .any(|label| label == target)
+        })
+        .collect();
+    let mut index = 0;
+    instructions.retain(|_| {
+        index += 1;
+        !useless.contains(&(index - 1))
+    });
+}
+
+fn remove_useless_labels<I: FlowInstruction>(instructions: &mut Vec<I>) {
+    let targets: HashSet<String> = instructions
+        .iter()
+        .filter_map(|ins| match ins.flow() {
+            Flow::Jump(target) | Flow::ConditionalJump(target) => Some(target.to_string()),
+            _ => None,
+        })
+        .collect();
+    instructions.retain(|ins| match ins.flow() {
+        Flow::Label(label) => targets.contains(label),
+        _ => true,
+    });
+}
@@ -44,7 +44,7 @@ impl VarResolver {
 
     fn resolve_decleration(&mut self, decleration: &mut Decleration) -> Result<()> {
         match decleration {
-            Decleration::Decleration { name, init } => {
+            Decleration::Decleration { name, init, .. } => {
                 if let Some((_, true)) = self.variable_map.get(name) {
                     return Err(Error::Resolver(
                         "Duplicate Variable Decleration".to_string(),
@@ -120,7 +120,7 @@ impl VarResolver {
                     self.resolve_statement(else_branch)?;
                 }
             }
-            Stmt::Label(_, stmt) => self.resolve_statement(stmt)?,
+            Stmt::Label(_, stmt) | Stmt::Line(_, stmt) => self.resolve_statement(stmt)?,
             Stmt::Compound(block) => {
                 let new_map = self.create_new_scope();
                 let old_map = std::mem::replace(&mut self.variable_map, new_map);
@@ -128,25 +128,11 @@ impl VarResolver {
                 self.variable_map = old_map;
             }
             Stmt::Goto(_) | Stmt::Null => {}
-            Stmt::Break { label } => todo!(),
-            Stmt::Continue { label } => todo!(),
-            Stmt::While {
-                condition,
-                body,
-                label,
-            } => todo!(),
-            Stmt::DoWhile {
-                body,
-                condition,
-                label,
-            } => todo!(),
-            Stmt::For {
-                init,
-                condition,
-                post,
-                body,
-                label,
-            } => todo!(),
+            Stmt::Break { .. } => todo!(),
+            Stmt::Continue { .. } => todo!(),
+            Stmt::While { .. } => todo!(),
+            Stmt::DoWhile { .. } => todo!(),
+            Stmt::For { .. } => todo!(),
         };
         Ok(())
     }
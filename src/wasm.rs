@@ -0,0 +1,717 @@
+use std::{
+    collections::{HashMap, HashSet},
+    io::{self, Write},
+};
+
+use crate::{
+    cfg::{Cfg, Flow, FlowInstruction},
+    code_emission, irc,
+    pass_manager::PassManager,
+    target::{Abi, Assembly, DataLayout, RegisterInfo, Target},
+};
+
+#[derive(Debug, Clone)]
+pub enum Program<'a> {
+    Function(Function<'a>),
+}
+
+#[derive(Debug, Clone)]
+pub struct Function<'a> {
+    pub name: &'a str,
+    /// Every variable of the function becomes an `i32` local.
+    pub locals: Vec<String>,
+    pub instructons: Vec<Instruction>,
+}
+
+/// Local holding the block to run next when the control flow can't be made
+/// structured. Variables always contain a `.`, so it can't clash with them.
+const DISPATCH: &str = "next";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BinaryOp {
+    Add,
+    Sub,
+    Mul,
+    DivS,
+    RemS,
+    Shl,
+    ShrS,
+    And,
+    Or,
+    Xor,
+    Eq,
+    Ne,
+    LtS,
+    LeS,
+    GtS,
+    GeS,
+}
+
+/// Instructions of the stack machine, control flow is structured and `Br`
+/// refers to an enclosing `Block` or `Loop` by name.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Instruction {
+    I32Const(i32),
+    LocalGet(String),
+    LocalSet(String),
+    Eqz,
+    Binary(BinaryOp),
+    /// Pops the condition, then the values for zero and for non zero.
+    Select,
+    Block(String),
+    Loop(String),
+    If,
+    Else,
+    End,
+    Br(String),
+    BrIf(String),
+    BrTable {
+        targets: Vec<String>,
+        default: String,
+    },
+    Return,
+    Unreachable,
+}
+
+impl BinaryOp {
+    fn from_operator(operator: irc::BinaryOp) -> Self {
+        match operator {
+            irc::BinaryOp::Add => Self::Add,
+            irc::BinaryOp::Subtract => Self::Sub,
+            irc::BinaryOp::Multiply => Self::Mul,
+            irc::BinaryOp::Divide => Self::DivS,
+            irc::BinaryOp::Remainder => Self::RemS,
+            irc::BinaryOp::LeftShift => Self::Shl,
+            irc::BinaryOp::RightShift => Self::ShrS,
+            irc::BinaryOp::BitAnd => Self::And,
+            irc::BinaryOp::BitOr => Self::Or,
+            irc::BinaryOp::Xor => Self::Xor,
+            irc::BinaryOp::Equal => Self::Eq,
+            irc::BinaryOp::NotEqual => Self::Ne,
+            irc::BinaryOp::LessThan => Self::LtS,
+            irc::BinaryOp::LessOrEqual => Self::LeS,
+            irc::BinaryOp::GreaterThan => Self::GtS,
+            irc::BinaryOp::GreaterOrEqual => Self::GeS,
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Self::Add => "i32.add",
+            Self::Sub => "i32.sub",
+            Self::Mul => "i32.mul",
+            Self::DivS => "i32.div_s",
+            Self::RemS => "i32.rem_s",
+            Self::Shl => "i32.shl",
+            Self::ShrS => "i32.shr_s",
+            Self::And => "i32.and",
+            Self::Or => "i32.or",
+            Self::Xor => "i32.xor",
+            Self::Eq => "i32.eq",
+            Self::Ne => "i32.ne",
+            Self::LtS => "i32.lt_s",
+            Self::LeS => "i32.le_s",
+            Self::GtS => "i32.gt_s",
+            Self::GeS => "i32.ge_s",
+        }
+    }
+}
+
+/// How control leaves a basic block. `None` targets fall off the end of the
+/// function.
+#[derive(Debug, Clone)]
+enum Exit {
+    Return(irc::Value),
+    Jump(Option<usize>),
+    Branch {
+        condition: irc::Value,
+        non_zero: Option<usize>,
+        zero: Option<usize>,
+    },
+}
+
+impl Exit {
+    fn successors(&self) -> Vec<usize> {
+        match self {
+            Self::Return(_) => Vec::new(),
+            Self::Jump(target) => target.iter().copied().collect(),
+            Self::Branch { non_zero, zero, .. } => non_zero.iter().chain(zero).copied().collect(),
+        }
+    }
+}
+
+/// WebAssembly, written as a module in the text format.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Wasm32;
+
+impl Target for Wasm32 {
+    fn name(&self) -> &'static str {
+        "wasm32-unknown-unknown"
+    }
+
+    fn architecture(&self) -> &'static str {
+        "wasm32"
+    }
+
+    fn data_layout(&self) -> DataLayout {
+        DataLayout {
+            int_size: 4,
+            pointer_size: 4,
+            stack_alignment: 16,
+        }
+    }
+
+    fn abi(&self) -> Abi {
+        Abi {
+            return_register: None,
+            argument_registers: &[],
+            callee_saved: &[],
+            red_zone: 0,
+        }
+    }
+
+    fn registers(&self) -> RegisterInfo {
+        RegisterInfo {
+            allocatable: &[],
+            scratch: &[],
+            stack_pointer: None,
+            frame_pointer: None,
+        }
+    }
+
+    fn gcc(&self) -> Option<&'static str> {
+        None
+    }
+
+    fn assembly_extension(&self) -> &'static str {
+        "wat"
+    }
+
+    fn select_instructions<'a>(
+        &self,
+        program: irc::Program<'a>,
+        _pass_manager: &mut PassManager,
+        _verbose: bool,
+    ) -> Box<dyn Assembly + 'a> {
+        Box::new(gen_program(program))
+    }
+}
+
+impl Assembly for Program<'_> {
+    fn emit(&self, out: &mut dyn Write, _options: code_emission::Options) -> io::Result<()> {
+        emit_program(out, self)
+    }
+}
+
+pub fn gen_program(program: irc::Program) -> Program {
+    match program {
+        irc::Program::Function(function) => Program::Function(gen_function(function)),
+    }
+}
+
+fn gen_function(function: irc::Function) -> Function {
+    let mut locals = Vec::new();
+    let mut seen = HashSet::new();
+    for ins in &function.instructons {
+        let sources = ins.sources().into_iter().filter_map(|value| match value {
+            irc::Value::Var(name) => Some(name.as_str()),
+            irc::Value::Constant(_) => None,
+        });
+        for name in sources.chain(ins.dst()) {
+            if seen.insert(name) {
+                locals.push(name.to_string());
+            }
+        }
+    }
+
+    let cfg = Cfg::new(function.instructons);
+    let labels: HashMap<String, usize> = cfg
+        .blocks
+        .iter()
+        .enumerate()
+        .filter_map(|(index, block)| match block.instructions.first()?.flow() {
+            Flow::Label(label) => Some((label.to_string(), index)),
+            _ => None,
+        })
+        .collect();
+    let block_count = cfg.blocks.len();
+    let mut bodies = Vec::new();
+    let mut exits = Vec::new();
+    for (index, block) in cfg.blocks.into_iter().enumerate() {
+        let next = (index + 1 < block_count).then_some(index + 1);
+        let target = |label: &str| labels.get(label).copied();
+        let mut body = Vec::new();
+        let mut exit = Exit::Jump(next);
+        for ins in block.instructions {
+            match ins {
+                irc::Instruction::Ret(value) => exit = Exit::Return(value),
+                irc::Instruction::Jump { target: label } => exit = Exit::Jump(target(&label)),
+                irc::Instruction::JumpIfZero {
+                    condition,
+                    target: label,
+                } => {
+                    exit = Exit::Branch {
+                        condition,
+                        non_zero: next,
+                        zero: target(&label),
+                    }
+                }
+                irc::Instruction::JumpIfNotZero {
+                    condition,
+                    target: label,
+                } => {
+                    exit = Exit::Branch {
+                        condition,
+                        non_zero: target(&label),
+                        zero: next,
+                    }
+                }
+                ins => gen_instruction(&mut body, ins),
+            }
+        }
+        bodies.push(body);
+        exits.push(exit);
+    }
+    // Going straight to where empty blocks jump lets `if (c) goto l;` become
+    // a `br_if`.
+    let forward = |mut target: Option<usize>| {
+        for _ in 0..block_count {
+            match target.map(|block| (bodies[block].is_empty(), &exits[block])) {
+                Some((true, Exit::Jump(next))) => target = *next,
+                _ => break,
+            }
+        }
+        target
+    };
+    let exits: Vec<Exit> = exits
+        .iter()
+        .map(|exit| match exit {
+            Exit::Return(_) => exit.clone(),
+            Exit::Jump(target) => Exit::Jump(forward(*target)),
+            Exit::Branch {
+                condition,
+                non_zero,
+                zero,
+            } => Exit::Branch {
+                condition: condition.clone(),
+                non_zero: forward(*non_zero),
+                zero: forward(*zero),
+            },
+        })
+        .collect();
+
+    let mut instructions = match Stackifier::new(&bodies, &exits) {
+        Some(mut stackifier) => {
+            stackifier.tree(0);
+            stackifier.instructions
+        }
+        None => {
+            locals.push(DISPATCH.to_string());
+            dispatch(&bodies, &exits)
+        }
+    };
+    if !matches!(
+        instructions.last(),
+        Some(Instruction::Return | Instruction::Br(_) | Instruction::Unreachable)
+    ) {
+        instructions.push(Instruction::Unreachable);
+    }
+    Function {
+        name: function.name,
+        locals,
+        instructons: instructions,
+    }
+}
+
+fn push_value(instructions: &mut Vec<Instruction>, value: &irc::Value) {
+    instructions.push(match value {
+        irc::Value::Constant(value) => Instruction::I32Const(*value),
+        irc::Value::Var(name) => Instruction::LocalGet(name.clone()),
+    });
+}
+
+fn gen_instruction(instructions: &mut Vec<Instruction>, instruction: irc::Instruction) {
+    let dst = match instruction {
+        irc::Instruction::Unary { operator, src, dst } => {
+            match operator {
+                irc::UnaryOp::Negate => {
+                    instructions.push(Instruction::I32Const(0));
+                    push_value(instructions, &src);
+                    instructions.push(Instruction::Binary(BinaryOp::Sub));
+                }
+                irc::UnaryOp::Complement => {
+                    push_value(instructions, &src);
+                    instructions.push(Instruction::I32Const(-1));
+                    instructions.push(Instruction::Binary(BinaryOp::Xor));
+                }
+                irc::UnaryOp::Not => {
+                    push_value(instructions, &src);
+                    instructions.push(Instruction::Eqz);
+                }
+            }
+            dst
+        }
+        irc::Instruction::Binary {
+            operator,
+            src1,
+            src2,
+            dst,
+        } => {
+            push_value(instructions, &src1);
+            push_value(instructions, &src2);
+            instructions.push(Instruction::Binary(BinaryOp::from_operator(operator)));
+            dst
+        }
+        irc::Instruction::Copy { src, dst } => {
+            push_value(instructions, &src);
+            dst
+        }
+        irc::Instruction::Select {
+            condition,
+            src1,
+            src2,
+            dst,
+        } => {
+            push_value(instructions, &src1);
+            push_value(instructions, &src2);
+            push_value(instructions, &condition);
+            instructions.push(Instruction::Select);
+            dst
+        }
+        irc::Instruction::Ret(_)
+        | irc::Instruction::Jump { .. }
+        | irc::Instruction::JumpIfZero { .. }
+        | irc::Instruction::JumpIfNotZero { .. } => unreachable!("ends a basic block"),
+        irc::Instruction::Label(_)
+        | irc::Instruction::Line(_)
+        | irc::Instruction::Variable { .. } => return,
+    };
+    instructions.push(Instruction::LocalSet(dst));
+}
+
+fn block_label(block: usize) -> String {
+    format!("block.{block}")
+}
+
+fn loop_label(block: usize) -> String {
+    format!("loop.{block}")
+}
+
+/// Turns a reducible control flow graph into nested blocks and loops,
+/// following "Beyond Relooper" (Ramsey, 2022).
+///
+/// Blocks are laid out in reverse postorder along the dominator tree. A
+/// backward edge continues the loop around its target, a forward edge to a
+/// block with several forward predecessors leaves the `block` that its
+/// target follows, and any other edge places the target inline.
+struct Stackifier<'a> {
+    bodies: &'a [Vec<Instruction>],
+    exits: &'a [Exit],
+    /// Position of each block in reverse postorder, `None` when unreachable.
+    order: Vec<Option<usize>>,
+    /// Children in the dominator tree.
+    children: Vec<Vec<usize>>,
+    merge: Vec<bool>,
+    loop_header: Vec<bool>,
+    instructions: Vec<Instruction>,
+}
+
+impl<'a> Stackifier<'a> {
+    /// Returns `None` if the graph is irreducible.
+    fn new(bodies: &'a [Vec<Instruction>], exits: &'a [Exit]) -> Option<Self> {
+        let count = exits.len();
+        let successors: Vec<Vec<usize>> = exits.iter().map(Exit::successors).collect();
+
+        let mut postorder = Vec::new();
+        let mut visited = vec![false; count];
+        let mut stack = vec![(0, 0)];
+        visited[0] = true;
+        while let Some((block, next)) = stack.pop() {
+            if let Some(&successor) = successors[block].get(next) {
+                stack.push((block, next + 1));
+                if !visited[successor] {
+                    visited[successor] = true;
+                    stack.push((successor, 0));
+                }
+            } else {
+                postorder.push(block);
+            }
+        }
+        let reverse_postorder: Vec<usize> = postorder.into_iter().rev().collect();
+        let mut order = vec![None; count];
+        for (position, &block) in reverse_postorder.iter().enumerate() {
+            order[block] = Some(position);
+        }
+
+        let mut predecessors = vec![Vec::new(); count];
+        for &block in &reverse_postorder {
+            for &successor in &successors[block] {
+                predecessors[successor].push(block);
+            }
+        }
+
+        // "A Simple, Fast Dominance Algorithm" (Cooper, Harvey and Kennedy).
+        let mut idom: Vec<Option<usize>> = vec![None; count];
+        idom[0] = Some(0);
+        let intersect = |idom: &[Option<usize>], mut a: usize, mut b: usize| {
+            while a != b {
+                while order[a] > order[b] {
+                    a = idom[a].expect("processed");
+                }
+                while order[b] > order[a] {
+                    b = idom[b].expect("processed");
+                }
+            }
+            a
+        };
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for &block in &reverse_postorder[1..] {
+                let new_idom = predecessors[block]
+                    .iter()
+                    .filter(|&&predecessor| idom[predecessor].is_some())
+                    .copied()
+                    .reduce(|a, b| intersect(&idom, a, b));
+                if new_idom != idom[block] {
+                    idom[block] = new_idom;
+                    changed = true;
+                }
+            }
+        }
+        let dominates = |a: usize, mut b: usize| loop {
+            if a == b {
+                return true;
+            }
+            if b == 0 {
+                return false;
+            }
+            b = idom[b].expect("reachable");
+        };
+
+        let mut merge = vec![false; count];
+        let mut loop_header = vec![false; count];
+        let mut forward = vec![0; count];
+        for &block in &reverse_postorder {
+            for &successor in &successors[block] {
+                if order[successor] <= order[block] {
+                    if !dominates(successor, block) {
+                        return None;
+                    }
+                    loop_header[successor] = true;
+                } else {
+                    forward[successor] += 1;
+                    merge[successor] = forward[successor] > 1;
+                }
+            }
+        }
+        let mut children = vec![Vec::new(); count];
+        for &block in &reverse_postorder[1..] {
+            children[idom[block].expect("reachable")].push(block);
+        }
+        Some(Self {
+            bodies,
+            exits,
+            order,
+            children,
+            merge,
+            loop_header,
+            instructions: Vec::new(),
+        })
+    }
+
+    fn tree(&mut self, block: usize) {
+        let mut merges: Vec<usize> = self.children[block]
+            .iter()
+            .copied()
+            .filter(|&child| self.merge[child])
+            .collect();
+        merges.sort_by_key(|&child| std::cmp::Reverse(self.order[child]));
+        if self.loop_header[block] {
+            self.instructions.push(Instruction::Loop(loop_label(block)));
+            self.within(block, &merges);
+            self.instructions.push(Instruction::End);
+        } else {
+            self.within(block, &merges);
+        }
+    }
+
+    /// Emits `block` followed by the merge blocks it dominates, the last one
+    /// in reverse postorder outermost.
+    fn within(&mut self, block: usize, merges: &[usize]) {
+        match merges.split_first() {
+            Some((&merge, rest)) => {
+                self.instructions
+                    .push(Instruction::Block(block_label(merge)));
+                self.within(block, rest);
+                self.instructions.push(Instruction::End);
+                self.tree(merge);
+            }
+            None => {
+                self.instructions.extend_from_slice(&self.bodies[block]);
+                self.exit(block);
+            }
+        }
+    }
+
+    fn exit(&mut self, block: usize) {
+        match &self.exits[block] {
+            Exit::Return(value) => {
+                push_value(&mut self.instructions, value);
+                self.instructions.push(Instruction::Return);
+            }
+            Exit::Jump(target) => self.branch(block, *target),
+            Exit::Branch {
+                condition,
+                non_zero,
+                zero,
+            } => {
+                push_value(&mut self.instructions, condition);
+                if let Some(label) = self.branch_label(block, *zero) {
+                    self.instructions.push(Instruction::Eqz);
+                    self.instructions.push(Instruction::BrIf(label));
+                    self.branch(block, *non_zero);
+                } else if let Some(label) = self.branch_label(block, *non_zero) {
+                    self.instructions.push(Instruction::BrIf(label));
+                    self.branch(block, *zero);
+                } else {
+                    self.instructions.push(Instruction::If);
+                    self.branch(block, *non_zero);
+                    self.instructions.push(Instruction::Else);
+                    self.branch(block, *zero);
+                    self.instructions.push(Instruction::End);
+                }
+            }
+        }
+    }
+
+    /// The label a branch from `from` to `to` breaks to, or `None` if `to`
+    /// is placed inline.
+    fn branch_label(&self, from: usize, to: Option<usize>) -> Option<String> {
+        let to = to?;
+        if self.order[to] <= self.order[from] {
+            Some(loop_label(to))
+        } else if self.merge[to] {
+            Some(block_label(to))
+        } else {
+            None
+        }
+    }
+
+    fn branch(&mut self, from: usize, to: Option<usize>) {
+        match (self.branch_label(from, to), to) {
+            (Some(label), _) => self.instructions.push(Instruction::Br(label)),
+            (None, Some(to)) => self.tree(to),
+            (None, None) => self.instructions.push(Instruction::Unreachable),
+        }
+    }
+}
+
+/// Runs the blocks from a loop around a `br_table` on the next block,
+/// which works for any control flow.
+fn dispatch(bodies: &[Vec<Instruction>], exits: &[Exit]) -> Vec<Instruction> {
+    let count = exits.len();
+    let jump = |instructions: &mut Vec<Instruction>, target: Option<usize>| {
+        instructions.push(Instruction::I32Const(target.unwrap_or(count) as i32));
+    };
+    let mut instructions = vec![Instruction::Loop(DISPATCH.to_string())];
+    instructions.push(Instruction::Block("invalid".to_string()));
+    for block in (0..count).rev() {
+        instructions.push(Instruction::Block(block_label(block)));
+    }
+    instructions.push(Instruction::LocalGet(DISPATCH.to_string()));
+    instructions.push(Instruction::BrTable {
+        targets: (0..count).map(block_label).collect(),
+        default: "invalid".to_string(),
+    });
+    for (body, exit) in bodies.iter().zip(exits) {
+        instructions.push(Instruction::End);
+        instructions.extend_from_slice(body);
+        match exit {
+            Exit::Return(value) => {
+                push_value(&mut instructions, value);
+                instructions.push(Instruction::Return);
+                continue;
+            }
+            Exit::Jump(target) => jump(&mut instructions, *target),
+            Exit::Branch {
+                condition,
+                non_zero,
+                zero,
+            } => {
+                jump(&mut instructions, *non_zero);
+                jump(&mut instructions, *zero);
+                push_value(&mut instructions, condition);
+                instructions.push(Instruction::Select);
+            }
+        }
+        instructions.push(Instruction::LocalSet(DISPATCH.to_string()));
+        instructions.push(Instruction::Br(DISPATCH.to_string()));
+    }
+    instructions.push(Instruction::End);
+    instructions.push(Instruction::Unreachable);
+    instructions.push(Instruction::End);
+    instructions
+}
+
+/// Writes the program as a module in the text format, exporting each
+/// function under its own name.
+pub fn emit_program(mut out: impl Write, program: &Program) -> io::Result<()> {
+    match program {
+        Program::Function(function) => {
+            writeln!(out, "(module")?;
+            emit_function(&mut out, function)?;
+            writeln!(out, ")")?;
+        }
+    }
+    out.flush()
+}
+
+fn emit_function(out: &mut impl Write, function: &Function) -> io::Result<()> {
+    writeln!(
+        out,
+        "  (func ${name} (export \"{name}\") (result i32)",
+        name = function.name
+    )?;
+    for local in &function.locals {
+        writeln!(out, "    (local ${local} i32)")?;
+    }
+    let mut depth = 2;
+    for ins in &function.instructons {
+        if matches!(ins, Instruction::End | Instruction::Else) {
+            depth -= 1;
+        }
+        write!(out, "{:indent$}", "", indent = depth * 2)?;
+        match ins {
+            Instruction::I32Const(value) => writeln!(out, "i32.const {value}")?,
+            Instruction::LocalGet(name) => writeln!(out, "local.get ${name}")?,
+            Instruction::LocalSet(name) => writeln!(out, "local.set ${name}")?,
+            Instruction::Eqz => writeln!(out, "i32.eqz")?,
+            Instruction::Binary(operator) => writeln!(out, "{}", operator.name())?,
+            Instruction::Select => writeln!(out, "select")?,
+            Instruction::Block(label) => writeln!(out, "block ${label}")?,
+            Instruction::Loop(label) => writeln!(out, "loop ${label}")?,
+            Instruction::If => writeln!(out, "if")?,
+            Instruction::Else => writeln!(out, "else")?,
+            Instruction::End => writeln!(out, "end")?,
+            Instruction::Br(label) => writeln!(out, "br ${label}")?,
+            Instruction::BrIf(label) => writeln!(out, "br_if ${label}")?,
+            Instruction::BrTable { targets, default } => {
+                write!(out, "br_table")?;
+                for target in targets {
+                    write!(out, " ${target}")?;
+                }
+                writeln!(out, " ${default}")?;
+            }
+            Instruction::Return => writeln!(out, "return")?,
+            Instruction::Unreachable => writeln!(out, "unreachable")?,
+        }
+        if matches!(
+            ins,
+            Instruction::Block(_) | Instruction::Loop(_) | Instruction::If | Instruction::Else
+        ) {
+            depth += 1;
+        }
+    }
+    writeln!(out, "  )")
+}
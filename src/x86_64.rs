@@ -0,0 +1,86 @@
+use std::io::{self, Write};
+
+use crate::{
+    asm_ast, code_emission, code_gen, elf, irc, machine_code,
+    pass_manager::PassManager,
+    target::{Abi, Assembly, DataLayout, RegisterInfo, Target},
+};
+
+/// x86-64 Linux with the System V ABI, assembled by gas or the built-in
+/// assembler.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct X86_64;
+
+impl Target for X86_64 {
+    fn name(&self) -> &'static str {
+        "x86_64-linux-gnu"
+    }
+
+    fn architecture(&self) -> &'static str {
+        "x86_64"
+    }
+
+    fn data_layout(&self) -> DataLayout {
+        DataLayout {
+            int_size: 4,
+            pointer_size: 8,
+            stack_alignment: 16,
+        }
+    }
+
+    fn abi(&self) -> Abi {
+        Abi {
+            return_register: Some("eax"),
+            argument_registers: &["rdi", "rsi", "rdx", "rcx", "r8", "r9"],
+            callee_saved: &["rbx", "rbp", "r12", "r13", "r14", "r15"],
+            red_zone: 128,
+        }
+    }
+
+    fn registers(&self) -> RegisterInfo {
+        RegisterInfo {
+            allocatable: &[
+                "rax", "rcx", "rdx", "rsi", "rdi", "r8", "r9", "rbx", "r12", "r13", "r14", "r15",
+            ],
+            scratch: &["r10", "r11"],
+            stack_pointer: Some("rsp"),
+            frame_pointer: Some("rbp"),
+        }
+    }
+
+    fn gcc(&self) -> Option<&'static str> {
+        Some("gcc")
+    }
+
+    fn has_assembler(&self) -> bool {
+        true
+    }
+
+    fn has_emission_options(&self) -> bool {
+        true
+    }
+
+    fn select_instructions<'a>(
+        &self,
+        program: irc::Program<'a>,
+        pass_manager: &mut PassManager,
+        verbose: bool,
+    ) -> Box<dyn Assembly + 'a> {
+        let mut program = code_gen::gen_program(program, verbose);
+        pass_manager.run_asm_before_frame(&mut program);
+        let stack_allocation = code_gen::replace_pseudo(&mut program);
+        code_gen::fix_instructions(&mut program, stack_allocation);
+        pass_manager.run_asm(&mut program);
+        Box::new(program)
+    }
+}
+
+impl Assembly for asm_ast::Program<'_> {
+    fn emit(&self, out: &mut dyn Write, options: code_emission::Options) -> io::Result<()> {
+        code_emission::emit_program(out, self, options)
+    }
+
+    fn encode<'a>(&'a self, file: &'a str) -> Option<elf::Object<'a>> {
+        Some(machine_code::encode_program(self, file))
+    }
+}
@@ -0,0 +1,82 @@
+mod common;
+
+use comp::{aarch64::Aarch64, session::Options};
+
+fn options(level: u8) -> Options {
+    Options {
+        target: &Aarch64,
+        ..Options::for_level(level)
+    }
+}
+
+#[test]
+fn matches_golden_output() {
+    for program in common::programs() {
+        common::check_golden(
+            &format!("aarch64/{}.s", program.name),
+            &common::assembly(&program, options(2)),
+        );
+    }
+}
+
+#[test]
+fn assembles_with_llvm_mc() {
+    if !common::has_tool("llvm-mc") {
+        return;
+    }
+    for program in common::programs() {
+        for level in 0..=2 {
+            common::run_tool_ok(
+                "llvm-mc",
+                &[
+                    "-triple=aarch64-linux-gnu",
+                    "-filetype=obj",
+                    "--fatal-warnings",
+                    "-o",
+                    "/dev/null",
+                ],
+                Some(&common::assembly(&program, options(level))),
+            );
+        }
+    }
+}
+
+/// A program whose `if (condition)` jumps over `statements` additions.
+fn long_jump(condition: &str, statements: usize) -> common::Program {
+    let body = "        x = x + 1;\n".repeat(statements);
+    common::Program {
+        name: format!("long_jump_{statements}"),
+        source: format!(
+            "int main(void) {{\n    int a = 0;\n    int x = 0;\n    if ({condition}) {{\n{body}    }}\n    return x;\n}}\n"
+        ),
+        expected: 0,
+    }
+}
+
+#[test]
+fn relaxes_long_branches() {
+    if !common::has_tool("llvm-mc") {
+        return;
+    }
+    // A `cbz` and a `b.cond` past the 1 MiB they reach, five instructions
+    // per addition at -O0.
+    for condition in ["a", "a > 1"] {
+        let program = long_jump(condition, 60000);
+        let assembly = common::assembly(&program, options(0));
+        assert!(
+            assembly.contains(".L.branch."),
+            "`if ({condition})` wasn't relaxed"
+        );
+        common::run_tool_ok(
+            "llvm-mc",
+            &[
+                "-triple=aarch64-linux-gnu",
+                "-filetype=obj",
+                "--fatal-warnings",
+                "-o",
+                "/dev/null",
+            ],
+            Some(&assembly),
+        );
+    }
+}
@@ -0,0 +1,118 @@
+mod common;
+
+use std::{
+    collections::{HashMap, HashSet},
+    fs,
+};
+
+use comp::{
+    asm_ast::{self, Operand},
+    code_gen, irc, register_allocation,
+    session::{Options, Session, Stage},
+};
+
+#[test]
+fn generated_only_when_asked_for() {
+    for program in common::programs() {
+        let session = Session::new(program.source.as_str(), Options::for_level(1));
+        let output = session.compile(Stage::Assembly);
+        common::check(&output, &program.name);
+        let irc::Program::Function(function) = output.irc.as_ref().expect("the irc is kept");
+        assert!(
+            !function.instructons.iter().any(|ins| matches!(
+                ins,
+                irc::Instruction::Line(_) | irc::Instruction::Variable { .. }
+            )),
+            "{}",
+            program.name
+        );
+        let assembly = output.assembly.expect("the assembly stage gives assembly");
+        assert!(!assembly.contains('#'), "{}:\n{assembly}", program.name);
+    }
+}
+
+#[test]
+fn annotates_only_lines_of_the_main_file() {
+    let directory = common::temp_dir("annotations");
+    let header = directory.join("body.h");
+    fs::write(&header, "/* the code\n   is on line 3 */\nb = b * 3;\n").unwrap();
+    let source = "int main(void) {\n    int b = 2;\n#include \"body.h\"\n    return b;\n}\n";
+    let path = directory.join("main.c");
+    fs::write(&path, source).unwrap();
+    let path = path.to_str().expect("the temporary directory is UTF-8");
+    let options = Options {
+        preprocess: true,
+        verbose_asm: true,
+        file_name: "main.c".to_string(),
+        ..Options::default()
+    };
+    let session = Session::open(path, options).expect("the source can be read");
+    let output = session.compile(Stage::Assembly);
+    common::check(&output, "main.c");
+    let assembly = output.assembly.expect("the assembly stage gives assembly");
+    let lines: Vec<_> = assembly
+        .lines()
+        .filter_map(|line| line.trim().strip_prefix("# "))
+        .filter(|line| line.starts_with(|c: char| c.is_ascii_digit()))
+        .collect();
+    assert_eq!(
+        lines,
+        ["1: int main(void) {", "2: int b = 2;", "4: return b;"],
+        "{assembly}"
+    );
+    // The statement from the header is still there, annotated with its irc.
+    assert!(assembly.contains("#   temp.1 = b.0 * 3"), "{assembly}");
+}
+
+#[test]
+fn described_variables_keep_a_stack_slot_of_their_own() {
+    for program in common::programs() {
+        for level in 0..=2 {
+            let options = Options {
+                debug_directory: Some("/tmp".to_string()),
+                ..Options::for_level(level)
+            };
+            let session = Session::new(program.source.as_str(), options);
+            let output = session.compile(Stage::Irc);
+            common::check(&output, &program.name);
+            let mut assembly = code_gen::gen_program(output.irc.expect("the irc is kept"), false);
+            register_allocation::allocate_registers(&mut assembly);
+            let before = assembly.clone();
+            code_gen::replace_pseudo(&mut assembly);
+            let (asm_ast::Program::Function(before), asm_ast::Program::Function(after)) =
+                (before, &assembly);
+            // What each stack slot was a pseudo of.
+            let mut slots: HashMap<Operand, HashSet<Operand>> = HashMap::new();
+            for (mut before, mut after) in before
+                .instructons
+                .into_iter()
+                .zip(after.instructons.clone())
+            {
+                for (pseudo, slot) in before.operands_mut().into_iter().zip(after.operands_mut()) {
+                    if let Operand::Pseudo(_) = pseudo {
+                        slots
+                            .entry(slot.clone())
+                            .or_default()
+                            .insert(pseudo.clone());
+                    }
+                }
+            }
+            let asm_ast::Program::Function(function) = &assembly;
+            for ins in &function.instructons {
+                if let asm_ast::Instruction::Variable { name, location, .. } = ins {
+                    assert!(
+                        matches!(location, Operand::Stack(_) | Operand::Pseudo(_)),
+                        "{name} of {} at -O{level} is in {location:?}",
+                        program.name
+                    );
+                    assert_eq!(
+                        slots.get(location).map_or(1, HashSet::len),
+                        1,
+                        "{name} of {} at -O{level} shares {location:?}",
+                        program.name
+                    );
+                }
+            }
+        }
+    }
+}
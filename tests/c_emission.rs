@@ -0,0 +1,55 @@
+mod common;
+
+use std::fs;
+
+use common::Program;
+use comp::{
+    c_emission, irc,
+    session::{Options, Session, Stage},
+};
+
+fn emit(program: &Program, level: u8) -> String {
+    let session = Session::new(program.source.as_str(), Options::for_level(level));
+    let output = session.compile(Stage::Irc);
+    common::check(&output, &program.name);
+    let irc: &irc::Program = output.irc.as_ref().expect("the irc stage gives irc");
+    let mut text = Vec::new();
+    c_emission::emit_program(&mut text, irc).expect("writing to a Vec doesn't fail");
+    String::from_utf8(text).expect("the C is UTF-8")
+}
+
+#[test]
+fn compiles_with_cc_and_runs() {
+    if !common::has_tool("cc") {
+        return;
+    }
+    let directory = common::temp_dir("c-emission");
+    for program in common::programs() {
+        for level in 0..=2 {
+            let source = directory.join(format!("{}-{level}.c", program.name));
+            let executable = source.with_extension("");
+            fs::write(&source, emit(&program, level)).unwrap();
+            common::run_tool_ok(
+                "cc",
+                &[
+                    "-std=c99".as_ref(),
+                    "-Werror".as_ref(),
+                    source.as_os_str(),
+                    "-o".as_ref(),
+                    executable.as_os_str(),
+                ],
+                None,
+            );
+            let executable = executable
+                .to_str()
+                .expect("the temporary directory is UTF-8");
+            let output = common::run_tool(executable, &[] as &[&str], None);
+            assert_eq!(
+                output.status.code(),
+                Some(program.expected as u8 as i32),
+                "{} at -O{level}",
+                program.name
+            );
+        }
+    }
+}
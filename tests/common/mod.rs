@@ -0,0 +1,162 @@
+//! Helpers shared by the integration tests.
+#![allow(dead_code)]
+
+use std::{
+    env,
+    ffi::OsStr,
+    fs,
+    io::Write,
+    path::{Path, PathBuf},
+    process::{self, Command, Stdio},
+    thread,
+};
+
+use comp::session::{Options, Output, Session, Stage};
+
+/// A program from `tests/programs`, whose first line is `// expect: N`
+/// with the value `main` returns.
+pub struct Program {
+    pub name: String,
+    /// The source without the expectation, as the lexer doesn't read
+    /// comments.
+    pub source: String,
+    pub expected: i32,
+}
+
+pub fn programs() -> Vec<Program> {
+    let directory = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/programs");
+    let mut paths: Vec<_> = fs::read_dir(&directory)
+        .expect("tests/programs exists")
+        .map(|entry| entry.expect("tests/programs is readable").path())
+        .filter(|path| path.extension() == Some(OsStr::new("c")))
+        .collect();
+    paths.sort();
+    paths
+        .iter()
+        .map(|path| {
+            let text = fs::read_to_string(path).expect("the program is readable");
+            let (header, source) = text.split_once('\n').unwrap_or_default();
+            let expected = header
+                .strip_prefix("// expect: ")
+                .and_then(|value| value.trim().parse().ok())
+                .unwrap_or_else(|| panic!("{} has no `// expect: N` line", path.display()));
+            Program {
+                name: path.file_stem().unwrap().to_string_lossy().into_owned(),
+                source: source.to_string(),
+                expected,
+            }
+        })
+        .collect()
+}
+
+/// Panics with the diagnostics if the compilation failed.
+pub fn check(output: &Output, name: &str) {
+    if output.failed() {
+        let diagnostics: Vec<_> = output.diagnostics.iter().map(|d| d.to_string()).collect();
+        panic!("{name} failed to compile:\n{}", diagnostics.join("\n"));
+    }
+}
+
+pub fn assembly(program: &Program, options: Options) -> String {
+    let session = Session::new(program.source.as_str(), options);
+    let output = session.compile(Stage::Assembly);
+    check(&output, &program.name);
+    output.assembly.expect("the assembly stage gives assembly")
+}
+
+pub fn object(program: &Program, options: Options) -> Vec<u8> {
+    let session = Session::new(program.source.as_str(), options);
+    let output = session.compile(Stage::Object);
+    check(&output, &program.name);
+    output.object.expect("the object stage gives an object")
+}
+
+/// Runs the program in memory, returning what `main` returned.
+pub fn jit(program: &Program, options: Options) -> i32 {
+    let session = Session::new(program.source.as_str(), options);
+    let output = session.compile(Stage::Run);
+    check(&output, &program.name);
+    output.status.expect("the run stage gives a status")
+}
+
+/// Whether `tool` can be run. Tests that need it print why they're skipped
+/// and pass when it isn't installed.
+pub fn has_tool(tool: &str) -> bool {
+    let found = Command::new(tool)
+        .arg("--version")
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .is_ok_and(|status| status.success());
+    if !found {
+        eprintln!("skipped, {tool} isn't installed");
+    }
+    found
+}
+
+/// Runs `tool` with `stdin` piped in when it's given.
+pub fn run_tool<S: AsRef<OsStr>>(tool: &str, args: &[S], stdin: Option<&str>) -> process::Output {
+    let mut child = Command::new(tool)
+        .args(args)
+        .stdin(if stdin.is_some() {
+            Stdio::piped()
+        } else {
+            Stdio::null()
+        })
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap_or_else(|e| panic!("couldn't run {tool}: {e}"));
+    let pipe = child.stdin.take();
+    thread::scope(|scope| {
+        if let (Some(mut pipe), Some(stdin)) = (pipe, stdin) {
+            scope.spawn(move || pipe.write_all(stdin.as_bytes()));
+        }
+        child.wait_with_output()
+    })
+    .unwrap_or_else(|e| panic!("couldn't run {tool}: {e}"))
+}
+
+/// Runs `tool`, panicking with its stderr unless it succeeds, and returns
+/// its stdout.
+pub fn run_tool_ok<S: AsRef<OsStr>>(tool: &str, args: &[S], stdin: Option<&str>) -> String {
+    let output = run_tool(tool, args, stdin);
+    assert!(
+        output.status.success(),
+        "{tool} failed:\n{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    String::from_utf8_lossy(&output.stdout).into_owned()
+}
+
+/// A fresh directory for the files of the test `name`.
+pub fn temp_dir(name: &str) -> PathBuf {
+    let directory = env::temp_dir().join(format!("comp-test-{}-{name}", process::id()));
+    let _ = fs::remove_dir_all(&directory);
+    fs::create_dir_all(&directory).expect("the temporary directory can be created");
+    directory
+}
+
+/// Compares `actual` with the golden file at `tests/golden/{path}`, or
+/// rewrites the file when `UPDATE_GOLDEN` is set.
+pub fn check_golden(path: &str, actual: &str) {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/golden")
+        .join(path);
+    if env::var_os("UPDATE_GOLDEN").is_some() {
+        fs::create_dir_all(path.parent().unwrap()).expect("the golden directory can be created");
+        fs::write(&path, actual).expect("the golden file can be written");
+        return;
+    }
+    let expected = fs::read_to_string(&path).unwrap_or_else(|e| {
+        panic!(
+            "couldn't read {}, run with UPDATE_GOLDEN=1 to create it: {e}",
+            path.display()
+        )
+    });
+    assert!(
+        expected == actual,
+        "{} is out of date, run with UPDATE_GOLDEN=1 if the change is intended:\n{actual}",
+        path.display()
+    );
+}
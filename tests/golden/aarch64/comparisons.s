@@ -0,0 +1,88 @@
+    .global main
+    .type main, %function
+    .p2align 2
+main:
+    stp x29, x30, [sp, #-16]!
+    mov x29, sp
+    sub sp, sp, #80
+    mov w11, #0
+    str w11, [sp, #0]
+    mov w11, #0
+    str w11, [sp, #4]
+    mov w11, #0
+    str w11, [sp, #8]
+    mov w11, #0
+    str w11, [sp, #12]
+.Lloop:
+    ldr w9, [sp, #0]
+    cmp w9, #4
+    cset w11, lt
+    str w11, [sp, #16]
+    ldr w9, [sp, #4]
+    ldr w10, [sp, #16]
+    add w11, w9, w10
+    str w11, [sp, #20]
+    ldr w11, [sp, #20]
+    str w11, [sp, #4]
+    ldr w9, [sp, #0]
+    cmp w9, #4
+    cset w11, eq
+    str w11, [sp, #24]
+    ldr w9, [sp, #8]
+    ldr w10, [sp, #24]
+    add w11, w9, w10
+    str w11, [sp, #28]
+    ldr w11, [sp, #28]
+    str w11, [sp, #8]
+    ldr w9, [sp, #0]
+    cmp w9, #4
+    cset w11, gt
+    str w11, [sp, #32]
+    ldr w9, [sp, #32]
+    mov w10, #3
+    mul w11, w9, w10
+    str w11, [sp, #36]
+    ldr w9, [sp, #12]
+    ldr w10, [sp, #36]
+    add w11, w9, w10
+    str w11, [sp, #40]
+    ldr w11, [sp, #40]
+    str w11, [sp, #12]
+    ldr w9, [sp, #0]
+    add w11, w9, #1
+    str w11, [sp, #44]
+    ldr w11, [sp, #44]
+    str w11, [sp, #0]
+    ldr w9, [sp, #44]
+    cmp w9, #10
+    b.ge .Lif_end12
+    b .Lloop
+.Lif_end12:
+    ldr w9, [sp, #20]
+    mov w10, #100
+    mul w11, w9, w10
+    str w11, [sp, #48]
+    ldr w9, [sp, #28]
+    mov w10, #10
+    mul w11, w9, w10
+    str w11, [sp, #52]
+    ldr w9, [sp, #48]
+    ldr w10, [sp, #52]
+    add w11, w9, w10
+    str w11, [sp, #56]
+    ldr w9, [sp, #56]
+    ldr w10, [sp, #40]
+    add w11, w9, w10
+    str w11, [sp, #60]
+    ldr w9, [sp, #60]
+    mov w10, #256
+    sdiv w12, w9, w10
+    msub w11, w12, w10, w9
+    str w11, [sp, #64]
+    ldr w0, [sp, #64]
+    mov sp, x29
+    ldp x29, x30, [sp], #16
+    ret
+    .size main, .-main
+
+.section .note.GNU-stack,"",@progbits
@@ -0,0 +1,13 @@
+    .global main
+    .type main, %function
+    .p2align 2
+main:
+    stp x29, x30, [sp, #-16]!
+    mov x29, sp
+    mov w0, #107
+    mov sp, x29
+    ldp x29, x30, [sp], #16
+    ret
+    .size main, .-main
+
+.section .note.GNU-stack,"",@progbits
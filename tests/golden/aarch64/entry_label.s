@@ -0,0 +1,27 @@
+    .global main
+    .type main, %function
+    .p2align 2
+main:
+    stp x29, x30, [sp, #-16]!
+    mov x29, sp
+    sub sp, sp, #16
+    mov w11, #0
+    str w11, [sp, #0]
+.Lentry:
+    ldr w9, [sp, #0]
+    add w11, w9, #1
+    str w11, [sp, #4]
+    ldr w11, [sp, #4]
+    str w11, [sp, #0]
+    ldr w9, [sp, #4]
+    cmp w9, #3
+    b.ge .Lif_end2
+    b .Lentry
+.Lif_end2:
+    ldr w0, [sp, #4]
+    mov sp, x29
+    ldp x29, x30, [sp], #16
+    ret
+    .size main, .-main
+
+.section .note.GNU-stack,"",@progbits
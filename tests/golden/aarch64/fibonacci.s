@@ -0,0 +1,46 @@
+    .global main
+    .type main, %function
+    .p2align 2
+main:
+    stp x29, x30, [sp, #-16]!
+    mov x29, sp
+    sub sp, sp, #32
+    mov w11, #0
+    str w11, [sp, #0]
+    mov w11, #1
+    str w11, [sp, #4]
+    mov w11, #0
+    str w11, [sp, #8]
+.Lfib:
+    ldr w9, [sp, #8]
+    cmp w9, #20
+    b.lt .Lif_end4
+    b .Ldone
+.Lif_end4:
+    ldr w9, [sp, #0]
+    ldr w10, [sp, #4]
+    add w11, w9, w10
+    str w11, [sp, #12]
+    ldr w11, [sp, #4]
+    str w11, [sp, #0]
+    ldr w11, [sp, #12]
+    str w11, [sp, #4]
+    ldr w9, [sp, #8]
+    add w11, w9, #1
+    str w11, [sp, #16]
+    ldr w11, [sp, #16]
+    str w11, [sp, #8]
+    b .Lfib
+.Ldone:
+    ldr w9, [sp, #0]
+    mov w10, #251
+    sdiv w12, w9, w10
+    msub w11, w12, w10, w9
+    str w11, [sp, #20]
+    ldr w0, [sp, #20]
+    mov sp, x29
+    ldp x29, x30, [sp], #16
+    ret
+    .size main, .-main
+
+.section .note.GNU-stack,"",@progbits
@@ -0,0 +1,55 @@
+    .global main
+    .type main, %function
+    .p2align 2
+main:
+    stp x29, x30, [sp, #-16]!
+    mov x29, sp
+    sub sp, sp, #32
+    mov w11, #0
+    str w11, [sp, #0]
+    mov w11, #3
+    str w11, [sp, #4]
+    b .Lb
+.La:
+    ldr w9, [sp, #8]
+    add w11, w9, #3
+    str w11, [sp, #12]
+    ldr w11, [sp, #12]
+    str w11, [sp, #0]
+    ldr w9, [sp, #12]
+    cmp w9, #40
+    b.le .Lif_end5
+    b .Lend
+.Lif_end5:
+.Lb:
+    ldr w9, [sp, #0]
+    mov w10, #2
+    mul w11, w9, w10
+    str w11, [sp, #16]
+    ldr w9, [sp, #16]
+    add w11, w9, #1
+    str w11, [sp, #8]
+    ldr w11, [sp, #8]
+    str w11, [sp, #0]
+    ldr w9, [sp, #8]
+    cmp w9, #30
+    b.ge .Lif_end9
+    b .La
+.Lif_end9:
+    ldr w9, [sp, #4]
+    sub w11, w9, #1
+    str w11, [sp, #20]
+    ldr w11, [sp, #20]
+    str w11, [sp, #4]
+    ldr w9, [sp, #20]
+    cbz w9, .Lif_end12
+    b .La
+.Lif_end12:
+.Lend:
+    ldr w0, [sp, #0]
+    mov sp, x29
+    ldp x29, x30, [sp], #16
+    ret
+    .size main, .-main
+
+.section .note.GNU-stack,"",@progbits
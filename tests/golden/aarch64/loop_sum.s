@@ -0,0 +1,42 @@
+    .global main
+    .type main, %function
+    .p2align 2
+main:
+    stp x29, x30, [sp, #-16]!
+    mov x29, sp
+    sub sp, sp, #32
+    mov w11, #0
+    str w11, [sp, #0]
+    mov w11, #0
+    str w11, [sp, #4]
+.Lloop:
+    ldr w9, [sp, #0]
+    cmp w9, #10
+    b.ge .Lif_end2
+    ldr w9, [sp, #0]
+    ldr w10, [sp, #0]
+    mul w11, w9, w10
+    str w11, [sp, #8]
+    ldr w9, [sp, #4]
+    ldr w10, [sp, #8]
+    add w11, w9, w10
+    str w11, [sp, #12]
+    ldr w11, [sp, #12]
+    str w11, [sp, #4]
+    ldr w9, [sp, #0]
+    add w11, w9, #1
+    str w11, [sp, #0]
+    b .Lloop
+.Lif_end2:
+    ldr w9, [sp, #4]
+    mov w10, #256
+    sdiv w12, w9, w10
+    msub w11, w12, w10, w9
+    str w11, [sp, #16]
+    ldr w0, [sp, #16]
+    mov sp, x29
+    ldp x29, x30, [sp], #16
+    ret
+    .size main, .-main
+
+.section .note.GNU-stack,"",@progbits
@@ -0,0 +1,64 @@
+    .global main
+    .type main, %function
+    .p2align 2
+main:
+    stp x29, x30, [sp, #-16]!
+    mov x29, sp
+    sub sp, sp, #48
+    mov w11, #0
+    str w11, [sp, #0]
+    mov w11, #1
+    str w11, [sp, #4]
+.Ltop:
+    mov w9, #10
+    ldr w10, [sp, #4]
+    mul w11, w9, w10
+    str w11, [sp, #8]
+    ldr w9, [sp, #0]
+    ldr w10, [sp, #8]
+    add w11, w9, w10
+    str w11, [sp, #12]
+    ldr w11, [sp, #12]
+    str w11, [sp, #0]
+    ldr w9, [sp, #4]
+    add w11, w9, #1
+    str w11, [sp, #16]
+    ldr w11, [sp, #16]
+    str w11, [sp, #4]
+    ldr w9, [sp, #16]
+    cmp w9, #5
+    b.ge .Lif_end9
+    b .Ltop
+.Lif_end9:
+    mov w11, #0
+    str w11, [sp, #20]
+    mov w11, #100
+    str w11, [sp, #24]
+.Lagain:
+    ldr w9, [sp, #24]
+    cmp w9, #0
+    b.le .Lif_end11
+    ldr w9, [sp, #24]
+    mov w10, #3
+    sdiv w11, w9, w10
+    str w11, [sp, #28]
+    ldr w11, [sp, #28]
+    str w11, [sp, #24]
+    ldr w9, [sp, #20]
+    add w11, w9, #1
+    str w11, [sp, #32]
+    ldr w11, [sp, #32]
+    str w11, [sp, #20]
+    b .Lagain
+.Lif_end11:
+    ldr w9, [sp, #12]
+    ldr w10, [sp, #20]
+    add w11, w9, w10
+    str w11, [sp, #36]
+    ldr w0, [sp, #36]
+    mov sp, x29
+    ldp x29, x30, [sp], #16
+    ret
+    .size main, .-main
+
+.section .note.GNU-stack,"",@progbits
@@ -0,0 +1,511 @@
+    .global main
+    .type main, %function
+    .p2align 2
+main:
+    stp x29, x30, [sp, #-16]!
+    mov x29, sp
+    sub sp, sp, #512
+    mov w11, #3
+    str w11, [sp, #0]
+    mov w11, #10
+    str w11, [sp, #4]
+    mov w11, #17
+    str w11, [sp, #8]
+    mov w11, #24
+    str w11, [sp, #12]
+    mov w11, #31
+    str w11, [sp, #16]
+    mov w11, #38
+    str w11, [sp, #20]
+    mov w11, #45
+    str w11, [sp, #24]
+    mov w11, #52
+    str w11, [sp, #28]
+    mov w11, #59
+    str w11, [sp, #32]
+    mov w11, #66
+    str w11, [sp, #36]
+    mov w11, #73
+    str w11, [sp, #40]
+    mov w11, #80
+    str w11, [sp, #44]
+    mov w11, #87
+    str w11, [sp, #48]
+    mov w11, #94
+    str w11, [sp, #52]
+    mov w11, #101
+    str w11, [sp, #56]
+    mov w11, #108
+    str w11, [sp, #60]
+    mov w11, #115
+    str w11, [sp, #64]
+    mov w11, #122
+    str w11, [sp, #68]
+    mov w11, #129
+    str w11, [sp, #72]
+    mov w11, #136
+    str w11, [sp, #76]
+    mov w11, #0
+    str w11, [sp, #80]
+.Lloop:
+    ldr w9, [sp, #80]
+    cmp w9, #3
+    b.ge .Lif_end22
+    ldr w9, [sp, #0]
+    mov w10, #3
+    mul w11, w9, w10
+    str w11, [sp, #84]
+    ldr w9, [sp, #84]
+    ldr w10, [sp, #4]
+    add w11, w9, w10
+    str w11, [sp, #88]
+    ldr w9, [sp, #88]
+    ldr w10, [sp, #80]
+    sub w11, w9, w10
+    str w11, [sp, #92]
+    ldr w11, [sp, #92]
+    str w11, [sp, #0]
+    ldr w9, [sp, #4]
+    mov w10, #3
+    mul w11, w9, w10
+    str w11, [sp, #96]
+    ldr w9, [sp, #96]
+    ldr w10, [sp, #8]
+    add w11, w9, w10
+    str w11, [sp, #100]
+    ldr w9, [sp, #100]
+    ldr w10, [sp, #80]
+    sub w11, w9, w10
+    str w11, [sp, #104]
+    ldr w11, [sp, #104]
+    str w11, [sp, #4]
+    ldr w9, [sp, #8]
+    mov w10, #3
+    mul w11, w9, w10
+    str w11, [sp, #108]
+    ldr w9, [sp, #108]
+    ldr w10, [sp, #12]
+    add w11, w9, w10
+    str w11, [sp, #112]
+    ldr w9, [sp, #112]
+    ldr w10, [sp, #80]
+    sub w11, w9, w10
+    str w11, [sp, #116]
+    ldr w11, [sp, #116]
+    str w11, [sp, #8]
+    ldr w9, [sp, #12]
+    mov w10, #3
+    mul w11, w9, w10
+    str w11, [sp, #120]
+    ldr w9, [sp, #120]
+    ldr w10, [sp, #16]
+    add w11, w9, w10
+    str w11, [sp, #124]
+    ldr w9, [sp, #124]
+    ldr w10, [sp, #80]
+    sub w11, w9, w10
+    str w11, [sp, #128]
+    ldr w11, [sp, #128]
+    str w11, [sp, #12]
+    ldr w9, [sp, #16]
+    mov w10, #3
+    mul w11, w9, w10
+    str w11, [sp, #132]
+    ldr w9, [sp, #132]
+    ldr w10, [sp, #20]
+    add w11, w9, w10
+    str w11, [sp, #136]
+    ldr w9, [sp, #136]
+    ldr w10, [sp, #80]
+    sub w11, w9, w10
+    str w11, [sp, #140]
+    ldr w11, [sp, #140]
+    str w11, [sp, #16]
+    ldr w9, [sp, #20]
+    mov w10, #3
+    mul w11, w9, w10
+    str w11, [sp, #144]
+    ldr w9, [sp, #144]
+    ldr w10, [sp, #24]
+    add w11, w9, w10
+    str w11, [sp, #148]
+    ldr w9, [sp, #148]
+    ldr w10, [sp, #80]
+    sub w11, w9, w10
+    str w11, [sp, #152]
+    ldr w11, [sp, #152]
+    str w11, [sp, #20]
+    ldr w9, [sp, #24]
+    mov w10, #3
+    mul w11, w9, w10
+    str w11, [sp, #156]
+    ldr w9, [sp, #156]
+    ldr w10, [sp, #28]
+    add w11, w9, w10
+    str w11, [sp, #160]
+    ldr w9, [sp, #160]
+    ldr w10, [sp, #80]
+    sub w11, w9, w10
+    str w11, [sp, #164]
+    ldr w11, [sp, #164]
+    str w11, [sp, #24]
+    ldr w9, [sp, #28]
+    mov w10, #3
+    mul w11, w9, w10
+    str w11, [sp, #168]
+    ldr w9, [sp, #168]
+    ldr w10, [sp, #32]
+    add w11, w9, w10
+    str w11, [sp, #172]
+    ldr w9, [sp, #172]
+    ldr w10, [sp, #80]
+    sub w11, w9, w10
+    str w11, [sp, #176]
+    ldr w11, [sp, #176]
+    str w11, [sp, #28]
+    ldr w9, [sp, #32]
+    mov w10, #3
+    mul w11, w9, w10
+    str w11, [sp, #180]
+    ldr w9, [sp, #180]
+    ldr w10, [sp, #36]
+    add w11, w9, w10
+    str w11, [sp, #184]
+    ldr w9, [sp, #184]
+    ldr w10, [sp, #80]
+    sub w11, w9, w10
+    str w11, [sp, #188]
+    ldr w11, [sp, #188]
+    str w11, [sp, #32]
+    ldr w9, [sp, #36]
+    mov w10, #3
+    mul w11, w9, w10
+    str w11, [sp, #192]
+    ldr w9, [sp, #192]
+    ldr w10, [sp, #40]
+    add w11, w9, w10
+    str w11, [sp, #196]
+    ldr w9, [sp, #196]
+    ldr w10, [sp, #80]
+    sub w11, w9, w10
+    str w11, [sp, #200]
+    ldr w11, [sp, #200]
+    str w11, [sp, #36]
+    ldr w9, [sp, #40]
+    mov w10, #3
+    mul w11, w9, w10
+    str w11, [sp, #204]
+    ldr w9, [sp, #204]
+    ldr w10, [sp, #44]
+    add w11, w9, w10
+    str w11, [sp, #208]
+    ldr w9, [sp, #208]
+    ldr w10, [sp, #80]
+    sub w11, w9, w10
+    str w11, [sp, #212]
+    ldr w11, [sp, #212]
+    str w11, [sp, #40]
+    ldr w9, [sp, #44]
+    mov w10, #3
+    mul w11, w9, w10
+    str w11, [sp, #216]
+    ldr w9, [sp, #216]
+    ldr w10, [sp, #48]
+    add w11, w9, w10
+    str w11, [sp, #220]
+    ldr w9, [sp, #220]
+    ldr w10, [sp, #80]
+    sub w11, w9, w10
+    str w11, [sp, #224]
+    ldr w11, [sp, #224]
+    str w11, [sp, #44]
+    ldr w9, [sp, #48]
+    mov w10, #3
+    mul w11, w9, w10
+    str w11, [sp, #228]
+    ldr w9, [sp, #228]
+    ldr w10, [sp, #52]
+    add w11, w9, w10
+    str w11, [sp, #232]
+    ldr w9, [sp, #232]
+    ldr w10, [sp, #80]
+    sub w11, w9, w10
+    str w11, [sp, #236]
+    ldr w11, [sp, #236]
+    str w11, [sp, #48]
+    ldr w9, [sp, #52]
+    mov w10, #3
+    mul w11, w9, w10
+    str w11, [sp, #240]
+    ldr w9, [sp, #240]
+    ldr w10, [sp, #56]
+    add w11, w9, w10
+    str w11, [sp, #244]
+    ldr w9, [sp, #244]
+    ldr w10, [sp, #80]
+    sub w11, w9, w10
+    str w11, [sp, #248]
+    ldr w11, [sp, #248]
+    str w11, [sp, #52]
+    ldr w9, [sp, #56]
+    mov w10, #3
+    mul w11, w9, w10
+    str w11, [sp, #252]
+    ldr w9, [sp, #252]
+    ldr w10, [sp, #60]
+    add w11, w9, w10
+    str w11, [sp, #256]
+    ldr w9, [sp, #256]
+    ldr w10, [sp, #80]
+    sub w11, w9, w10
+    str w11, [sp, #260]
+    ldr w11, [sp, #260]
+    str w11, [sp, #56]
+    ldr w9, [sp, #60]
+    mov w10, #3
+    mul w11, w9, w10
+    str w11, [sp, #264]
+    ldr w9, [sp, #264]
+    ldr w10, [sp, #64]
+    add w11, w9, w10
+    str w11, [sp, #268]
+    ldr w9, [sp, #268]
+    ldr w10, [sp, #80]
+    sub w11, w9, w10
+    str w11, [sp, #272]
+    ldr w11, [sp, #272]
+    str w11, [sp, #60]
+    ldr w9, [sp, #64]
+    mov w10, #3
+    mul w11, w9, w10
+    str w11, [sp, #276]
+    ldr w9, [sp, #276]
+    ldr w10, [sp, #68]
+    add w11, w9, w10
+    str w11, [sp, #280]
+    ldr w9, [sp, #280]
+    ldr w10, [sp, #80]
+    sub w11, w9, w10
+    str w11, [sp, #284]
+    ldr w11, [sp, #284]
+    str w11, [sp, #64]
+    ldr w9, [sp, #68]
+    mov w10, #3
+    mul w11, w9, w10
+    str w11, [sp, #288]
+    ldr w9, [sp, #288]
+    ldr w10, [sp, #72]
+    add w11, w9, w10
+    str w11, [sp, #292]
+    ldr w9, [sp, #292]
+    ldr w10, [sp, #80]
+    sub w11, w9, w10
+    str w11, [sp, #296]
+    ldr w11, [sp, #296]
+    str w11, [sp, #68]
+    ldr w9, [sp, #72]
+    mov w10, #3
+    mul w11, w9, w10
+    str w11, [sp, #300]
+    ldr w9, [sp, #300]
+    ldr w10, [sp, #76]
+    add w11, w9, w10
+    str w11, [sp, #304]
+    ldr w9, [sp, #304]
+    ldr w10, [sp, #80]
+    sub w11, w9, w10
+    str w11, [sp, #308]
+    ldr w11, [sp, #308]
+    str w11, [sp, #72]
+    ldr w9, [sp, #76]
+    mov w10, #3
+    mul w11, w9, w10
+    str w11, [sp, #312]
+    ldr w9, [sp, #312]
+    ldr w10, [sp, #92]
+    add w11, w9, w10
+    str w11, [sp, #316]
+    ldr w9, [sp, #316]
+    ldr w10, [sp, #80]
+    sub w11, w9, w10
+    str w11, [sp, #320]
+    ldr w11, [sp, #320]
+    str w11, [sp, #76]
+    ldr w9, [sp, #80]
+    add w11, w9, #1
+    str w11, [sp, #324]
+    ldr w11, [sp, #324]
+    str w11, [sp, #80]
+    b .Lloop
+.Lif_end22:
+    ldr w9, [sp, #4]
+    add w11, w9, #1
+    str w11, [sp, #328]
+    ldr w9, [sp, #0]
+    ldr w10, [sp, #328]
+    eor w11, w9, w10
+    str w11, [sp, #332]
+    ldr w9, [sp, #8]
+    add w11, w9, #2
+    str w11, [sp, #336]
+    ldr w9, [sp, #332]
+    ldr w10, [sp, #336]
+    eor w11, w9, w10
+    str w11, [sp, #340]
+    ldr w9, [sp, #12]
+    add w11, w9, #3
+    str w11, [sp, #344]
+    ldr w9, [sp, #340]
+    ldr w10, [sp, #344]
+    eor w11, w9, w10
+    str w11, [sp, #348]
+    ldr w9, [sp, #16]
+    add w11, w9, #4
+    str w11, [sp, #352]
+    ldr w9, [sp, #348]
+    ldr w10, [sp, #352]
+    eor w11, w9, w10
+    str w11, [sp, #356]
+    ldr w9, [sp, #20]
+    add w11, w9, #5
+    str w11, [sp, #360]
+    ldr w9, [sp, #356]
+    ldr w10, [sp, #360]
+    eor w11, w9, w10
+    str w11, [sp, #364]
+    ldr w9, [sp, #24]
+    add w11, w9, #6
+    str w11, [sp, #368]
+    ldr w9, [sp, #364]
+    ldr w10, [sp, #368]
+    eor w11, w9, w10
+    str w11, [sp, #372]
+    ldr w9, [sp, #28]
+    add w11, w9, #7
+    str w11, [sp, #376]
+    ldr w9, [sp, #372]
+    ldr w10, [sp, #376]
+    eor w11, w9, w10
+    str w11, [sp, #380]
+    ldr w9, [sp, #32]
+    add w11, w9, #8
+    str w11, [sp, #384]
+    ldr w9, [sp, #380]
+    ldr w10, [sp, #384]
+    eor w11, w9, w10
+    str w11, [sp, #388]
+    ldr w9, [sp, #36]
+    add w11, w9, #9
+    str w11, [sp, #392]
+    ldr w9, [sp, #388]
+    ldr w10, [sp, #392]
+    eor w11, w9, w10
+    str w11, [sp, #396]
+    ldr w9, [sp, #40]
+    add w11, w9, #10
+    str w11, [sp, #400]
+    ldr w9, [sp, #396]
+    ldr w10, [sp, #400]
+    eor w11, w9, w10
+    str w11, [sp, #404]
+    ldr w9, [sp, #44]
+    add w11, w9, #11
+    str w11, [sp, #408]
+    ldr w9, [sp, #404]
+    ldr w10, [sp, #408]
+    eor w11, w9, w10
+    str w11, [sp, #412]
+    ldr w9, [sp, #48]
+    add w11, w9, #12
+    str w11, [sp, #416]
+    ldr w9, [sp, #412]
+    ldr w10, [sp, #416]
+    eor w11, w9, w10
+    str w11, [sp, #420]
+    ldr w9, [sp, #52]
+    add w11, w9, #13
+    str w11, [sp, #424]
+    ldr w9, [sp, #420]
+    ldr w10, [sp, #424]
+    eor w11, w9, w10
+    str w11, [sp, #428]
+    ldr w9, [sp, #56]
+    add w11, w9, #14
+    str w11, [sp, #432]
+    ldr w9, [sp, #428]
+    ldr w10, [sp, #432]
+    eor w11, w9, w10
+    str w11, [sp, #436]
+    ldr w9, [sp, #60]
+    add w11, w9, #15
+    str w11, [sp, #440]
+    ldr w9, [sp, #436]
+    ldr w10, [sp, #440]
+    eor w11, w9, w10
+    str w11, [sp, #444]
+    ldr w9, [sp, #64]
+    add w11, w9, #16
+    str w11, [sp, #448]
+    ldr w9, [sp, #444]
+    ldr w10, [sp, #448]
+    eor w11, w9, w10
+    str w11, [sp, #452]
+    ldr w9, [sp, #68]
+    add w11, w9, #17
+    str w11, [sp, #456]
+    ldr w9, [sp, #452]
+    ldr w10, [sp, #456]
+    eor w11, w9, w10
+    str w11, [sp, #460]
+    ldr w9, [sp, #72]
+    add w11, w9, #18
+    str w11, [sp, #464]
+    ldr w9, [sp, #460]
+    ldr w10, [sp, #464]
+    eor w11, w9, w10
+    str w11, [sp, #468]
+    ldr w9, [sp, #76]
+    add w11, w9, #19
+    str w11, [sp, #472]
+    ldr w9, [sp, #468]
+    ldr w10, [sp, #472]
+    eor w11, w9, w10
+    str w11, [sp, #476]
+    ldr w9, [sp, #476]
+    mov w10, #7
+    sdiv w11, w9, w10
+    str w11, [sp, #480]
+    ldr w9, [sp, #476]
+    mov w10, #13
+    sdiv w12, w9, w10
+    msub w11, w12, w10, w9
+    str w11, [sp, #484]
+    ldr w9, [sp, #480]
+    ldr w10, [sp, #484]
+    add w11, w9, w10
+    str w11, [sp, #488]
+    ldr w9, [sp, #476]
+    lsl w11, w9, #2
+    str w11, [sp, #492]
+    ldr w9, [sp, #488]
+    ldr w10, [sp, #492]
+    add w11, w9, w10
+    str w11, [sp, #496]
+    ldr w9, [sp, #476]
+    asr w11, w9, #3
+    str w11, [sp, #500]
+    ldr w9, [sp, #496]
+    ldr w10, [sp, #500]
+    add w11, w9, w10
+    str w11, [sp, #504]
+    ldr w9, [sp, #504]
+    mov w10, #255
+    and w11, w9, w10
+    str w11, [sp, #508]
+    ldr w0, [sp, #508]
+    mov sp, x29
+    ldp x29, x30, [sp], #16
+    ret
+    .size main, .-main
+
+.section .note.GNU-stack,"",@progbits
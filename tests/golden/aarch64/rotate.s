@@ -0,0 +1,80 @@
+    .global main
+    .type main, %function
+    .p2align 2
+main:
+    stp x29, x30, [sp, #-16]!
+    mov x29, sp
+    sub sp, sp, #64
+    mov w11, #1
+    str w11, [sp, #0]
+    mov w11, #2
+    str w11, [sp, #4]
+    mov w11, #3
+    str w11, [sp, #8]
+    mov w11, #0
+    str w11, [sp, #12]
+.Lloop:
+    ldr w9, [sp, #12]
+    cmp w9, #5
+    b.ge .Lif_end6
+    ldr w11, [sp, #0]
+    str w11, [sp, #16]
+    ldr w11, [sp, #4]
+    str w11, [sp, #0]
+    ldr w11, [sp, #8]
+    str w11, [sp, #4]
+    ldr w11, [sp, #16]
+    str w11, [sp, #8]
+    ldr w9, [sp, #12]
+    add w11, w9, #1
+    str w11, [sp, #20]
+    ldr w11, [sp, #20]
+    str w11, [sp, #12]
+    b .Lloop
+.Lif_end6:
+    ldr w9, [sp, #0]
+    ldr w10, [sp, #4]
+    sub w11, w9, w10
+    str w11, [sp, #24]
+    ldr w9, [sp, #4]
+    ldr w10, [sp, #0]
+    sub w11, w9, w10
+    str w11, [sp, #28]
+    ldr w9, [sp, #0]
+    ldr w10, [sp, #4]
+    cmp w9, w10
+    cset w11, gt
+    str w11, [sp, #32]
+    ldr w9, [sp, #32]
+    cmp w9, #0
+    ldr w9, [sp, #24]
+    ldr w10, [sp, #28]
+    csel w11, w9, w10, ne
+    str w11, [sp, #36]
+    ldr w9, [sp, #0]
+    mov w10, #100
+    mul w11, w9, w10
+    str w11, [sp, #40]
+    ldr w9, [sp, #4]
+    mov w10, #10
+    mul w11, w9, w10
+    str w11, [sp, #44]
+    ldr w9, [sp, #40]
+    ldr w10, [sp, #44]
+    add w11, w9, w10
+    str w11, [sp, #48]
+    ldr w9, [sp, #48]
+    ldr w10, [sp, #8]
+    add w11, w9, w10
+    str w11, [sp, #52]
+    ldr w9, [sp, #52]
+    ldr w10, [sp, #36]
+    add w11, w9, w10
+    str w11, [sp, #56]
+    ldr w0, [sp, #56]
+    mov sp, x29
+    ldp x29, x30, [sp], #16
+    ret
+    .size main, .-main
+
+.section .note.GNU-stack,"",@progbits
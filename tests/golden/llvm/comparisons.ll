@@ -0,0 +1,94 @@
+define i32 @main() {
+.entry:
+  %i.0 = alloca i32
+  %less.1 = alloca i32
+  %equal.2 = alloca i32
+  %greater.3 = alloca i32
+  %temp.4 = alloca i32
+  %temp.5 = alloca i32
+  %temp.6 = alloca i32
+  %temp.7 = alloca i32
+  %temp.8 = alloca i32
+  %temp.9 = alloca i32
+  %temp.10 = alloca i32
+  %temp.11 = alloca i32
+  %temp.13 = alloca i32
+  %temp.14 = alloca i32
+  %temp.15 = alloca i32
+  %temp.16 = alloca i32
+  %temp.17 = alloca i32
+  %temp.18 = alloca i32
+  store i32 0, i32* %i.0
+  store i32 0, i32* %less.1
+  store i32 0, i32* %equal.2
+  store i32 0, i32* %greater.3
+  br label %loop
+loop:
+  %.0 = load i32, i32* %i.0
+  %.1 = icmp slt i32 %.0, 4
+  %.2 = zext i1 %.1 to i32
+  store i32 %.2, i32* %temp.4
+  %.3 = load i32, i32* %less.1
+  %.4 = load i32, i32* %temp.4
+  %.5 = add nsw i32 %.3, %.4
+  store i32 %.5, i32* %temp.5
+  %.6 = load i32, i32* %temp.5
+  store i32 %.6, i32* %less.1
+  %.7 = load i32, i32* %i.0
+  %.8 = icmp eq i32 %.7, 4
+  %.9 = zext i1 %.8 to i32
+  store i32 %.9, i32* %temp.6
+  %.10 = load i32, i32* %equal.2
+  %.11 = load i32, i32* %temp.6
+  %.12 = add nsw i32 %.10, %.11
+  store i32 %.12, i32* %temp.7
+  %.13 = load i32, i32* %temp.7
+  store i32 %.13, i32* %equal.2
+  %.14 = load i32, i32* %i.0
+  %.15 = icmp sgt i32 %.14, 4
+  %.16 = zext i1 %.15 to i32
+  store i32 %.16, i32* %temp.8
+  %.17 = load i32, i32* %temp.8
+  %.18 = mul nsw i32 %.17, 3
+  store i32 %.18, i32* %temp.9
+  %.19 = load i32, i32* %greater.3
+  %.20 = load i32, i32* %temp.9
+  %.21 = add nsw i32 %.19, %.20
+  store i32 %.21, i32* %temp.10
+  %.22 = load i32, i32* %temp.10
+  store i32 %.22, i32* %greater.3
+  %.23 = load i32, i32* %i.0
+  %.24 = add nsw i32 %.23, 1
+  store i32 %.24, i32* %temp.11
+  %.25 = load i32, i32* %temp.11
+  store i32 %.25, i32* %i.0
+  %.26 = load i32, i32* %temp.11
+  %.27 = icmp slt i32 %.26, 10
+  %.28 = zext i1 %.27 to i32
+  store i32 %.28, i32* %temp.13
+  %.29 = load i32, i32* %temp.13
+  %.30 = icmp ne i32 %.29, 0
+  br i1 %.30, label %.31, label %if_end12
+.31:
+  br label %loop
+if_end12:
+  %.32 = load i32, i32* %temp.5
+  %.33 = mul nsw i32 %.32, 100
+  store i32 %.33, i32* %temp.14
+  %.34 = load i32, i32* %temp.7
+  %.35 = mul nsw i32 %.34, 10
+  store i32 %.35, i32* %temp.15
+  %.36 = load i32, i32* %temp.14
+  %.37 = load i32, i32* %temp.15
+  %.38 = add nsw i32 %.36, %.37
+  store i32 %.38, i32* %temp.16
+  %.39 = load i32, i32* %temp.16
+  %.40 = load i32, i32* %temp.10
+  %.41 = add nsw i32 %.39, %.40
+  store i32 %.41, i32* %temp.17
+  %.42 = load i32, i32* %temp.17
+  %.43 = srem i32 %.42, 256
+  store i32 %.43, i32* %temp.18
+  %.44 = load i32, i32* %temp.18
+  ret i32 %.44
+}
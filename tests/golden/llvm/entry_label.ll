@@ -0,0 +1,26 @@
+define i32 @main() {
+.entry:
+  %x.0 = alloca i32
+  %temp.1 = alloca i32
+  %temp.3 = alloca i32
+  store i32 0, i32* %x.0
+  br label %entry
+entry:
+  %.0 = load i32, i32* %x.0
+  %.1 = add nsw i32 %.0, 1
+  store i32 %.1, i32* %temp.1
+  %.2 = load i32, i32* %temp.1
+  store i32 %.2, i32* %x.0
+  %.3 = load i32, i32* %temp.1
+  %.4 = icmp slt i32 %.3, 3
+  %.5 = zext i1 %.4 to i32
+  store i32 %.5, i32* %temp.3
+  %.6 = load i32, i32* %temp.3
+  %.7 = icmp ne i32 %.6, 0
+  br i1 %.7, label %.8, label %if_end2
+.8:
+  br label %entry
+if_end2:
+  %.9 = load i32, i32* %temp.1
+  ret i32 %.9
+}
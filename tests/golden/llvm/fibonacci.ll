@@ -0,0 +1,45 @@
+define i32 @main() {
+.entry:
+  %a.0 = alloca i32
+  %b.1 = alloca i32
+  %i.2 = alloca i32
+  %temp.5 = alloca i32
+  %temp.6 = alloca i32
+  %temp.7 = alloca i32
+  %temp.8 = alloca i32
+  store i32 0, i32* %a.0
+  store i32 1, i32* %b.1
+  store i32 0, i32* %i.2
+  br label %fib
+fib:
+  %.0 = load i32, i32* %i.2
+  %.1 = icmp sge i32 %.0, 20
+  %.2 = zext i1 %.1 to i32
+  store i32 %.2, i32* %temp.5
+  %.3 = load i32, i32* %temp.5
+  %.4 = icmp ne i32 %.3, 0
+  br i1 %.4, label %.5, label %if_end4
+.5:
+  br label %done
+if_end4:
+  %.6 = load i32, i32* %a.0
+  %.7 = load i32, i32* %b.1
+  %.8 = add nsw i32 %.6, %.7
+  store i32 %.8, i32* %temp.6
+  %.9 = load i32, i32* %b.1
+  store i32 %.9, i32* %a.0
+  %.10 = load i32, i32* %temp.6
+  store i32 %.10, i32* %b.1
+  %.11 = load i32, i32* %i.2
+  %.12 = add nsw i32 %.11, 1
+  store i32 %.12, i32* %temp.7
+  %.13 = load i32, i32* %temp.7
+  store i32 %.13, i32* %i.2
+  br label %fib
+done:
+  %.14 = load i32, i32* %a.0
+  %.15 = srem i32 %.14, 251
+  store i32 %.15, i32* %temp.8
+  %.16 = load i32, i32* %temp.8
+  ret i32 %.16
+}
@@ -0,0 +1,65 @@
+define i32 @main() {
+.entry:
+  %n.0 = alloca i32
+  %k.1 = alloca i32
+  %temp.8 = alloca i32
+  %temp.4 = alloca i32
+  %temp.6 = alloca i32
+  %temp.7 = alloca i32
+  %temp.10 = alloca i32
+  %temp.11 = alloca i32
+  store i32 0, i32* %n.0
+  store i32 3, i32* %k.1
+  br label %b
+a:
+  %.0 = load i32, i32* %temp.8
+  %.1 = add nsw i32 %.0, 3
+  store i32 %.1, i32* %temp.4
+  %.2 = load i32, i32* %temp.4
+  store i32 %.2, i32* %n.0
+  %.3 = load i32, i32* %temp.4
+  %.4 = icmp sgt i32 %.3, 40
+  %.5 = zext i1 %.4 to i32
+  store i32 %.5, i32* %temp.6
+  %.6 = load i32, i32* %temp.6
+  %.7 = icmp ne i32 %.6, 0
+  br i1 %.7, label %.8, label %if_end5
+.8:
+  br label %end
+if_end5:
+  br label %b
+b:
+  %.9 = load i32, i32* %n.0
+  %.10 = mul nsw i32 %.9, 2
+  store i32 %.10, i32* %temp.7
+  %.11 = load i32, i32* %temp.7
+  %.12 = add nsw i32 %.11, 1
+  store i32 %.12, i32* %temp.8
+  %.13 = load i32, i32* %temp.8
+  store i32 %.13, i32* %n.0
+  %.14 = load i32, i32* %temp.8
+  %.15 = icmp slt i32 %.14, 30
+  %.16 = zext i1 %.15 to i32
+  store i32 %.16, i32* %temp.10
+  %.17 = load i32, i32* %temp.10
+  %.18 = icmp ne i32 %.17, 0
+  br i1 %.18, label %.19, label %if_end9
+.19:
+  br label %a
+if_end9:
+  %.20 = load i32, i32* %k.1
+  %.21 = sub nsw i32 %.20, 1
+  store i32 %.21, i32* %temp.11
+  %.22 = load i32, i32* %temp.11
+  store i32 %.22, i32* %k.1
+  %.23 = load i32, i32* %temp.11
+  %.24 = icmp ne i32 %.23, 0
+  br i1 %.24, label %.25, label %if_end12
+.25:
+  br label %a
+if_end12:
+  br label %end
+end:
+  %.26 = load i32, i32* %n.0
+  ret i32 %.26
+}
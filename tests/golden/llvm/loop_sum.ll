@@ -0,0 +1,41 @@
+define i32 @main() {
+.entry:
+  %i.0 = alloca i32
+  %sum.1 = alloca i32
+  %temp.3 = alloca i32
+  %temp.4 = alloca i32
+  %temp.5 = alloca i32
+  %temp.7 = alloca i32
+  store i32 0, i32* %i.0
+  store i32 0, i32* %sum.1
+  br label %loop
+loop:
+  %.0 = load i32, i32* %i.0
+  %.1 = icmp slt i32 %.0, 10
+  %.2 = zext i1 %.1 to i32
+  store i32 %.2, i32* %temp.3
+  %.3 = load i32, i32* %temp.3
+  %.4 = icmp ne i32 %.3, 0
+  br i1 %.4, label %.5, label %if_end2
+.5:
+  %.6 = load i32, i32* %i.0
+  %.7 = load i32, i32* %i.0
+  %.8 = mul nsw i32 %.6, %.7
+  store i32 %.8, i32* %temp.4
+  %.9 = load i32, i32* %sum.1
+  %.10 = load i32, i32* %temp.4
+  %.11 = add nsw i32 %.9, %.10
+  store i32 %.11, i32* %temp.5
+  %.12 = load i32, i32* %temp.5
+  store i32 %.12, i32* %sum.1
+  %.13 = load i32, i32* %i.0
+  %.14 = add nsw i32 %.13, 1
+  store i32 %.14, i32* %i.0
+  br label %loop
+if_end2:
+  %.15 = load i32, i32* %sum.1
+  %.16 = srem i32 %.15, 256
+  store i32 %.16, i32* %temp.7
+  %.17 = load i32, i32* %temp.7
+  ret i32 %.17
+}
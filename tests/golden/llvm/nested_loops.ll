@@ -0,0 +1,73 @@
+define i32 @main() {
+.entry:
+  %y.1 = alloca i32
+  %k.2 = alloca i32
+  %temp.6 = alloca i32
+  %temp.7 = alloca i32
+  %temp.8 = alloca i32
+  %temp.10 = alloca i32
+  %n.4 = alloca i32
+  %m.5 = alloca i32
+  %temp.12 = alloca i32
+  %temp.13 = alloca i32
+  %temp.14 = alloca i32
+  %temp.15 = alloca i32
+  store i32 0, i32* %y.1
+  store i32 1, i32* %k.2
+  br label %top
+top:
+  %.0 = load i32, i32* %k.2
+  %.1 = mul nsw i32 10, %.0
+  store i32 %.1, i32* %temp.6
+  %.2 = load i32, i32* %y.1
+  %.3 = load i32, i32* %temp.6
+  %.4 = add nsw i32 %.2, %.3
+  store i32 %.4, i32* %temp.7
+  %.5 = load i32, i32* %temp.7
+  store i32 %.5, i32* %y.1
+  %.6 = load i32, i32* %k.2
+  %.7 = add nsw i32 %.6, 1
+  store i32 %.7, i32* %temp.8
+  %.8 = load i32, i32* %temp.8
+  store i32 %.8, i32* %k.2
+  %.9 = load i32, i32* %temp.8
+  %.10 = icmp slt i32 %.9, 5
+  %.11 = zext i1 %.10 to i32
+  store i32 %.11, i32* %temp.10
+  %.12 = load i32, i32* %temp.10
+  %.13 = icmp ne i32 %.12, 0
+  br i1 %.13, label %.14, label %if_end9
+.14:
+  br label %top
+if_end9:
+  store i32 0, i32* %n.4
+  store i32 100, i32* %m.5
+  br label %again
+again:
+  %.15 = load i32, i32* %m.5
+  %.16 = icmp sgt i32 %.15, 0
+  %.17 = zext i1 %.16 to i32
+  store i32 %.17, i32* %temp.12
+  %.18 = load i32, i32* %temp.12
+  %.19 = icmp ne i32 %.18, 0
+  br i1 %.19, label %.20, label %if_end11
+.20:
+  %.21 = load i32, i32* %m.5
+  %.22 = sdiv i32 %.21, 3
+  store i32 %.22, i32* %temp.13
+  %.23 = load i32, i32* %temp.13
+  store i32 %.23, i32* %m.5
+  %.24 = load i32, i32* %n.4
+  %.25 = add nsw i32 %.24, 1
+  store i32 %.25, i32* %temp.14
+  %.26 = load i32, i32* %temp.14
+  store i32 %.26, i32* %n.4
+  br label %again
+if_end11:
+  %.27 = load i32, i32* %temp.7
+  %.28 = load i32, i32* %n.4
+  %.29 = add nsw i32 %.27, %.28
+  store i32 %.29, i32* %temp.15
+  %.30 = load i32, i32* %temp.15
+  ret i32 %.30
+}
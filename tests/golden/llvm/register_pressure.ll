@@ -0,0 +1,592 @@
+define i32 @main() {
+.entry:
+  %v0.0 = alloca i32
+  %v1.1 = alloca i32
+  %v2.2 = alloca i32
+  %v3.3 = alloca i32
+  %v4.4 = alloca i32
+  %v5.5 = alloca i32
+  %v6.6 = alloca i32
+  %v7.7 = alloca i32
+  %v8.8 = alloca i32
+  %v9.9 = alloca i32
+  %v10.10 = alloca i32
+  %v11.11 = alloca i32
+  %v12.12 = alloca i32
+  %v13.13 = alloca i32
+  %v14.14 = alloca i32
+  %v15.15 = alloca i32
+  %v16.16 = alloca i32
+  %v17.17 = alloca i32
+  %v18.18 = alloca i32
+  %v19.19 = alloca i32
+  %k.20 = alloca i32
+  %temp.23 = alloca i32
+  %temp.24 = alloca i32
+  %temp.25 = alloca i32
+  %temp.26 = alloca i32
+  %temp.27 = alloca i32
+  %temp.28 = alloca i32
+  %temp.29 = alloca i32
+  %temp.30 = alloca i32
+  %temp.31 = alloca i32
+  %temp.32 = alloca i32
+  %temp.33 = alloca i32
+  %temp.34 = alloca i32
+  %temp.35 = alloca i32
+  %temp.36 = alloca i32
+  %temp.37 = alloca i32
+  %temp.38 = alloca i32
+  %temp.39 = alloca i32
+  %temp.40 = alloca i32
+  %temp.41 = alloca i32
+  %temp.42 = alloca i32
+  %temp.43 = alloca i32
+  %temp.44 = alloca i32
+  %temp.45 = alloca i32
+  %temp.46 = alloca i32
+  %temp.47 = alloca i32
+  %temp.48 = alloca i32
+  %temp.49 = alloca i32
+  %temp.50 = alloca i32
+  %temp.51 = alloca i32
+  %temp.52 = alloca i32
+  %temp.53 = alloca i32
+  %temp.54 = alloca i32
+  %temp.55 = alloca i32
+  %temp.56 = alloca i32
+  %temp.57 = alloca i32
+  %temp.58 = alloca i32
+  %temp.59 = alloca i32
+  %temp.60 = alloca i32
+  %temp.61 = alloca i32
+  %temp.62 = alloca i32
+  %temp.63 = alloca i32
+  %temp.64 = alloca i32
+  %temp.65 = alloca i32
+  %temp.66 = alloca i32
+  %temp.67 = alloca i32
+  %temp.68 = alloca i32
+  %temp.69 = alloca i32
+  %temp.70 = alloca i32
+  %temp.71 = alloca i32
+  %temp.72 = alloca i32
+  %temp.73 = alloca i32
+  %temp.74 = alloca i32
+  %temp.75 = alloca i32
+  %temp.76 = alloca i32
+  %temp.77 = alloca i32
+  %temp.78 = alloca i32
+  %temp.79 = alloca i32
+  %temp.80 = alloca i32
+  %temp.81 = alloca i32
+  %temp.82 = alloca i32
+  %temp.83 = alloca i32
+  %temp.84 = alloca i32
+  %temp.87 = alloca i32
+  %temp.88 = alloca i32
+  %temp.89 = alloca i32
+  %temp.90 = alloca i32
+  %temp.91 = alloca i32
+  %temp.92 = alloca i32
+  %temp.93 = alloca i32
+  %temp.94 = alloca i32
+  %temp.95 = alloca i32
+  %temp.96 = alloca i32
+  %temp.97 = alloca i32
+  %temp.98 = alloca i32
+  %temp.99 = alloca i32
+  %temp.100 = alloca i32
+  %temp.101 = alloca i32
+  %temp.102 = alloca i32
+  %temp.103 = alloca i32
+  %temp.104 = alloca i32
+  %temp.105 = alloca i32
+  %temp.106 = alloca i32
+  %temp.107 = alloca i32
+  %temp.108 = alloca i32
+  %temp.109 = alloca i32
+  %temp.110 = alloca i32
+  %temp.111 = alloca i32
+  %temp.112 = alloca i32
+  %temp.113 = alloca i32
+  %temp.114 = alloca i32
+  %temp.115 = alloca i32
+  %temp.116 = alloca i32
+  %temp.117 = alloca i32
+  %temp.118 = alloca i32
+  %temp.119 = alloca i32
+  %temp.120 = alloca i32
+  %temp.121 = alloca i32
+  %temp.122 = alloca i32
+  %temp.123 = alloca i32
+  %temp.124 = alloca i32
+  %temp.125 = alloca i32
+  %temp.126 = alloca i32
+  %temp.127 = alloca i32
+  %temp.128 = alloca i32
+  %temp.129 = alloca i32
+  %temp.130 = alloca i32
+  %temp.131 = alloca i32
+  %temp.132 = alloca i32
+  store i32 3, i32* %v0.0
+  store i32 10, i32* %v1.1
+  store i32 17, i32* %v2.2
+  store i32 24, i32* %v3.3
+  store i32 31, i32* %v4.4
+  store i32 38, i32* %v5.5
+  store i32 45, i32* %v6.6
+  store i32 52, i32* %v7.7
+  store i32 59, i32* %v8.8
+  store i32 66, i32* %v9.9
+  store i32 73, i32* %v10.10
+  store i32 80, i32* %v11.11
+  store i32 87, i32* %v12.12
+  store i32 94, i32* %v13.13
+  store i32 101, i32* %v14.14
+  store i32 108, i32* %v15.15
+  store i32 115, i32* %v16.16
+  store i32 122, i32* %v17.17
+  store i32 129, i32* %v18.18
+  store i32 136, i32* %v19.19
+  store i32 0, i32* %k.20
+  br label %loop
+loop:
+  %.0 = load i32, i32* %k.20
+  %.1 = icmp slt i32 %.0, 3
+  %.2 = zext i1 %.1 to i32
+  store i32 %.2, i32* %temp.23
+  %.3 = load i32, i32* %temp.23
+  %.4 = icmp ne i32 %.3, 0
+  br i1 %.4, label %.5, label %if_end22
+.5:
+  %.6 = load i32, i32* %v0.0
+  %.7 = mul nsw i32 %.6, 3
+  store i32 %.7, i32* %temp.24
+  %.8 = load i32, i32* %temp.24
+  %.9 = load i32, i32* %v1.1
+  %.10 = add nsw i32 %.8, %.9
+  store i32 %.10, i32* %temp.25
+  %.11 = load i32, i32* %temp.25
+  %.12 = load i32, i32* %k.20
+  %.13 = sub nsw i32 %.11, %.12
+  store i32 %.13, i32* %temp.26
+  %.14 = load i32, i32* %temp.26
+  store i32 %.14, i32* %v0.0
+  %.15 = load i32, i32* %v1.1
+  %.16 = mul nsw i32 %.15, 3
+  store i32 %.16, i32* %temp.27
+  %.17 = load i32, i32* %temp.27
+  %.18 = load i32, i32* %v2.2
+  %.19 = add nsw i32 %.17, %.18
+  store i32 %.19, i32* %temp.28
+  %.20 = load i32, i32* %temp.28
+  %.21 = load i32, i32* %k.20
+  %.22 = sub nsw i32 %.20, %.21
+  store i32 %.22, i32* %temp.29
+  %.23 = load i32, i32* %temp.29
+  store i32 %.23, i32* %v1.1
+  %.24 = load i32, i32* %v2.2
+  %.25 = mul nsw i32 %.24, 3
+  store i32 %.25, i32* %temp.30
+  %.26 = load i32, i32* %temp.30
+  %.27 = load i32, i32* %v3.3
+  %.28 = add nsw i32 %.26, %.27
+  store i32 %.28, i32* %temp.31
+  %.29 = load i32, i32* %temp.31
+  %.30 = load i32, i32* %k.20
+  %.31 = sub nsw i32 %.29, %.30
+  store i32 %.31, i32* %temp.32
+  %.32 = load i32, i32* %temp.32
+  store i32 %.32, i32* %v2.2
+  %.33 = load i32, i32* %v3.3
+  %.34 = mul nsw i32 %.33, 3
+  store i32 %.34, i32* %temp.33
+  %.35 = load i32, i32* %temp.33
+  %.36 = load i32, i32* %v4.4
+  %.37 = add nsw i32 %.35, %.36
+  store i32 %.37, i32* %temp.34
+  %.38 = load i32, i32* %temp.34
+  %.39 = load i32, i32* %k.20
+  %.40 = sub nsw i32 %.38, %.39
+  store i32 %.40, i32* %temp.35
+  %.41 = load i32, i32* %temp.35
+  store i32 %.41, i32* %v3.3
+  %.42 = load i32, i32* %v4.4
+  %.43 = mul nsw i32 %.42, 3
+  store i32 %.43, i32* %temp.36
+  %.44 = load i32, i32* %temp.36
+  %.45 = load i32, i32* %v5.5
+  %.46 = add nsw i32 %.44, %.45
+  store i32 %.46, i32* %temp.37
+  %.47 = load i32, i32* %temp.37
+  %.48 = load i32, i32* %k.20
+  %.49 = sub nsw i32 %.47, %.48
+  store i32 %.49, i32* %temp.38
+  %.50 = load i32, i32* %temp.38
+  store i32 %.50, i32* %v4.4
+  %.51 = load i32, i32* %v5.5
+  %.52 = mul nsw i32 %.51, 3
+  store i32 %.52, i32* %temp.39
+  %.53 = load i32, i32* %temp.39
+  %.54 = load i32, i32* %v6.6
+  %.55 = add nsw i32 %.53, %.54
+  store i32 %.55, i32* %temp.40
+  %.56 = load i32, i32* %temp.40
+  %.57 = load i32, i32* %k.20
+  %.58 = sub nsw i32 %.56, %.57
+  store i32 %.58, i32* %temp.41
+  %.59 = load i32, i32* %temp.41
+  store i32 %.59, i32* %v5.5
+  %.60 = load i32, i32* %v6.6
+  %.61 = mul nsw i32 %.60, 3
+  store i32 %.61, i32* %temp.42
+  %.62 = load i32, i32* %temp.42
+  %.63 = load i32, i32* %v7.7
+  %.64 = add nsw i32 %.62, %.63
+  store i32 %.64, i32* %temp.43
+  %.65 = load i32, i32* %temp.43
+  %.66 = load i32, i32* %k.20
+  %.67 = sub nsw i32 %.65, %.66
+  store i32 %.67, i32* %temp.44
+  %.68 = load i32, i32* %temp.44
+  store i32 %.68, i32* %v6.6
+  %.69 = load i32, i32* %v7.7
+  %.70 = mul nsw i32 %.69, 3
+  store i32 %.70, i32* %temp.45
+  %.71 = load i32, i32* %temp.45
+  %.72 = load i32, i32* %v8.8
+  %.73 = add nsw i32 %.71, %.72
+  store i32 %.73, i32* %temp.46
+  %.74 = load i32, i32* %temp.46
+  %.75 = load i32, i32* %k.20
+  %.76 = sub nsw i32 %.74, %.75
+  store i32 %.76, i32* %temp.47
+  %.77 = load i32, i32* %temp.47
+  store i32 %.77, i32* %v7.7
+  %.78 = load i32, i32* %v8.8
+  %.79 = mul nsw i32 %.78, 3
+  store i32 %.79, i32* %temp.48
+  %.80 = load i32, i32* %temp.48
+  %.81 = load i32, i32* %v9.9
+  %.82 = add nsw i32 %.80, %.81
+  store i32 %.82, i32* %temp.49
+  %.83 = load i32, i32* %temp.49
+  %.84 = load i32, i32* %k.20
+  %.85 = sub nsw i32 %.83, %.84
+  store i32 %.85, i32* %temp.50
+  %.86 = load i32, i32* %temp.50
+  store i32 %.86, i32* %v8.8
+  %.87 = load i32, i32* %v9.9
+  %.88 = mul nsw i32 %.87, 3
+  store i32 %.88, i32* %temp.51
+  %.89 = load i32, i32* %temp.51
+  %.90 = load i32, i32* %v10.10
+  %.91 = add nsw i32 %.89, %.90
+  store i32 %.91, i32* %temp.52
+  %.92 = load i32, i32* %temp.52
+  %.93 = load i32, i32* %k.20
+  %.94 = sub nsw i32 %.92, %.93
+  store i32 %.94, i32* %temp.53
+  %.95 = load i32, i32* %temp.53
+  store i32 %.95, i32* %v9.9
+  %.96 = load i32, i32* %v10.10
+  %.97 = mul nsw i32 %.96, 3
+  store i32 %.97, i32* %temp.54
+  %.98 = load i32, i32* %temp.54
+  %.99 = load i32, i32* %v11.11
+  %.100 = add nsw i32 %.98, %.99
+  store i32 %.100, i32* %temp.55
+  %.101 = load i32, i32* %temp.55
+  %.102 = load i32, i32* %k.20
+  %.103 = sub nsw i32 %.101, %.102
+  store i32 %.103, i32* %temp.56
+  %.104 = load i32, i32* %temp.56
+  store i32 %.104, i32* %v10.10
+  %.105 = load i32, i32* %v11.11
+  %.106 = mul nsw i32 %.105, 3
+  store i32 %.106, i32* %temp.57
+  %.107 = load i32, i32* %temp.57
+  %.108 = load i32, i32* %v12.12
+  %.109 = add nsw i32 %.107, %.108
+  store i32 %.109, i32* %temp.58
+  %.110 = load i32, i32* %temp.58
+  %.111 = load i32, i32* %k.20
+  %.112 = sub nsw i32 %.110, %.111
+  store i32 %.112, i32* %temp.59
+  %.113 = load i32, i32* %temp.59
+  store i32 %.113, i32* %v11.11
+  %.114 = load i32, i32* %v12.12
+  %.115 = mul nsw i32 %.114, 3
+  store i32 %.115, i32* %temp.60
+  %.116 = load i32, i32* %temp.60
+  %.117 = load i32, i32* %v13.13
+  %.118 = add nsw i32 %.116, %.117
+  store i32 %.118, i32* %temp.61
+  %.119 = load i32, i32* %temp.61
+  %.120 = load i32, i32* %k.20
+  %.121 = sub nsw i32 %.119, %.120
+  store i32 %.121, i32* %temp.62
+  %.122 = load i32, i32* %temp.62
+  store i32 %.122, i32* %v12.12
+  %.123 = load i32, i32* %v13.13
+  %.124 = mul nsw i32 %.123, 3
+  store i32 %.124, i32* %temp.63
+  %.125 = load i32, i32* %temp.63
+  %.126 = load i32, i32* %v14.14
+  %.127 = add nsw i32 %.125, %.126
+  store i32 %.127, i32* %temp.64
+  %.128 = load i32, i32* %temp.64
+  %.129 = load i32, i32* %k.20
+  %.130 = sub nsw i32 %.128, %.129
+  store i32 %.130, i32* %temp.65
+  %.131 = load i32, i32* %temp.65
+  store i32 %.131, i32* %v13.13
+  %.132 = load i32, i32* %v14.14
+  %.133 = mul nsw i32 %.132, 3
+  store i32 %.133, i32* %temp.66
+  %.134 = load i32, i32* %temp.66
+  %.135 = load i32, i32* %v15.15
+  %.136 = add nsw i32 %.134, %.135
+  store i32 %.136, i32* %temp.67
+  %.137 = load i32, i32* %temp.67
+  %.138 = load i32, i32* %k.20
+  %.139 = sub nsw i32 %.137, %.138
+  store i32 %.139, i32* %temp.68
+  %.140 = load i32, i32* %temp.68
+  store i32 %.140, i32* %v14.14
+  %.141 = load i32, i32* %v15.15
+  %.142 = mul nsw i32 %.141, 3
+  store i32 %.142, i32* %temp.69
+  %.143 = load i32, i32* %temp.69
+  %.144 = load i32, i32* %v16.16
+  %.145 = add nsw i32 %.143, %.144
+  store i32 %.145, i32* %temp.70
+  %.146 = load i32, i32* %temp.70
+  %.147 = load i32, i32* %k.20
+  %.148 = sub nsw i32 %.146, %.147
+  store i32 %.148, i32* %temp.71
+  %.149 = load i32, i32* %temp.71
+  store i32 %.149, i32* %v15.15
+  %.150 = load i32, i32* %v16.16
+  %.151 = mul nsw i32 %.150, 3
+  store i32 %.151, i32* %temp.72
+  %.152 = load i32, i32* %temp.72
+  %.153 = load i32, i32* %v17.17
+  %.154 = add nsw i32 %.152, %.153
+  store i32 %.154, i32* %temp.73
+  %.155 = load i32, i32* %temp.73
+  %.156 = load i32, i32* %k.20
+  %.157 = sub nsw i32 %.155, %.156
+  store i32 %.157, i32* %temp.74
+  %.158 = load i32, i32* %temp.74
+  store i32 %.158, i32* %v16.16
+  %.159 = load i32, i32* %v17.17
+  %.160 = mul nsw i32 %.159, 3
+  store i32 %.160, i32* %temp.75
+  %.161 = load i32, i32* %temp.75
+  %.162 = load i32, i32* %v18.18
+  %.163 = add nsw i32 %.161, %.162
+  store i32 %.163, i32* %temp.76
+  %.164 = load i32, i32* %temp.76
+  %.165 = load i32, i32* %k.20
+  %.166 = sub nsw i32 %.164, %.165
+  store i32 %.166, i32* %temp.77
+  %.167 = load i32, i32* %temp.77
+  store i32 %.167, i32* %v17.17
+  %.168 = load i32, i32* %v18.18
+  %.169 = mul nsw i32 %.168, 3
+  store i32 %.169, i32* %temp.78
+  %.170 = load i32, i32* %temp.78
+  %.171 = load i32, i32* %v19.19
+  %.172 = add nsw i32 %.170, %.171
+  store i32 %.172, i32* %temp.79
+  %.173 = load i32, i32* %temp.79
+  %.174 = load i32, i32* %k.20
+  %.175 = sub nsw i32 %.173, %.174
+  store i32 %.175, i32* %temp.80
+  %.176 = load i32, i32* %temp.80
+  store i32 %.176, i32* %v18.18
+  %.177 = load i32, i32* %v19.19
+  %.178 = mul nsw i32 %.177, 3
+  store i32 %.178, i32* %temp.81
+  %.179 = load i32, i32* %temp.81
+  %.180 = load i32, i32* %temp.26
+  %.181 = add nsw i32 %.179, %.180
+  store i32 %.181, i32* %temp.82
+  %.182 = load i32, i32* %temp.82
+  %.183 = load i32, i32* %k.20
+  %.184 = sub nsw i32 %.182, %.183
+  store i32 %.184, i32* %temp.83
+  %.185 = load i32, i32* %temp.83
+  store i32 %.185, i32* %v19.19
+  %.186 = load i32, i32* %k.20
+  %.187 = add nsw i32 %.186, 1
+  store i32 %.187, i32* %temp.84
+  %.188 = load i32, i32* %temp.84
+  store i32 %.188, i32* %k.20
+  br label %loop
+if_end22:
+  %.189 = load i32, i32* %v1.1
+  %.190 = add nsw i32 %.189, 1
+  store i32 %.190, i32* %temp.87
+  %.191 = load i32, i32* %v0.0
+  %.192 = load i32, i32* %temp.87
+  %.193 = xor i32 %.191, %.192
+  store i32 %.193, i32* %temp.88
+  %.194 = load i32, i32* %v2.2
+  %.195 = add nsw i32 %.194, 2
+  store i32 %.195, i32* %temp.89
+  %.196 = load i32, i32* %temp.88
+  %.197 = load i32, i32* %temp.89
+  %.198 = xor i32 %.196, %.197
+  store i32 %.198, i32* %temp.90
+  %.199 = load i32, i32* %v3.3
+  %.200 = add nsw i32 %.199, 3
+  store i32 %.200, i32* %temp.91
+  %.201 = load i32, i32* %temp.90
+  %.202 = load i32, i32* %temp.91
+  %.203 = xor i32 %.201, %.202
+  store i32 %.203, i32* %temp.92
+  %.204 = load i32, i32* %v4.4
+  %.205 = add nsw i32 %.204, 4
+  store i32 %.205, i32* %temp.93
+  %.206 = load i32, i32* %temp.92
+  %.207 = load i32, i32* %temp.93
+  %.208 = xor i32 %.206, %.207
+  store i32 %.208, i32* %temp.94
+  %.209 = load i32, i32* %v5.5
+  %.210 = add nsw i32 %.209, 5
+  store i32 %.210, i32* %temp.95
+  %.211 = load i32, i32* %temp.94
+  %.212 = load i32, i32* %temp.95
+  %.213 = xor i32 %.211, %.212
+  store i32 %.213, i32* %temp.96
+  %.214 = load i32, i32* %v6.6
+  %.215 = add nsw i32 %.214, 6
+  store i32 %.215, i32* %temp.97
+  %.216 = load i32, i32* %temp.96
+  %.217 = load i32, i32* %temp.97
+  %.218 = xor i32 %.216, %.217
+  store i32 %.218, i32* %temp.98
+  %.219 = load i32, i32* %v7.7
+  %.220 = add nsw i32 %.219, 7
+  store i32 %.220, i32* %temp.99
+  %.221 = load i32, i32* %temp.98
+  %.222 = load i32, i32* %temp.99
+  %.223 = xor i32 %.221, %.222
+  store i32 %.223, i32* %temp.100
+  %.224 = load i32, i32* %v8.8
+  %.225 = add nsw i32 %.224, 8
+  store i32 %.225, i32* %temp.101
+  %.226 = load i32, i32* %temp.100
+  %.227 = load i32, i32* %temp.101
+  %.228 = xor i32 %.226, %.227
+  store i32 %.228, i32* %temp.102
+  %.229 = load i32, i32* %v9.9
+  %.230 = add nsw i32 %.229, 9
+  store i32 %.230, i32* %temp.103
+  %.231 = load i32, i32* %temp.102
+  %.232 = load i32, i32* %temp.103
+  %.233 = xor i32 %.231, %.232
+  store i32 %.233, i32* %temp.104
+  %.234 = load i32, i32* %v10.10
+  %.235 = add nsw i32 %.234, 10
+  store i32 %.235, i32* %temp.105
+  %.236 = load i32, i32* %temp.104
+  %.237 = load i32, i32* %temp.105
+  %.238 = xor i32 %.236, %.237
+  store i32 %.238, i32* %temp.106
+  %.239 = load i32, i32* %v11.11
+  %.240 = add nsw i32 %.239, 11
+  store i32 %.240, i32* %temp.107
+  %.241 = load i32, i32* %temp.106
+  %.242 = load i32, i32* %temp.107
+  %.243 = xor i32 %.241, %.242
+  store i32 %.243, i32* %temp.108
+  %.244 = load i32, i32* %v12.12
+  %.245 = add nsw i32 %.244, 12
+  store i32 %.245, i32* %temp.109
+  %.246 = load i32, i32* %temp.108
+  %.247 = load i32, i32* %temp.109
+  %.248 = xor i32 %.246, %.247
+  store i32 %.248, i32* %temp.110
+  %.249 = load i32, i32* %v13.13
+  %.250 = add nsw i32 %.249, 13
+  store i32 %.250, i32* %temp.111
+  %.251 = load i32, i32* %temp.110
+  %.252 = load i32, i32* %temp.111
+  %.253 = xor i32 %.251, %.252
+  store i32 %.253, i32* %temp.112
+  %.254 = load i32, i32* %v14.14
+  %.255 = add nsw i32 %.254, 14
+  store i32 %.255, i32* %temp.113
+  %.256 = load i32, i32* %temp.112
+  %.257 = load i32, i32* %temp.113
+  %.258 = xor i32 %.256, %.257
+  store i32 %.258, i32* %temp.114
+  %.259 = load i32, i32* %v15.15
+  %.260 = add nsw i32 %.259, 15
+  store i32 %.260, i32* %temp.115
+  %.261 = load i32, i32* %temp.114
+  %.262 = load i32, i32* %temp.115
+  %.263 = xor i32 %.261, %.262
+  store i32 %.263, i32* %temp.116
+  %.264 = load i32, i32* %v16.16
+  %.265 = add nsw i32 %.264, 16
+  store i32 %.265, i32* %temp.117
+  %.266 = load i32, i32* %temp.116
+  %.267 = load i32, i32* %temp.117
+  %.268 = xor i32 %.266, %.267
+  store i32 %.268, i32* %temp.118
+  %.269 = load i32, i32* %v17.17
+  %.270 = add nsw i32 %.269, 17
+  store i32 %.270, i32* %temp.119
+  %.271 = load i32, i32* %temp.118
+  %.272 = load i32, i32* %temp.119
+  %.273 = xor i32 %.271, %.272
+  store i32 %.273, i32* %temp.120
+  %.274 = load i32, i32* %v18.18
+  %.275 = add nsw i32 %.274, 18
+  store i32 %.275, i32* %temp.121
+  %.276 = load i32, i32* %temp.120
+  %.277 = load i32, i32* %temp.121
+  %.278 = xor i32 %.276, %.277
+  store i32 %.278, i32* %temp.122
+  %.279 = load i32, i32* %v19.19
+  %.280 = add nsw i32 %.279, 19
+  store i32 %.280, i32* %temp.123
+  %.281 = load i32, i32* %temp.122
+  %.282 = load i32, i32* %temp.123
+  %.283 = xor i32 %.281, %.282
+  store i32 %.283, i32* %temp.124
+  %.284 = load i32, i32* %temp.124
+  %.285 = sdiv i32 %.284, 7
+  store i32 %.285, i32* %temp.125
+  %.286 = load i32, i32* %temp.124
+  %.287 = srem i32 %.286, 13
+  store i32 %.287, i32* %temp.126
+  %.288 = load i32, i32* %temp.125
+  %.289 = load i32, i32* %temp.126
+  %.290 = add nsw i32 %.288, %.289
+  store i32 %.290, i32* %temp.127
+  %.291 = load i32, i32* %temp.124
+  %.292 = shl nsw i32 %.291, 2
+  store i32 %.292, i32* %temp.128
+  %.293 = load i32, i32* %temp.127
+  %.294 = load i32, i32* %temp.128
+  %.295 = add nsw i32 %.293, %.294
+  store i32 %.295, i32* %temp.129
+  %.296 = load i32, i32* %temp.124
+  %.297 = ashr i32 %.296, 3
+  store i32 %.297, i32* %temp.130
+  %.298 = load i32, i32* %temp.129
+  %.299 = load i32, i32* %temp.130
+  %.300 = add nsw i32 %.298, %.299
+  store i32 %.300, i32* %temp.131
+  %.301 = load i32, i32* %temp.131
+  %.302 = and i32 %.301, 255
+  store i32 %.302, i32* %temp.132
+  %.303 = load i32, i32* %temp.132
+  ret i32 %.303
+}
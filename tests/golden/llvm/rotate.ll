@@ -0,0 +1,87 @@
+define i32 @main() {
+.entry:
+  %a.0 = alloca i32
+  %b.1 = alloca i32
+  %c.2 = alloca i32
+  %i.3 = alloca i32
+  %temp.7 = alloca i32
+  %t.4 = alloca i32
+  %temp.8 = alloca i32
+  %temp.12 = alloca i32
+  %temp.13 = alloca i32
+  %temp.11 = alloca i32
+  %x.5 = alloca i32
+  %temp.14 = alloca i32
+  %temp.15 = alloca i32
+  %temp.16 = alloca i32
+  %temp.17 = alloca i32
+  %temp.18 = alloca i32
+  store i32 1, i32* %a.0
+  store i32 2, i32* %b.1
+  store i32 3, i32* %c.2
+  store i32 0, i32* %i.3
+  br label %loop
+loop:
+  %.0 = load i32, i32* %i.3
+  %.1 = icmp slt i32 %.0, 5
+  %.2 = zext i1 %.1 to i32
+  store i32 %.2, i32* %temp.7
+  %.3 = load i32, i32* %temp.7
+  %.4 = icmp ne i32 %.3, 0
+  br i1 %.4, label %.5, label %if_end6
+.5:
+  %.6 = load i32, i32* %a.0
+  store i32 %.6, i32* %t.4
+  %.7 = load i32, i32* %b.1
+  store i32 %.7, i32* %a.0
+  %.8 = load i32, i32* %c.2
+  store i32 %.8, i32* %b.1
+  %.9 = load i32, i32* %t.4
+  store i32 %.9, i32* %c.2
+  %.10 = load i32, i32* %i.3
+  %.11 = add nsw i32 %.10, 1
+  store i32 %.11, i32* %temp.8
+  %.12 = load i32, i32* %temp.8
+  store i32 %.12, i32* %i.3
+  br label %loop
+if_end6:
+  %.13 = load i32, i32* %a.0
+  %.14 = load i32, i32* %b.1
+  %.15 = sub nsw i32 %.13, %.14
+  store i32 %.15, i32* %temp.12
+  %.16 = load i32, i32* %b.1
+  %.17 = load i32, i32* %a.0
+  %.18 = sub nsw i32 %.16, %.17
+  store i32 %.18, i32* %temp.13
+  %.19 = load i32, i32* %a.0
+  %.20 = load i32, i32* %b.1
+  %.21 = icmp sgt i32 %.19, %.20
+  %.22 = zext i1 %.21 to i32
+  store i32 %.22, i32* %temp.11
+  %.23 = load i32, i32* %temp.11
+  %.24 = icmp ne i32 %.23, 0
+  %.25 = load i32, i32* %temp.12
+  %.26 = load i32, i32* %temp.13
+  %.27 = select i1 %.24, i32 %.25, i32 %.26
+  store i32 %.27, i32* %x.5
+  %.28 = load i32, i32* %a.0
+  %.29 = mul nsw i32 %.28, 100
+  store i32 %.29, i32* %temp.14
+  %.30 = load i32, i32* %b.1
+  %.31 = mul nsw i32 %.30, 10
+  store i32 %.31, i32* %temp.15
+  %.32 = load i32, i32* %temp.14
+  %.33 = load i32, i32* %temp.15
+  %.34 = add nsw i32 %.32, %.33
+  store i32 %.34, i32* %temp.16
+  %.35 = load i32, i32* %temp.16
+  %.36 = load i32, i32* %c.2
+  %.37 = add nsw i32 %.35, %.36
+  store i32 %.37, i32* %temp.17
+  %.38 = load i32, i32* %temp.17
+  %.39 = load i32, i32* %x.5
+  %.40 = add nsw i32 %.38, %.39
+  store i32 %.40, i32* %temp.18
+  %.41 = load i32, i32* %temp.18
+  ret i32 %.41
+}
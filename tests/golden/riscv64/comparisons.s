@@ -0,0 +1,91 @@
+    .text
+    .globl main
+    .type main, @function
+    .p2align 2
+main:
+    addi sp, sp, -16
+    sd ra, 8(sp)
+    sd s0, 0(sp)
+    addi s0, sp, 16
+    addi sp, sp, -80
+    li t2, 0
+    sw t2, 0(sp)
+    li t2, 0
+    sw t2, 4(sp)
+    li t2, 0
+    sw t2, 8(sp)
+    li t2, 0
+    sw t2, 12(sp)
+.Lloop:
+    lw t0, 0(sp)
+    slti t2, t0, 4
+    sw t2, 16(sp)
+    lw t0, 4(sp)
+    lw t1, 16(sp)
+    addw t2, t0, t1
+    sw t2, 20(sp)
+    lw t2, 20(sp)
+    sw t2, 4(sp)
+    lw t0, 0(sp)
+    xori t2, t0, 4
+    seqz t2, t2
+    sw t2, 24(sp)
+    lw t0, 8(sp)
+    lw t1, 24(sp)
+    addw t2, t0, t1
+    sw t2, 28(sp)
+    lw t2, 28(sp)
+    sw t2, 8(sp)
+    li t0, 4
+    lw t1, 0(sp)
+    slt t2, t0, t1
+    sw t2, 32(sp)
+    lw t0, 32(sp)
+    li t1, 3
+    mulw t2, t0, t1
+    sw t2, 36(sp)
+    lw t0, 12(sp)
+    lw t1, 36(sp)
+    addw t2, t0, t1
+    sw t2, 40(sp)
+    lw t2, 40(sp)
+    sw t2, 12(sp)
+    lw t0, 0(sp)
+    addiw t2, t0, 1
+    sw t2, 44(sp)
+    lw t2, 44(sp)
+    sw t2, 0(sp)
+    lw t0, 44(sp)
+    li t1, 10
+    bge t0, t1, .Lif_end12
+    j .Lloop
+.Lif_end12:
+    lw t0, 20(sp)
+    li t1, 100
+    mulw t2, t0, t1
+    sw t2, 48(sp)
+    lw t0, 28(sp)
+    li t1, 10
+    mulw t2, t0, t1
+    sw t2, 52(sp)
+    lw t0, 48(sp)
+    lw t1, 52(sp)
+    addw t2, t0, t1
+    sw t2, 56(sp)
+    lw t0, 56(sp)
+    lw t1, 40(sp)
+    addw t2, t0, t1
+    sw t2, 60(sp)
+    lw t0, 60(sp)
+    li t1, 256
+    remw t2, t0, t1
+    sw t2, 64(sp)
+    lw a0, 64(sp)
+    addi sp, s0, -16
+    ld ra, 8(sp)
+    ld s0, 0(sp)
+    addi sp, sp, 16
+    ret
+    .size main, .-main
+
+.section .note.GNU-stack,"",@progbits
@@ -0,0 +1,18 @@
+    .text
+    .globl main
+    .type main, @function
+    .p2align 2
+main:
+    addi sp, sp, -16
+    sd ra, 8(sp)
+    sd s0, 0(sp)
+    addi s0, sp, 16
+    li a0, 283
+    addi sp, s0, -16
+    ld ra, 8(sp)
+    ld s0, 0(sp)
+    addi sp, sp, 16
+    ret
+    .size main, .-main
+
+.section .note.GNU-stack,"",@progbits
@@ -0,0 +1,32 @@
+    .text
+    .globl main
+    .type main, @function
+    .p2align 2
+main:
+    addi sp, sp, -16
+    sd ra, 8(sp)
+    sd s0, 0(sp)
+    addi s0, sp, 16
+    addi sp, sp, -16
+    li t2, 0
+    sw t2, 0(sp)
+.Lentry:
+    lw t0, 0(sp)
+    addiw t2, t0, 1
+    sw t2, 4(sp)
+    lw t2, 4(sp)
+    sw t2, 0(sp)
+    lw t0, 4(sp)
+    li t1, 3
+    bge t0, t1, .Lif_end2
+    j .Lentry
+.Lif_end2:
+    lw a0, 4(sp)
+    addi sp, s0, -16
+    ld ra, 8(sp)
+    ld s0, 0(sp)
+    addi sp, sp, 16
+    ret
+    .size main, .-main
+
+.section .note.GNU-stack,"",@progbits
@@ -0,0 +1,50 @@
+    .text
+    .globl main
+    .type main, @function
+    .p2align 2
+main:
+    addi sp, sp, -16
+    sd ra, 8(sp)
+    sd s0, 0(sp)
+    addi s0, sp, 16
+    addi sp, sp, -32
+    li t2, 0
+    sw t2, 0(sp)
+    li t2, 1
+    sw t2, 4(sp)
+    li t2, 0
+    sw t2, 8(sp)
+.Lfib:
+    lw t0, 8(sp)
+    li t1, 20
+    blt t0, t1, .Lif_end4
+    j .Ldone
+.Lif_end4:
+    lw t0, 0(sp)
+    lw t1, 4(sp)
+    addw t2, t0, t1
+    sw t2, 12(sp)
+    lw t2, 4(sp)
+    sw t2, 0(sp)
+    lw t2, 12(sp)
+    sw t2, 4(sp)
+    lw t0, 8(sp)
+    addiw t2, t0, 1
+    sw t2, 16(sp)
+    lw t2, 16(sp)
+    sw t2, 8(sp)
+    j .Lfib
+.Ldone:
+    lw t0, 0(sp)
+    li t1, 251
+    remw t2, t0, t1
+    sw t2, 20(sp)
+    lw a0, 20(sp)
+    addi sp, s0, -16
+    ld ra, 8(sp)
+    ld s0, 0(sp)
+    addi sp, sp, 16
+    ret
+    .size main, .-main
+
+.section .note.GNU-stack,"",@progbits
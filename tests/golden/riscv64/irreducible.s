@@ -0,0 +1,60 @@
+    .text
+    .globl main
+    .type main, @function
+    .p2align 2
+main:
+    addi sp, sp, -16
+    sd ra, 8(sp)
+    sd s0, 0(sp)
+    addi s0, sp, 16
+    addi sp, sp, -32
+    li t2, 0
+    sw t2, 0(sp)
+    li t2, 3
+    sw t2, 4(sp)
+    j .Lb
+.La:
+    lw t0, 8(sp)
+    addiw t2, t0, 3
+    sw t2, 12(sp)
+    lw t2, 12(sp)
+    sw t2, 0(sp)
+    lw t0, 12(sp)
+    li t1, 40
+    ble t0, t1, .Lif_end5
+    j .Lend
+.Lif_end5:
+.Lb:
+    lw t0, 0(sp)
+    li t1, 2
+    mulw t2, t0, t1
+    sw t2, 16(sp)
+    lw t0, 16(sp)
+    addiw t2, t0, 1
+    sw t2, 8(sp)
+    lw t2, 8(sp)
+    sw t2, 0(sp)
+    lw t0, 8(sp)
+    li t1, 30
+    bge t0, t1, .Lif_end9
+    j .La
+.Lif_end9:
+    lw t0, 4(sp)
+    addiw t2, t0, -1
+    sw t2, 20(sp)
+    lw t2, 20(sp)
+    sw t2, 4(sp)
+    lw t0, 20(sp)
+    beq t0, zero, .Lif_end12
+    j .La
+.Lif_end12:
+.Lend:
+    lw a0, 0(sp)
+    addi sp, s0, -16
+    ld ra, 8(sp)
+    ld s0, 0(sp)
+    addi sp, sp, 16
+    ret
+    .size main, .-main
+
+.section .note.GNU-stack,"",@progbits
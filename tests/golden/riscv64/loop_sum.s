@@ -0,0 +1,46 @@
+    .text
+    .globl main
+    .type main, @function
+    .p2align 2
+main:
+    addi sp, sp, -16
+    sd ra, 8(sp)
+    sd s0, 0(sp)
+    addi s0, sp, 16
+    addi sp, sp, -32
+    li t2, 0
+    sw t2, 0(sp)
+    li t2, 0
+    sw t2, 4(sp)
+.Lloop:
+    lw t0, 0(sp)
+    li t1, 10
+    bge t0, t1, .Lif_end2
+    lw t0, 0(sp)
+    lw t1, 0(sp)
+    mulw t2, t0, t1
+    sw t2, 8(sp)
+    lw t0, 4(sp)
+    lw t1, 8(sp)
+    addw t2, t0, t1
+    sw t2, 12(sp)
+    lw t2, 12(sp)
+    sw t2, 4(sp)
+    lw t0, 0(sp)
+    addiw t2, t0, 1
+    sw t2, 0(sp)
+    j .Lloop
+.Lif_end2:
+    lw t0, 4(sp)
+    li t1, 256
+    remw t2, t0, t1
+    sw t2, 16(sp)
+    lw a0, 16(sp)
+    addi sp, s0, -16
+    ld ra, 8(sp)
+    ld s0, 0(sp)
+    addi sp, sp, 16
+    ret
+    .size main, .-main
+
+.section .note.GNU-stack,"",@progbits
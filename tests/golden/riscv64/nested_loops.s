@@ -0,0 +1,68 @@
+    .text
+    .globl main
+    .type main, @function
+    .p2align 2
+main:
+    addi sp, sp, -16
+    sd ra, 8(sp)
+    sd s0, 0(sp)
+    addi s0, sp, 16
+    addi sp, sp, -48
+    li t2, 0
+    sw t2, 0(sp)
+    li t2, 1
+    sw t2, 4(sp)
+.Ltop:
+    li t0, 10
+    lw t1, 4(sp)
+    mulw t2, t0, t1
+    sw t2, 8(sp)
+    lw t0, 0(sp)
+    lw t1, 8(sp)
+    addw t2, t0, t1
+    sw t2, 12(sp)
+    lw t2, 12(sp)
+    sw t2, 0(sp)
+    lw t0, 4(sp)
+    addiw t2, t0, 1
+    sw t2, 16(sp)
+    lw t2, 16(sp)
+    sw t2, 4(sp)
+    lw t0, 16(sp)
+    li t1, 5
+    bge t0, t1, .Lif_end9
+    j .Ltop
+.Lif_end9:
+    li t2, 0
+    sw t2, 20(sp)
+    li t2, 100
+    sw t2, 24(sp)
+.Lagain:
+    lw t0, 24(sp)
+    ble t0, zero, .Lif_end11
+    lw t0, 24(sp)
+    li t1, 3
+    divw t2, t0, t1
+    sw t2, 28(sp)
+    lw t2, 28(sp)
+    sw t2, 24(sp)
+    lw t0, 20(sp)
+    addiw t2, t0, 1
+    sw t2, 32(sp)
+    lw t2, 32(sp)
+    sw t2, 20(sp)
+    j .Lagain
+.Lif_end11:
+    lw t0, 12(sp)
+    lw t1, 20(sp)
+    addw t2, t0, t1
+    sw t2, 36(sp)
+    lw a0, 36(sp)
+    addi sp, s0, -16
+    ld ra, 8(sp)
+    ld s0, 0(sp)
+    addi sp, sp, 16
+    ret
+    .size main, .-main
+
+.section .note.GNU-stack,"",@progbits
@@ -0,0 +1,514 @@
+    .text
+    .globl main
+    .type main, @function
+    .p2align 2
+main:
+    addi sp, sp, -16
+    sd ra, 8(sp)
+    sd s0, 0(sp)
+    addi s0, sp, 16
+    addi sp, sp, -512
+    li t2, 3
+    sw t2, 0(sp)
+    li t2, 10
+    sw t2, 4(sp)
+    li t2, 17
+    sw t2, 8(sp)
+    li t2, 24
+    sw t2, 12(sp)
+    li t2, 31
+    sw t2, 16(sp)
+    li t2, 38
+    sw t2, 20(sp)
+    li t2, 45
+    sw t2, 24(sp)
+    li t2, 52
+    sw t2, 28(sp)
+    li t2, 59
+    sw t2, 32(sp)
+    li t2, 66
+    sw t2, 36(sp)
+    li t2, 73
+    sw t2, 40(sp)
+    li t2, 80
+    sw t2, 44(sp)
+    li t2, 87
+    sw t2, 48(sp)
+    li t2, 94
+    sw t2, 52(sp)
+    li t2, 101
+    sw t2, 56(sp)
+    li t2, 108
+    sw t2, 60(sp)
+    li t2, 115
+    sw t2, 64(sp)
+    li t2, 122
+    sw t2, 68(sp)
+    li t2, 129
+    sw t2, 72(sp)
+    li t2, 136
+    sw t2, 76(sp)
+    li t2, 0
+    sw t2, 80(sp)
+.Lloop:
+    lw t0, 80(sp)
+    li t1, 3
+    bge t0, t1, .Lif_end22
+    lw t0, 0(sp)
+    li t1, 3
+    mulw t2, t0, t1
+    sw t2, 84(sp)
+    lw t0, 84(sp)
+    lw t1, 4(sp)
+    addw t2, t0, t1
+    sw t2, 88(sp)
+    lw t0, 88(sp)
+    lw t1, 80(sp)
+    subw t2, t0, t1
+    sw t2, 92(sp)
+    lw t2, 92(sp)
+    sw t2, 0(sp)
+    lw t0, 4(sp)
+    li t1, 3
+    mulw t2, t0, t1
+    sw t2, 96(sp)
+    lw t0, 96(sp)
+    lw t1, 8(sp)
+    addw t2, t0, t1
+    sw t2, 100(sp)
+    lw t0, 100(sp)
+    lw t1, 80(sp)
+    subw t2, t0, t1
+    sw t2, 104(sp)
+    lw t2, 104(sp)
+    sw t2, 4(sp)
+    lw t0, 8(sp)
+    li t1, 3
+    mulw t2, t0, t1
+    sw t2, 108(sp)
+    lw t0, 108(sp)
+    lw t1, 12(sp)
+    addw t2, t0, t1
+    sw t2, 112(sp)
+    lw t0, 112(sp)
+    lw t1, 80(sp)
+    subw t2, t0, t1
+    sw t2, 116(sp)
+    lw t2, 116(sp)
+    sw t2, 8(sp)
+    lw t0, 12(sp)
+    li t1, 3
+    mulw t2, t0, t1
+    sw t2, 120(sp)
+    lw t0, 120(sp)
+    lw t1, 16(sp)
+    addw t2, t0, t1
+    sw t2, 124(sp)
+    lw t0, 124(sp)
+    lw t1, 80(sp)
+    subw t2, t0, t1
+    sw t2, 128(sp)
+    lw t2, 128(sp)
+    sw t2, 12(sp)
+    lw t0, 16(sp)
+    li t1, 3
+    mulw t2, t0, t1
+    sw t2, 132(sp)
+    lw t0, 132(sp)
+    lw t1, 20(sp)
+    addw t2, t0, t1
+    sw t2, 136(sp)
+    lw t0, 136(sp)
+    lw t1, 80(sp)
+    subw t2, t0, t1
+    sw t2, 140(sp)
+    lw t2, 140(sp)
+    sw t2, 16(sp)
+    lw t0, 20(sp)
+    li t1, 3
+    mulw t2, t0, t1
+    sw t2, 144(sp)
+    lw t0, 144(sp)
+    lw t1, 24(sp)
+    addw t2, t0, t1
+    sw t2, 148(sp)
+    lw t0, 148(sp)
+    lw t1, 80(sp)
+    subw t2, t0, t1
+    sw t2, 152(sp)
+    lw t2, 152(sp)
+    sw t2, 20(sp)
+    lw t0, 24(sp)
+    li t1, 3
+    mulw t2, t0, t1
+    sw t2, 156(sp)
+    lw t0, 156(sp)
+    lw t1, 28(sp)
+    addw t2, t0, t1
+    sw t2, 160(sp)
+    lw t0, 160(sp)
+    lw t1, 80(sp)
+    subw t2, t0, t1
+    sw t2, 164(sp)
+    lw t2, 164(sp)
+    sw t2, 24(sp)
+    lw t0, 28(sp)
+    li t1, 3
+    mulw t2, t0, t1
+    sw t2, 168(sp)
+    lw t0, 168(sp)
+    lw t1, 32(sp)
+    addw t2, t0, t1
+    sw t2, 172(sp)
+    lw t0, 172(sp)
+    lw t1, 80(sp)
+    subw t2, t0, t1
+    sw t2, 176(sp)
+    lw t2, 176(sp)
+    sw t2, 28(sp)
+    lw t0, 32(sp)
+    li t1, 3
+    mulw t2, t0, t1
+    sw t2, 180(sp)
+    lw t0, 180(sp)
+    lw t1, 36(sp)
+    addw t2, t0, t1
+    sw t2, 184(sp)
+    lw t0, 184(sp)
+    lw t1, 80(sp)
+    subw t2, t0, t1
+    sw t2, 188(sp)
+    lw t2, 188(sp)
+    sw t2, 32(sp)
+    lw t0, 36(sp)
+    li t1, 3
+    mulw t2, t0, t1
+    sw t2, 192(sp)
+    lw t0, 192(sp)
+    lw t1, 40(sp)
+    addw t2, t0, t1
+    sw t2, 196(sp)
+    lw t0, 196(sp)
+    lw t1, 80(sp)
+    subw t2, t0, t1
+    sw t2, 200(sp)
+    lw t2, 200(sp)
+    sw t2, 36(sp)
+    lw t0, 40(sp)
+    li t1, 3
+    mulw t2, t0, t1
+    sw t2, 204(sp)
+    lw t0, 204(sp)
+    lw t1, 44(sp)
+    addw t2, t0, t1
+    sw t2, 208(sp)
+    lw t0, 208(sp)
+    lw t1, 80(sp)
+    subw t2, t0, t1
+    sw t2, 212(sp)
+    lw t2, 212(sp)
+    sw t2, 40(sp)
+    lw t0, 44(sp)
+    li t1, 3
+    mulw t2, t0, t1
+    sw t2, 216(sp)
+    lw t0, 216(sp)
+    lw t1, 48(sp)
+    addw t2, t0, t1
+    sw t2, 220(sp)
+    lw t0, 220(sp)
+    lw t1, 80(sp)
+    subw t2, t0, t1
+    sw t2, 224(sp)
+    lw t2, 224(sp)
+    sw t2, 44(sp)
+    lw t0, 48(sp)
+    li t1, 3
+    mulw t2, t0, t1
+    sw t2, 228(sp)
+    lw t0, 228(sp)
+    lw t1, 52(sp)
+    addw t2, t0, t1
+    sw t2, 232(sp)
+    lw t0, 232(sp)
+    lw t1, 80(sp)
+    subw t2, t0, t1
+    sw t2, 236(sp)
+    lw t2, 236(sp)
+    sw t2, 48(sp)
+    lw t0, 52(sp)
+    li t1, 3
+    mulw t2, t0, t1
+    sw t2, 240(sp)
+    lw t0, 240(sp)
+    lw t1, 56(sp)
+    addw t2, t0, t1
+    sw t2, 244(sp)
+    lw t0, 244(sp)
+    lw t1, 80(sp)
+    subw t2, t0, t1
+    sw t2, 248(sp)
+    lw t2, 248(sp)
+    sw t2, 52(sp)
+    lw t0, 56(sp)
+    li t1, 3
+    mulw t2, t0, t1
+    sw t2, 252(sp)
+    lw t0, 252(sp)
+    lw t1, 60(sp)
+    addw t2, t0, t1
+    sw t2, 256(sp)
+    lw t0, 256(sp)
+    lw t1, 80(sp)
+    subw t2, t0, t1
+    sw t2, 260(sp)
+    lw t2, 260(sp)
+    sw t2, 56(sp)
+    lw t0, 60(sp)
+    li t1, 3
+    mulw t2, t0, t1
+    sw t2, 264(sp)
+    lw t0, 264(sp)
+    lw t1, 64(sp)
+    addw t2, t0, t1
+    sw t2, 268(sp)
+    lw t0, 268(sp)
+    lw t1, 80(sp)
+    subw t2, t0, t1
+    sw t2, 272(sp)
+    lw t2, 272(sp)
+    sw t2, 60(sp)
+    lw t0, 64(sp)
+    li t1, 3
+    mulw t2, t0, t1
+    sw t2, 276(sp)
+    lw t0, 276(sp)
+    lw t1, 68(sp)
+    addw t2, t0, t1
+    sw t2, 280(sp)
+    lw t0, 280(sp)
+    lw t1, 80(sp)
+    subw t2, t0, t1
+    sw t2, 284(sp)
+    lw t2, 284(sp)
+    sw t2, 64(sp)
+    lw t0, 68(sp)
+    li t1, 3
+    mulw t2, t0, t1
+    sw t2, 288(sp)
+    lw t0, 288(sp)
+    lw t1, 72(sp)
+    addw t2, t0, t1
+    sw t2, 292(sp)
+    lw t0, 292(sp)
+    lw t1, 80(sp)
+    subw t2, t0, t1
+    sw t2, 296(sp)
+    lw t2, 296(sp)
+    sw t2, 68(sp)
+    lw t0, 72(sp)
+    li t1, 3
+    mulw t2, t0, t1
+    sw t2, 300(sp)
+    lw t0, 300(sp)
+    lw t1, 76(sp)
+    addw t2, t0, t1
+    sw t2, 304(sp)
+    lw t0, 304(sp)
+    lw t1, 80(sp)
+    subw t2, t0, t1
+    sw t2, 308(sp)
+    lw t2, 308(sp)
+    sw t2, 72(sp)
+    lw t0, 76(sp)
+    li t1, 3
+    mulw t2, t0, t1
+    sw t2, 312(sp)
+    lw t0, 312(sp)
+    lw t1, 92(sp)
+    addw t2, t0, t1
+    sw t2, 316(sp)
+    lw t0, 316(sp)
+    lw t1, 80(sp)
+    subw t2, t0, t1
+    sw t2, 320(sp)
+    lw t2, 320(sp)
+    sw t2, 76(sp)
+    lw t0, 80(sp)
+    addiw t2, t0, 1
+    sw t2, 324(sp)
+    lw t2, 324(sp)
+    sw t2, 80(sp)
+    j .Lloop
+.Lif_end22:
+    lw t0, 4(sp)
+    addiw t2, t0, 1
+    sw t2, 328(sp)
+    lw t0, 0(sp)
+    lw t1, 328(sp)
+    xor t2, t0, t1
+    sw t2, 332(sp)
+    lw t0, 8(sp)
+    addiw t2, t0, 2
+    sw t2, 336(sp)
+    lw t0, 332(sp)
+    lw t1, 336(sp)
+    xor t2, t0, t1
+    sw t2, 340(sp)
+    lw t0, 12(sp)
+    addiw t2, t0, 3
+    sw t2, 344(sp)
+    lw t0, 340(sp)
+    lw t1, 344(sp)
+    xor t2, t0, t1
+    sw t2, 348(sp)
+    lw t0, 16(sp)
+    addiw t2, t0, 4
+    sw t2, 352(sp)
+    lw t0, 348(sp)
+    lw t1, 352(sp)
+    xor t2, t0, t1
+    sw t2, 356(sp)
+    lw t0, 20(sp)
+    addiw t2, t0, 5
+    sw t2, 360(sp)
+    lw t0, 356(sp)
+    lw t1, 360(sp)
+    xor t2, t0, t1
+    sw t2, 364(sp)
+    lw t0, 24(sp)
+    addiw t2, t0, 6
+    sw t2, 368(sp)
+    lw t0, 364(sp)
+    lw t1, 368(sp)
+    xor t2, t0, t1
+    sw t2, 372(sp)
+    lw t0, 28(sp)
+    addiw t2, t0, 7
+    sw t2, 376(sp)
+    lw t0, 372(sp)
+    lw t1, 376(sp)
+    xor t2, t0, t1
+    sw t2, 380(sp)
+    lw t0, 32(sp)
+    addiw t2, t0, 8
+    sw t2, 384(sp)
+    lw t0, 380(sp)
+    lw t1, 384(sp)
+    xor t2, t0, t1
+    sw t2, 388(sp)
+    lw t0, 36(sp)
+    addiw t2, t0, 9
+    sw t2, 392(sp)
+    lw t0, 388(sp)
+    lw t1, 392(sp)
+    xor t2, t0, t1
+    sw t2, 396(sp)
+    lw t0, 40(sp)
+    addiw t2, t0, 10
+    sw t2, 400(sp)
+    lw t0, 396(sp)
+    lw t1, 400(sp)
+    xor t2, t0, t1
+    sw t2, 404(sp)
+    lw t0, 44(sp)
+    addiw t2, t0, 11
+    sw t2, 408(sp)
+    lw t0, 404(sp)
+    lw t1, 408(sp)
+    xor t2, t0, t1
+    sw t2, 412(sp)
+    lw t0, 48(sp)
+    addiw t2, t0, 12
+    sw t2, 416(sp)
+    lw t0, 412(sp)
+    lw t1, 416(sp)
+    xor t2, t0, t1
+    sw t2, 420(sp)
+    lw t0, 52(sp)
+    addiw t2, t0, 13
+    sw t2, 424(sp)
+    lw t0, 420(sp)
+    lw t1, 424(sp)
+    xor t2, t0, t1
+    sw t2, 428(sp)
+    lw t0, 56(sp)
+    addiw t2, t0, 14
+    sw t2, 432(sp)
+    lw t0, 428(sp)
+    lw t1, 432(sp)
+    xor t2, t0, t1
+    sw t2, 436(sp)
+    lw t0, 60(sp)
+    addiw t2, t0, 15
+    sw t2, 440(sp)
+    lw t0, 436(sp)
+    lw t1, 440(sp)
+    xor t2, t0, t1
+    sw t2, 444(sp)
+    lw t0, 64(sp)
+    addiw t2, t0, 16
+    sw t2, 448(sp)
+    lw t0, 444(sp)
+    lw t1, 448(sp)
+    xor t2, t0, t1
+    sw t2, 452(sp)
+    lw t0, 68(sp)
+    addiw t2, t0, 17
+    sw t2, 456(sp)
+    lw t0, 452(sp)
+    lw t1, 456(sp)
+    xor t2, t0, t1
+    sw t2, 460(sp)
+    lw t0, 72(sp)
+    addiw t2, t0, 18
+    sw t2, 464(sp)
+    lw t0, 460(sp)
+    lw t1, 464(sp)
+    xor t2, t0, t1
+    sw t2, 468(sp)
+    lw t0, 76(sp)
+    addiw t2, t0, 19
+    sw t2, 472(sp)
+    lw t0, 468(sp)
+    lw t1, 472(sp)
+    xor t2, t0, t1
+    sw t2, 476(sp)
+    lw t0, 476(sp)
+    li t1, 7
+    divw t2, t0, t1
+    sw t2, 480(sp)
+    lw t0, 476(sp)
+    li t1, 13
+    remw t2, t0, t1
+    sw t2, 484(sp)
+    lw t0, 480(sp)
+    lw t1, 484(sp)
+    addw t2, t0, t1
+    sw t2, 488(sp)
+    lw t0, 476(sp)
+    slliw t2, t0, 2
+    sw t2, 492(sp)
+    lw t0, 488(sp)
+    lw t1, 492(sp)
+    addw t2, t0, t1
+    sw t2, 496(sp)
+    lw t0, 476(sp)
+    sraiw t2, t0, 3
+    sw t2, 500(sp)
+    lw t0, 496(sp)
+    lw t1, 500(sp)
+    addw t2, t0, t1
+    sw t2, 504(sp)
+    lw t0, 504(sp)
+    andi t2, t0, 255
+    sw t2, 508(sp)
+    lw a0, 508(sp)
+    addi sp, s0, -16
+    ld ra, 8(sp)
+    ld s0, 0(sp)
+    addi sp, sp, 16
+    ret
+    .size main, .-main
+
+.section .note.GNU-stack,"",@progbits
@@ -0,0 +1,84 @@
+    .text
+    .globl main
+    .type main, @function
+    .p2align 2
+main:
+    addi sp, sp, -16
+    sd ra, 8(sp)
+    sd s0, 0(sp)
+    addi s0, sp, 16
+    addi sp, sp, -64
+    li t2, 1
+    sw t2, 0(sp)
+    li t2, 2
+    sw t2, 4(sp)
+    li t2, 3
+    sw t2, 8(sp)
+    li t2, 0
+    sw t2, 12(sp)
+.Lloop:
+    lw t0, 12(sp)
+    li t1, 5
+    bge t0, t1, .Lif_end6
+    lw t2, 0(sp)
+    sw t2, 16(sp)
+    lw t2, 4(sp)
+    sw t2, 0(sp)
+    lw t2, 8(sp)
+    sw t2, 4(sp)
+    lw t2, 16(sp)
+    sw t2, 8(sp)
+    lw t0, 12(sp)
+    addiw t2, t0, 1
+    sw t2, 20(sp)
+    lw t2, 20(sp)
+    sw t2, 12(sp)
+    j .Lloop
+.Lif_end6:
+    lw t0, 0(sp)
+    lw t1, 4(sp)
+    subw t2, t0, t1
+    sw t2, 24(sp)
+    lw t0, 4(sp)
+    lw t1, 0(sp)
+    subw t2, t0, t1
+    sw t2, 28(sp)
+    lw t0, 4(sp)
+    lw t1, 0(sp)
+    slt t2, t0, t1
+    sw t2, 32(sp)
+    lw t0, 32(sp)
+    lw t2, 24(sp)
+    bne t0, zero, .L.select.0
+    lw t2, 28(sp)
+.L.select.0:
+    sw t2, 36(sp)
+    lw t0, 0(sp)
+    li t1, 100
+    mulw t2, t0, t1
+    sw t2, 40(sp)
+    lw t0, 4(sp)
+    li t1, 10
+    mulw t2, t0, t1
+    sw t2, 44(sp)
+    lw t0, 40(sp)
+    lw t1, 44(sp)
+    addw t2, t0, t1
+    sw t2, 48(sp)
+    lw t0, 48(sp)
+    lw t1, 8(sp)
+    addw t2, t0, t1
+    sw t2, 52(sp)
+    lw t0, 52(sp)
+    lw t1, 36(sp)
+    addw t2, t0, t1
+    sw t2, 56(sp)
+    lw a0, 56(sp)
+    addi sp, s0, -16
+    ld ra, 8(sp)
+    ld s0, 0(sp)
+    addi sp, sp, 16
+    ret
+    .size main, .-main
+
+.section .note.GNU-stack,"",@progbits
@@ -0,0 +1,101 @@
+(module
+  (func $main (export "main") (result i32)
+    (local $i.0 i32)
+    (local $less.1 i32)
+    (local $equal.2 i32)
+    (local $greater.3 i32)
+    (local $temp.4 i32)
+    (local $temp.5 i32)
+    (local $temp.6 i32)
+    (local $temp.7 i32)
+    (local $temp.8 i32)
+    (local $temp.9 i32)
+    (local $temp.10 i32)
+    (local $temp.11 i32)
+    (local $temp.13 i32)
+    (local $temp.14 i32)
+    (local $temp.15 i32)
+    (local $temp.16 i32)
+    (local $temp.17 i32)
+    (local $temp.18 i32)
+    i32.const 0
+    local.set $i.0
+    i32.const 0
+    local.set $less.1
+    i32.const 0
+    local.set $equal.2
+    i32.const 0
+    local.set $greater.3
+    loop $loop.1
+      local.get $i.0
+      i32.const 4
+      i32.lt_s
+      local.set $temp.4
+      local.get $less.1
+      local.get $temp.4
+      i32.add
+      local.set $temp.5
+      local.get $temp.5
+      local.set $less.1
+      local.get $i.0
+      i32.const 4
+      i32.eq
+      local.set $temp.6
+      local.get $equal.2
+      local.get $temp.6
+      i32.add
+      local.set $temp.7
+      local.get $temp.7
+      local.set $equal.2
+      local.get $i.0
+      i32.const 4
+      i32.gt_s
+      local.set $temp.8
+      local.get $temp.8
+      i32.const 3
+      i32.mul
+      local.set $temp.9
+      local.get $greater.3
+      local.get $temp.9
+      i32.add
+      local.set $temp.10
+      local.get $temp.10
+      local.set $greater.3
+      local.get $i.0
+      i32.const 1
+      i32.add
+      local.set $temp.11
+      local.get $temp.11
+      local.set $i.0
+      local.get $temp.11
+      i32.const 10
+      i32.lt_s
+      local.set $temp.13
+      local.get $temp.13
+      br_if $loop.1
+      local.get $temp.5
+      i32.const 100
+      i32.mul
+      local.set $temp.14
+      local.get $temp.7
+      i32.const 10
+      i32.mul
+      local.set $temp.15
+      local.get $temp.14
+      local.get $temp.15
+      i32.add
+      local.set $temp.16
+      local.get $temp.16
+      local.get $temp.10
+      i32.add
+      local.set $temp.17
+      local.get $temp.17
+      i32.const 256
+      i32.rem_s
+      local.set $temp.18
+      local.get $temp.18
+      return
+    end
+    unreachable
+  )
+)
@@ -0,0 +1,6 @@
+(module
+  (func $main (export "main") (result i32)
+    i32.const 107
+    return
+  )
+)
@@ -0,0 +1,26 @@
+(module
+  (func $main (export "main") (result i32)
+    (local $x.0 i32)
+    (local $temp.1 i32)
+    (local $temp.3 i32)
+    i32.const 0
+    local.set $x.0
+    loop $loop.1
+      local.get $x.0
+      i32.const 1
+      i32.add
+      local.set $temp.1
+      local.get $temp.1
+      local.set $x.0
+      local.get $temp.1
+      i32.const 3
+      i32.lt_s
+      local.set $temp.3
+      local.get $temp.3
+      br_if $loop.1
+      local.get $temp.1
+      return
+    end
+    unreachable
+  )
+)
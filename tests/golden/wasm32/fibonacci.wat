@@ -0,0 +1,49 @@
+(module
+  (func $main (export "main") (result i32)
+    (local $a.0 i32)
+    (local $b.1 i32)
+    (local $i.2 i32)
+    (local $temp.5 i32)
+    (local $temp.6 i32)
+    (local $temp.7 i32)
+    (local $temp.8 i32)
+    i32.const 0
+    local.set $a.0
+    i32.const 1
+    local.set $b.1
+    i32.const 0
+    local.set $i.2
+    loop $loop.1
+      local.get $i.2
+      i32.const 20
+      i32.ge_s
+      local.set $temp.5
+      local.get $temp.5
+      if
+        local.get $a.0
+        i32.const 251
+        i32.rem_s
+        local.set $temp.8
+        local.get $temp.8
+        return
+      else
+        local.get $a.0
+        local.get $b.1
+        i32.add
+        local.set $temp.6
+        local.get $b.1
+        local.set $a.0
+        local.get $temp.6
+        local.set $b.1
+        local.get $i.2
+        i32.const 1
+        i32.add
+        local.set $temp.7
+        local.get $temp.7
+        local.set $i.2
+        br $loop.1
+      end
+    end
+    unreachable
+  )
+)
@@ -0,0 +1,65 @@
+(module
+  (func $main (export "main") (result i32)
+    (local $n.0 i32)
+    (local $k.1 i32)
+    (local $temp.8 i32)
+    (local $temp.4 i32)
+    (local $temp.6 i32)
+    (local $temp.7 i32)
+    (local $temp.10 i32)
+    (local $temp.11 i32)
+    i32.const 0
+    local.set $n.0
+    i32.const 3
+    local.set $k.1
+    loop $loop.4
+      block $block.9
+        block $block.1
+          local.get $n.0
+          i32.const 2
+          i32.mul
+          local.set $temp.7
+          local.get $temp.7
+          i32.const 1
+          i32.add
+          local.set $temp.8
+          local.get $temp.8
+          local.set $n.0
+          local.get $temp.8
+          i32.const 30
+          i32.lt_s
+          local.set $temp.10
+          local.get $temp.10
+          br_if $block.1
+          local.get $k.1
+          i32.const 1
+          i32.sub
+          local.set $temp.11
+          local.get $temp.11
+          local.set $k.1
+          local.get $temp.11
+          i32.eqz
+          br_if $block.9
+          br $block.1
+        end
+        local.get $temp.8
+        i32.const 3
+        i32.add
+        local.set $temp.4
+        local.get $temp.4
+        local.set $n.0
+        local.get $temp.4
+        i32.const 40
+        i32.gt_s
+        local.set $temp.6
+        local.get $temp.6
+        i32.eqz
+        br_if $loop.4
+        br $block.9
+      end
+      local.get $n.0
+      return
+    end
+    unreachable
+  )
+)
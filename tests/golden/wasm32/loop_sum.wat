@@ -0,0 +1,46 @@
+(module
+  (func $main (export "main") (result i32)
+    (local $i.0 i32)
+    (local $sum.1 i32)
+    (local $temp.3 i32)
+    (local $temp.4 i32)
+    (local $temp.5 i32)
+    (local $temp.7 i32)
+    i32.const 0
+    local.set $i.0
+    i32.const 0
+    local.set $sum.1
+    loop $loop.1
+      local.get $i.0
+      i32.const 10
+      i32.lt_s
+      local.set $temp.3
+      local.get $temp.3
+      if
+        local.get $i.0
+        local.get $i.0
+        i32.mul
+        local.set $temp.4
+        local.get $sum.1
+        local.get $temp.4
+        i32.add
+        local.set $temp.5
+        local.get $temp.5
+        local.set $sum.1
+        local.get $i.0
+        i32.const 1
+        i32.add
+        local.set $i.0
+        br $loop.1
+      else
+        local.get $sum.1
+        i32.const 256
+        i32.rem_s
+        local.set $temp.7
+        local.get $temp.7
+        return
+      end
+    end
+    unreachable
+  )
+)
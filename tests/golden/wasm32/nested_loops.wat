@@ -0,0 +1,78 @@
+(module
+  (func $main (export "main") (result i32)
+    (local $y.1 i32)
+    (local $k.2 i32)
+    (local $temp.6 i32)
+    (local $temp.7 i32)
+    (local $temp.8 i32)
+    (local $temp.10 i32)
+    (local $n.4 i32)
+    (local $m.5 i32)
+    (local $temp.12 i32)
+    (local $temp.13 i32)
+    (local $temp.14 i32)
+    (local $temp.15 i32)
+    i32.const 0
+    local.set $y.1
+    i32.const 1
+    local.set $k.2
+    loop $loop.1
+      i32.const 10
+      local.get $k.2
+      i32.mul
+      local.set $temp.6
+      local.get $y.1
+      local.get $temp.6
+      i32.add
+      local.set $temp.7
+      local.get $temp.7
+      local.set $y.1
+      local.get $k.2
+      i32.const 1
+      i32.add
+      local.set $temp.8
+      local.get $temp.8
+      local.set $k.2
+      local.get $temp.8
+      i32.const 5
+      i32.lt_s
+      local.set $temp.10
+      local.get $temp.10
+      br_if $loop.1
+      i32.const 0
+      local.set $n.4
+      i32.const 100
+      local.set $m.5
+      loop $loop.4
+        local.get $m.5
+        i32.const 0
+        i32.gt_s
+        local.set $temp.12
+        local.get $temp.12
+        if
+          local.get $m.5
+          i32.const 3
+          i32.div_s
+          local.set $temp.13
+          local.get $temp.13
+          local.set $m.5
+          local.get $n.4
+          i32.const 1
+          i32.add
+          local.set $temp.14
+          local.get $temp.14
+          local.set $n.4
+          br $loop.4
+        else
+          local.get $temp.7
+          local.get $n.4
+          i32.add
+          local.set $temp.15
+          local.get $temp.15
+          return
+        end
+      end
+    end
+    unreachable
+  )
+)
@@ -0,0 +1,659 @@
+(module
+  (func $main (export "main") (result i32)
+    (local $v0.0 i32)
+    (local $v1.1 i32)
+    (local $v2.2 i32)
+    (local $v3.3 i32)
+    (local $v4.4 i32)
+    (local $v5.5 i32)
+    (local $v6.6 i32)
+    (local $v7.7 i32)
+    (local $v8.8 i32)
+    (local $v9.9 i32)
+    (local $v10.10 i32)
+    (local $v11.11 i32)
+    (local $v12.12 i32)
+    (local $v13.13 i32)
+    (local $v14.14 i32)
+    (local $v15.15 i32)
+    (local $v16.16 i32)
+    (local $v17.17 i32)
+    (local $v18.18 i32)
+    (local $v19.19 i32)
+    (local $k.20 i32)
+    (local $temp.23 i32)
+    (local $temp.24 i32)
+    (local $temp.25 i32)
+    (local $temp.26 i32)
+    (local $temp.27 i32)
+    (local $temp.28 i32)
+    (local $temp.29 i32)
+    (local $temp.30 i32)
+    (local $temp.31 i32)
+    (local $temp.32 i32)
+    (local $temp.33 i32)
+    (local $temp.34 i32)
+    (local $temp.35 i32)
+    (local $temp.36 i32)
+    (local $temp.37 i32)
+    (local $temp.38 i32)
+    (local $temp.39 i32)
+    (local $temp.40 i32)
+    (local $temp.41 i32)
+    (local $temp.42 i32)
+    (local $temp.43 i32)
+    (local $temp.44 i32)
+    (local $temp.45 i32)
+    (local $temp.46 i32)
+    (local $temp.47 i32)
+    (local $temp.48 i32)
+    (local $temp.49 i32)
+    (local $temp.50 i32)
+    (local $temp.51 i32)
+    (local $temp.52 i32)
+    (local $temp.53 i32)
+    (local $temp.54 i32)
+    (local $temp.55 i32)
+    (local $temp.56 i32)
+    (local $temp.57 i32)
+    (local $temp.58 i32)
+    (local $temp.59 i32)
+    (local $temp.60 i32)
+    (local $temp.61 i32)
+    (local $temp.62 i32)
+    (local $temp.63 i32)
+    (local $temp.64 i32)
+    (local $temp.65 i32)
+    (local $temp.66 i32)
+    (local $temp.67 i32)
+    (local $temp.68 i32)
+    (local $temp.69 i32)
+    (local $temp.70 i32)
+    (local $temp.71 i32)
+    (local $temp.72 i32)
+    (local $temp.73 i32)
+    (local $temp.74 i32)
+    (local $temp.75 i32)
+    (local $temp.76 i32)
+    (local $temp.77 i32)
+    (local $temp.78 i32)
+    (local $temp.79 i32)
+    (local $temp.80 i32)
+    (local $temp.81 i32)
+    (local $temp.82 i32)
+    (local $temp.83 i32)
+    (local $temp.84 i32)
+    (local $temp.87 i32)
+    (local $temp.88 i32)
+    (local $temp.89 i32)
+    (local $temp.90 i32)
+    (local $temp.91 i32)
+    (local $temp.92 i32)
+    (local $temp.93 i32)
+    (local $temp.94 i32)
+    (local $temp.95 i32)
+    (local $temp.96 i32)
+    (local $temp.97 i32)
+    (local $temp.98 i32)
+    (local $temp.99 i32)
+    (local $temp.100 i32)
+    (local $temp.101 i32)
+    (local $temp.102 i32)
+    (local $temp.103 i32)
+    (local $temp.104 i32)
+    (local $temp.105 i32)
+    (local $temp.106 i32)
+    (local $temp.107 i32)
+    (local $temp.108 i32)
+    (local $temp.109 i32)
+    (local $temp.110 i32)
+    (local $temp.111 i32)
+    (local $temp.112 i32)
+    (local $temp.113 i32)
+    (local $temp.114 i32)
+    (local $temp.115 i32)
+    (local $temp.116 i32)
+    (local $temp.117 i32)
+    (local $temp.118 i32)
+    (local $temp.119 i32)
+    (local $temp.120 i32)
+    (local $temp.121 i32)
+    (local $temp.122 i32)
+    (local $temp.123 i32)
+    (local $temp.124 i32)
+    (local $temp.125 i32)
+    (local $temp.126 i32)
+    (local $temp.127 i32)
+    (local $temp.128 i32)
+    (local $temp.129 i32)
+    (local $temp.130 i32)
+    (local $temp.131 i32)
+    (local $temp.132 i32)
+    i32.const 3
+    local.set $v0.0
+    i32.const 10
+    local.set $v1.1
+    i32.const 17
+    local.set $v2.2
+    i32.const 24
+    local.set $v3.3
+    i32.const 31
+    local.set $v4.4
+    i32.const 38
+    local.set $v5.5
+    i32.const 45
+    local.set $v6.6
+    i32.const 52
+    local.set $v7.7
+    i32.const 59
+    local.set $v8.8
+    i32.const 66
+    local.set $v9.9
+    i32.const 73
+    local.set $v10.10
+    i32.const 80
+    local.set $v11.11
+    i32.const 87
+    local.set $v12.12
+    i32.const 94
+    local.set $v13.13
+    i32.const 101
+    local.set $v14.14
+    i32.const 108
+    local.set $v15.15
+    i32.const 115
+    local.set $v16.16
+    i32.const 122
+    local.set $v17.17
+    i32.const 129
+    local.set $v18.18
+    i32.const 136
+    local.set $v19.19
+    i32.const 0
+    local.set $k.20
+    loop $loop.1
+      local.get $k.20
+      i32.const 3
+      i32.lt_s
+      local.set $temp.23
+      local.get $temp.23
+      if
+        local.get $v0.0
+        i32.const 3
+        i32.mul
+        local.set $temp.24
+        local.get $temp.24
+        local.get $v1.1
+        i32.add
+        local.set $temp.25
+        local.get $temp.25
+        local.get $k.20
+        i32.sub
+        local.set $temp.26
+        local.get $temp.26
+        local.set $v0.0
+        local.get $v1.1
+        i32.const 3
+        i32.mul
+        local.set $temp.27
+        local.get $temp.27
+        local.get $v2.2
+        i32.add
+        local.set $temp.28
+        local.get $temp.28
+        local.get $k.20
+        i32.sub
+        local.set $temp.29
+        local.get $temp.29
+        local.set $v1.1
+        local.get $v2.2
+        i32.const 3
+        i32.mul
+        local.set $temp.30
+        local.get $temp.30
+        local.get $v3.3
+        i32.add
+        local.set $temp.31
+        local.get $temp.31
+        local.get $k.20
+        i32.sub
+        local.set $temp.32
+        local.get $temp.32
+        local.set $v2.2
+        local.get $v3.3
+        i32.const 3
+        i32.mul
+        local.set $temp.33
+        local.get $temp.33
+        local.get $v4.4
+        i32.add
+        local.set $temp.34
+        local.get $temp.34
+        local.get $k.20
+        i32.sub
+        local.set $temp.35
+        local.get $temp.35
+        local.set $v3.3
+        local.get $v4.4
+        i32.const 3
+        i32.mul
+        local.set $temp.36
+        local.get $temp.36
+        local.get $v5.5
+        i32.add
+        local.set $temp.37
+        local.get $temp.37
+        local.get $k.20
+        i32.sub
+        local.set $temp.38
+        local.get $temp.38
+        local.set $v4.4
+        local.get $v5.5
+        i32.const 3
+        i32.mul
+        local.set $temp.39
+        local.get $temp.39
+        local.get $v6.6
+        i32.add
+        local.set $temp.40
+        local.get $temp.40
+        local.get $k.20
+        i32.sub
+        local.set $temp.41
+        local.get $temp.41
+        local.set $v5.5
+        local.get $v6.6
+        i32.const 3
+        i32.mul
+        local.set $temp.42
+        local.get $temp.42
+        local.get $v7.7
+        i32.add
+        local.set $temp.43
+        local.get $temp.43
+        local.get $k.20
+        i32.sub
+        local.set $temp.44
+        local.get $temp.44
+        local.set $v6.6
+        local.get $v7.7
+        i32.const 3
+        i32.mul
+        local.set $temp.45
+        local.get $temp.45
+        local.get $v8.8
+        i32.add
+        local.set $temp.46
+        local.get $temp.46
+        local.get $k.20
+        i32.sub
+        local.set $temp.47
+        local.get $temp.47
+        local.set $v7.7
+        local.get $v8.8
+        i32.const 3
+        i32.mul
+        local.set $temp.48
+        local.get $temp.48
+        local.get $v9.9
+        i32.add
+        local.set $temp.49
+        local.get $temp.49
+        local.get $k.20
+        i32.sub
+        local.set $temp.50
+        local.get $temp.50
+        local.set $v8.8
+        local.get $v9.9
+        i32.const 3
+        i32.mul
+        local.set $temp.51
+        local.get $temp.51
+        local.get $v10.10
+        i32.add
+        local.set $temp.52
+        local.get $temp.52
+        local.get $k.20
+        i32.sub
+        local.set $temp.53
+        local.get $temp.53
+        local.set $v9.9
+        local.get $v10.10
+        i32.const 3
+        i32.mul
+        local.set $temp.54
+        local.get $temp.54
+        local.get $v11.11
+        i32.add
+        local.set $temp.55
+        local.get $temp.55
+        local.get $k.20
+        i32.sub
+        local.set $temp.56
+        local.get $temp.56
+        local.set $v10.10
+        local.get $v11.11
+        i32.const 3
+        i32.mul
+        local.set $temp.57
+        local.get $temp.57
+        local.get $v12.12
+        i32.add
+        local.set $temp.58
+        local.get $temp.58
+        local.get $k.20
+        i32.sub
+        local.set $temp.59
+        local.get $temp.59
+        local.set $v11.11
+        local.get $v12.12
+        i32.const 3
+        i32.mul
+        local.set $temp.60
+        local.get $temp.60
+        local.get $v13.13
+        i32.add
+        local.set $temp.61
+        local.get $temp.61
+        local.get $k.20
+        i32.sub
+        local.set $temp.62
+        local.get $temp.62
+        local.set $v12.12
+        local.get $v13.13
+        i32.const 3
+        i32.mul
+        local.set $temp.63
+        local.get $temp.63
+        local.get $v14.14
+        i32.add
+        local.set $temp.64
+        local.get $temp.64
+        local.get $k.20
+        i32.sub
+        local.set $temp.65
+        local.get $temp.65
+        local.set $v13.13
+        local.get $v14.14
+        i32.const 3
+        i32.mul
+        local.set $temp.66
+        local.get $temp.66
+        local.get $v15.15
+        i32.add
+        local.set $temp.67
+        local.get $temp.67
+        local.get $k.20
+        i32.sub
+        local.set $temp.68
+        local.get $temp.68
+        local.set $v14.14
+        local.get $v15.15
+        i32.const 3
+        i32.mul
+        local.set $temp.69
+        local.get $temp.69
+        local.get $v16.16
+        i32.add
+        local.set $temp.70
+        local.get $temp.70
+        local.get $k.20
+        i32.sub
+        local.set $temp.71
+        local.get $temp.71
+        local.set $v15.15
+        local.get $v16.16
+        i32.const 3
+        i32.mul
+        local.set $temp.72
+        local.get $temp.72
+        local.get $v17.17
+        i32.add
+        local.set $temp.73
+        local.get $temp.73
+        local.get $k.20
+        i32.sub
+        local.set $temp.74
+        local.get $temp.74
+        local.set $v16.16
+        local.get $v17.17
+        i32.const 3
+        i32.mul
+        local.set $temp.75
+        local.get $temp.75
+        local.get $v18.18
+        i32.add
+        local.set $temp.76
+        local.get $temp.76
+        local.get $k.20
+        i32.sub
+        local.set $temp.77
+        local.get $temp.77
+        local.set $v17.17
+        local.get $v18.18
+        i32.const 3
+        i32.mul
+        local.set $temp.78
+        local.get $temp.78
+        local.get $v19.19
+        i32.add
+        local.set $temp.79
+        local.get $temp.79
+        local.get $k.20
+        i32.sub
+        local.set $temp.80
+        local.get $temp.80
+        local.set $v18.18
+        local.get $v19.19
+        i32.const 3
+        i32.mul
+        local.set $temp.81
+        local.get $temp.81
+        local.get $temp.26
+        i32.add
+        local.set $temp.82
+        local.get $temp.82
+        local.get $k.20
+        i32.sub
+        local.set $temp.83
+        local.get $temp.83
+        local.set $v19.19
+        local.get $k.20
+        i32.const 1
+        i32.add
+        local.set $temp.84
+        local.get $temp.84
+        local.set $k.20
+        br $loop.1
+      else
+        local.get $v1.1
+        i32.const 1
+        i32.add
+        local.set $temp.87
+        local.get $v0.0
+        local.get $temp.87
+        i32.xor
+        local.set $temp.88
+        local.get $v2.2
+        i32.const 2
+        i32.add
+        local.set $temp.89
+        local.get $temp.88
+        local.get $temp.89
+        i32.xor
+        local.set $temp.90
+        local.get $v3.3
+        i32.const 3
+        i32.add
+        local.set $temp.91
+        local.get $temp.90
+        local.get $temp.91
+        i32.xor
+        local.set $temp.92
+        local.get $v4.4
+        i32.const 4
+        i32.add
+        local.set $temp.93
+        local.get $temp.92
+        local.get $temp.93
+        i32.xor
+        local.set $temp.94
+        local.get $v5.5
+        i32.const 5
+        i32.add
+        local.set $temp.95
+        local.get $temp.94
+        local.get $temp.95
+        i32.xor
+        local.set $temp.96
+        local.get $v6.6
+        i32.const 6
+        i32.add
+        local.set $temp.97
+        local.get $temp.96
+        local.get $temp.97
+        i32.xor
+        local.set $temp.98
+        local.get $v7.7
+        i32.const 7
+        i32.add
+        local.set $temp.99
+        local.get $temp.98
+        local.get $temp.99
+        i32.xor
+        local.set $temp.100
+        local.get $v8.8
+        i32.const 8
+        i32.add
+        local.set $temp.101
+        local.get $temp.100
+        local.get $temp.101
+        i32.xor
+        local.set $temp.102
+        local.get $v9.9
+        i32.const 9
+        i32.add
+        local.set $temp.103
+        local.get $temp.102
+        local.get $temp.103
+        i32.xor
+        local.set $temp.104
+        local.get $v10.10
+        i32.const 10
+        i32.add
+        local.set $temp.105
+        local.get $temp.104
+        local.get $temp.105
+        i32.xor
+        local.set $temp.106
+        local.get $v11.11
+        i32.const 11
+        i32.add
+        local.set $temp.107
+        local.get $temp.106
+        local.get $temp.107
+        i32.xor
+        local.set $temp.108
+        local.get $v12.12
+        i32.const 12
+        i32.add
+        local.set $temp.109
+        local.get $temp.108
+        local.get $temp.109
+        i32.xor
+        local.set $temp.110
+        local.get $v13.13
+        i32.const 13
+        i32.add
+        local.set $temp.111
+        local.get $temp.110
+        local.get $temp.111
+        i32.xor
+        local.set $temp.112
+        local.get $v14.14
+        i32.const 14
+        i32.add
+        local.set $temp.113
+        local.get $temp.112
+        local.get $temp.113
+        i32.xor
+        local.set $temp.114
+        local.get $v15.15
+        i32.const 15
+        i32.add
+        local.set $temp.115
+        local.get $temp.114
+        local.get $temp.115
+        i32.xor
+        local.set $temp.116
+        local.get $v16.16
+        i32.const 16
+        i32.add
+        local.set $temp.117
+        local.get $temp.116
+        local.get $temp.117
+        i32.xor
+        local.set $temp.118
+        local.get $v17.17
+        i32.const 17
+        i32.add
+        local.set $temp.119
+        local.get $temp.118
+        local.get $temp.119
+        i32.xor
+        local.set $temp.120
+        local.get $v18.18
+        i32.const 18
+        i32.add
+        local.set $temp.121
+        local.get $temp.120
+        local.get $temp.121
+        i32.xor
+        local.set $temp.122
+        local.get $v19.19
+        i32.const 19
+        i32.add
+        local.set $temp.123
+        local.get $temp.122
+        local.get $temp.123
+        i32.xor
+        local.set $temp.124
+        local.get $temp.124
+        i32.const 7
+        i32.div_s
+        local.set $temp.125
+        local.get $temp.124
+        i32.const 13
+        i32.rem_s
+        local.set $temp.126
+        local.get $temp.125
+        local.get $temp.126
+        i32.add
+        local.set $temp.127
+        local.get $temp.124
+        i32.const 2
+        i32.shl
+        local.set $temp.128
+        local.get $temp.127
+        local.get $temp.128
+        i32.add
+        local.set $temp.129
+        local.get $temp.124
+        i32.const 3
+        i32.shr_s
+        local.set $temp.130
+        local.get $temp.129
+        local.get $temp.130
+        i32.add
+        local.set $temp.131
+        local.get $temp.131
+        i32.const 255
+        i32.and
+        local.set $temp.132
+        local.get $temp.132
+        return
+      end
+    end
+    unreachable
+  )
+)
@@ -0,0 +1,93 @@
+(module
+  (func $main (export "main") (result i32)
+    (local $a.0 i32)
+    (local $b.1 i32)
+    (local $c.2 i32)
+    (local $i.3 i32)
+    (local $temp.7 i32)
+    (local $t.4 i32)
+    (local $temp.8 i32)
+    (local $temp.12 i32)
+    (local $temp.13 i32)
+    (local $temp.11 i32)
+    (local $x.5 i32)
+    (local $temp.14 i32)
+    (local $temp.15 i32)
+    (local $temp.16 i32)
+    (local $temp.17 i32)
+    (local $temp.18 i32)
+    i32.const 1
+    local.set $a.0
+    i32.const 2
+    local.set $b.1
+    i32.const 3
+    local.set $c.2
+    i32.const 0
+    local.set $i.3
+    loop $loop.1
+      local.get $i.3
+      i32.const 5
+      i32.lt_s
+      local.set $temp.7
+      local.get $temp.7
+      if
+        local.get $a.0
+        local.set $t.4
+        local.get $b.1
+        local.set $a.0
+        local.get $c.2
+        local.set $b.1
+        local.get $t.4
+        local.set $c.2
+        local.get $i.3
+        i32.const 1
+        i32.add
+        local.set $temp.8
+        local.get $temp.8
+        local.set $i.3
+        br $loop.1
+      else
+        local.get $a.0
+        local.get $b.1
+        i32.sub
+        local.set $temp.12
+        local.get $b.1
+        local.get $a.0
+        i32.sub
+        local.set $temp.13
+        local.get $a.0
+        local.get $b.1
+        i32.gt_s
+        local.set $temp.11
+        local.get $temp.12
+        local.get $temp.13
+        local.get $temp.11
+        select
+        local.set $x.5
+        local.get $a.0
+        i32.const 100
+        i32.mul
+        local.set $temp.14
+        local.get $b.1
+        i32.const 10
+        i32.mul
+        local.set $temp.15
+        local.get $temp.14
+        local.get $temp.15
+        i32.add
+        local.set $temp.16
+        local.get $temp.16
+        local.get $c.2
+        i32.add
+        local.set $temp.17
+        local.get $temp.17
+        local.get $x.5
+        i32.add
+        local.set $temp.18
+        local.get $temp.18
+        return
+      end
+    end
+    unreachable
+  )
+)
@@ -0,0 +1,72 @@
+mod common;
+
+use common::Program;
+use comp::{
+    irc, llvm_emission,
+    session::{Options, Session, Stage},
+};
+
+fn emit(program: &Program, level: u8) -> String {
+    let session = Session::new(program.source.as_str(), Options::for_level(level));
+    let output = session.compile(Stage::Irc);
+    common::check(&output, &program.name);
+    let irc: &irc::Program = output.irc.as_ref().expect("the irc stage gives irc");
+    let mut text = Vec::new();
+    llvm_emission::emit_program(&mut text, irc).expect("writing to a Vec doesn't fail");
+    String::from_utf8(text).expect("the IR is UTF-8")
+}
+
+#[test]
+fn matches_golden_output() {
+    for program in common::programs() {
+        common::check_golden(&format!("llvm/{}.ll", program.name), &emit(&program, 2));
+    }
+}
+
+#[test]
+fn entry_block_is_apart_from_labels() {
+    let program = common::programs()
+        .into_iter()
+        .find(|program| program.name == "entry_label")
+        .expect("tests/programs/entry_label.c exists");
+    let ir = emit(&program, 0);
+    let blocks: Vec<_> = ir.lines().filter(|line| line.ends_with(':')).collect();
+    assert_eq!(blocks.first(), Some(&".entry:"));
+    assert_eq!(blocks.iter().filter(|&&block| block == "entry:").count(), 1);
+}
+
+#[test]
+fn runs_in_lli() {
+    if !common::has_tool("lli") {
+        return;
+    }
+    for program in common::programs() {
+        for level in 0..=2 {
+            let ir = emit(&program, level);
+            let output = common::run_tool("lli", &["-"], Some(&ir));
+            assert_eq!(
+                output.status.code(),
+                Some(program.expected as u8 as i32),
+                "{} at -O{level}:\n{}",
+                program.name,
+                String::from_utf8_lossy(&output.stderr)
+            );
+        }
+    }
+}
+
+#[test]
+fn compiles_with_llc() {
+    if !common::has_tool("llc") {
+        return;
+    }
+    for program in common::programs() {
+        for level in 0..=2 {
+            common::run_tool_ok(
+                "llc",
+                &["-O2", "-filetype=null", "-"],
+                Some(&emit(&program, level)),
+            );
+        }
+    }
+}
@@ -0,0 +1,90 @@
+mod common;
+
+use std::fs;
+
+use comp::session::Options;
+
+/// The `.text` section of the object file at `path`.
+fn text_section(path: &str) -> Vec<u8> {
+    let binary = format!("{path}.text");
+    common::run_tool_ok::<&str>(
+        "objcopy",
+        &["-O", "binary", "-j", ".text", path, &binary],
+        None,
+    );
+    fs::read(&binary).expect("objcopy wrote the section")
+}
+
+#[test]
+fn encodes_like_gas() {
+    if !common::has_tool("gcc") || !common::has_tool("objcopy") {
+        return;
+    }
+    let directory = common::temp_dir("encodes_like_gas");
+    for program in common::programs() {
+        for level in 0..=2 {
+            let ours = directory.join(format!("{}-O{level}.o", program.name));
+            let ours = ours.to_string_lossy();
+            fs::write(&*ours, common::object(&program, Options::for_level(level)))
+                .expect("the object can be written");
+            let gas = directory.join(format!("{}-O{level}-gas.o", program.name));
+            let gas = gas.to_string_lossy();
+            let assembly = common::assembly(&program, Options::for_level(level));
+            common::run_tool_ok::<&str>(
+                "gcc",
+                &["-c", "-x", "assembler", "-", "-o", &gas],
+                Some(&assembly),
+            );
+            assert!(
+                text_section(&ours) == text_section(&gas),
+                "{} at -O{level} is encoded differently from gas",
+                program.name
+            );
+        }
+    }
+    let _ = fs::remove_dir_all(&directory);
+}
+
+#[test]
+fn objects_link_and_run() {
+    if !common::has_tool("gcc") {
+        return;
+    }
+    let directory = common::temp_dir("objects_link_and_run");
+    for program in common::programs() {
+        for level in 0..=2 {
+            let object = directory.join(format!("{}-O{level}.o", program.name));
+            let object = object.to_string_lossy();
+            let executable = directory.join(format!("{}-O{level}", program.name));
+            let executable = executable.to_string_lossy();
+            fs::write(
+                &*object,
+                common::object(&program, Options::for_level(level)),
+            )
+            .expect("the object can be written");
+            common::run_tool_ok::<&str>("gcc", &[&object, "-o", &executable], None);
+            let output = common::run_tool::<&str>(&executable, &[], None);
+            assert_eq!(
+                output.status.code(),
+                Some(program.expected as u8 as i32),
+                "{} at -O{level}",
+                program.name
+            );
+        }
+    }
+    let _ = fs::remove_dir_all(&directory);
+}
+
+#[test]
+fn runs_in_memory() {
+    for program in common::programs() {
+        for level in 0..=2 {
+            assert_eq!(
+                common::jit(&program, Options::for_level(level)),
+                program.expected,
+                "{} at -O{level}",
+                program.name
+            );
+        }
+    }
+}
@@ -0,0 +1,124 @@
+mod common;
+
+use std::collections::HashSet;
+
+use common::Program;
+use comp::{
+    irc, irc_interpreter,
+    pass_manager::IrcPass,
+    session::{Options, Session, Stage},
+    ssa::SsaFunction,
+};
+
+fn with_passes(irc_passes: Vec<IrcPass>) -> Options {
+    Options {
+        irc_passes,
+        ..Options::default()
+    }
+}
+
+/// Runs the passes of `options` and interprets the irc.
+fn interpret(program: &Program, options: Options) -> i32 {
+    let session = Session::new(program.source.as_str(), options);
+    let output = session.compile(Stage::Irc);
+    common::check(&output, &program.name);
+    irc_interpreter::run(output.irc.as_ref().expect("the irc stage gives irc"))
+        .unwrap_or_else(|e| panic!("{} failed in the interpreter: {e}", program.name))
+}
+
+fn function<'a>(session: &'a Session, name: &str) -> irc::Function<'a> {
+    let output = session.compile(Stage::Irc);
+    common::check(&output, name);
+    match output.irc.expect("the irc stage gives irc") {
+        irc::Program::Function(function) => function,
+    }
+}
+
+#[test]
+fn ssa_round_trip_preserves_semantics() {
+    for program in common::programs() {
+        let mut optimized = IrcPass::for_level(2);
+        optimized.push(IrcPass::SsaRoundTrip);
+        for passes in [vec![IrcPass::SsaRoundTrip], optimized] {
+            let name = &program.name;
+            assert_eq!(
+                interpret(&program, with_passes(passes.clone())),
+                program.expected,
+                "{name} with {passes:?}"
+            );
+            assert_eq!(
+                common::jit(&program, with_passes(passes.clone())),
+                program.expected,
+                "{name} with {passes:?}"
+            );
+        }
+    }
+}
+
+#[test]
+fn ssa_form_assigns_each_variable_once() {
+    for program in common::programs() {
+        let session = Session::new(program.source.as_str(), Options::default());
+        let ssa = SsaFunction::new(function(&session, &program.name));
+        let mut assigned = HashSet::new();
+        for (block, phis) in ssa.cfg.blocks.iter().zip(&ssa.phis) {
+            for phi in phis {
+                assert_eq!(
+                    phi.args.len(),
+                    block.predecessors.len(),
+                    "{}: {phi:?}",
+                    program.name
+                );
+                assert!(
+                    assigned.insert(phi.dst.clone()),
+                    "{}: {}",
+                    program.name,
+                    phi.dst
+                );
+            }
+            for dst in block.instructions.iter().filter_map(|ins| ins.dst()) {
+                assert!(assigned.insert(dst.to_string()), "{}: {dst}", program.name);
+            }
+        }
+    }
+}
+
+#[test]
+fn each_pass_preserves_semantics() {
+    for program in common::programs() {
+        for pass in IrcPass::ALL {
+            let name = &program.name;
+            assert_eq!(
+                interpret(&program, with_passes(vec![pass])),
+                program.expected,
+                "{name} with {}",
+                pass.name()
+            );
+            assert_eq!(
+                common::jit(&program, with_passes(vec![pass])),
+                program.expected,
+                "{name} with {}",
+                pass.name()
+            );
+        }
+    }
+}
+
+#[test]
+fn optimization_levels_preserve_semantics() {
+    for program in common::programs() {
+        for level in 0..=2 {
+            let name = &program.name;
+            assert_eq!(
+                interpret(&program, Options::for_level(level)),
+                program.expected,
+                "{name} at -O{level}"
+            );
+            assert_eq!(
+                common::jit(&program, Options::for_level(level)),
+                program.expected,
+                "{name} at -O{level}"
+            );
+        }
+    }
+}
@@ -0,0 +1,225 @@
+mod common;
+
+use comp::{
+    asm_ast::{BinaryOp, CondCode, Function, Instruction, Operand, Program, Register},
+    pass_manager::{AsmPass, IrcPass},
+    peephole,
+    session::Options,
+};
+
+fn optimize(instructions: Vec<Instruction>) -> Vec<Instruction> {
+    let mut program = Program::Function(Function {
+        name: "main",
+        instructons: instructions,
+    });
+    peephole::optimize(&mut program);
+    match program {
+        Program::Function(function) => function.instructons,
+    }
+}
+
+fn mov(src: Operand, dst: Operand) -> Instruction {
+    Instruction::Mov { src, dst }
+}
+
+fn reg(register: Register) -> Operand {
+    Operand::Register(register)
+}
+
+#[test]
+fn removes_moves_back() {
+    assert_eq!(
+        optimize(vec![
+            mov(reg(Register::AX), Operand::Stack(8)),
+            mov(Operand::Stack(8), reg(Register::AX)),
+        ]),
+        [mov(reg(Register::AX), Operand::Stack(8))]
+    );
+}
+
+#[test]
+fn zeroes_with_xor_before_setcc() {
+    let compare = Instruction::Cmp {
+        operand1: Operand::Imm(3),
+        operand2: reg(Register::CX),
+    };
+    let set = Instruction::SetCC {
+        cond_code: CondCode::L,
+        operand: reg(Register::AX),
+    };
+    assert_eq!(
+        optimize(vec![
+            compare.clone(),
+            mov(Operand::Imm(0), reg(Register::AX)),
+            set.clone(),
+        ]),
+        [
+            Instruction::Binary {
+                operator: BinaryOp::Xor,
+                operand1: reg(Register::AX),
+                operand2: reg(Register::AX),
+            },
+            compare,
+            set,
+        ]
+    );
+}
+
+#[test]
+fn keeps_zeroing_of_a_compared_register() {
+    let instructions = vec![
+        Instruction::Cmp {
+            operand1: Operand::Imm(3),
+            operand2: reg(Register::AX),
+        },
+        mov(Operand::Imm(0), reg(Register::AX)),
+        Instruction::SetCC {
+            cond_code: CondCode::L,
+            operand: reg(Register::AX),
+        },
+    ];
+    assert_eq!(optimize(instructions.clone()), instructions);
+}
+
+#[test]
+fn keeps_annotations() {
+    assert_eq!(
+        optimize(vec![
+            mov(reg(Register::AX), Operand::Stack(8)),
+            Instruction::Line(3),
+            mov(Operand::Stack(8), reg(Register::AX)),
+            Instruction::Return,
+        ]),
+        [
+            Instruction::Line(3),
+            mov(reg(Register::AX), Operand::Stack(8)),
+            Instruction::Return
+        ]
+    );
+}
+
+#[test]
+fn preserves_semantics() {
+    for program in common::programs() {
+        for level in 0..=2 {
+            for asm_passes in [
+                vec![AsmPass::Peephole],
+                vec![AsmPass::RegisterAllocation, AsmPass::Peephole],
+            ] {
+                let options = Options {
+                    irc_passes: IrcPass::for_level(level),
+                    asm_passes: asm_passes.clone(),
+                    ..Options::default()
+                };
+                assert_eq!(
+                    common::jit(&program, options),
+                    program.expected,
+                    "{} at -O{level} with {asm_passes:?}",
+                    program.name
+                );
+            }
+        }
+    }
+}
+
+/// The instructions of AT&T assembly, as the mnemonic and the operands.
+/// Labels are kept, as the rules don't look across them.
+fn instructions(assembly: &str) -> Vec<(&str, Vec<&str>)> {
+    assembly
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && (line.ends_with(':') || !line.starts_with('.')))
+        .map(|line| {
+            let (mnemonic, operands) = line.split_once(char::is_whitespace).unwrap_or((line, ""));
+            // Commas inside parentheses separate the parts of an address.
+            let mut split = Vec::new();
+            let (mut depth, mut start) = (0, 0);
+            for (index, c) in operands.char_indices() {
+                match c {
+                    '(' => depth += 1,
+                    ')' => depth -= 1,
+                    ',' if depth == 0 => {
+                        split.push(operands[start..index].trim());
+                        start = index + 1;
+                    }
+                    _ => {}
+                }
+            }
+            if !operands.trim().is_empty() {
+                split.push(operands[start..].trim());
+            }
+            (mnemonic, split)
+        })
+        .collect()
+}
+
+/// How many self moves, moves straight back, zeroings before a `setcc` and
+/// comparisons of a register with zero show up in the assembly.
+fn patterns(assembly: &str) -> [usize; 4] {
+    let instructions = instructions(assembly);
+    let mut counts = [0; 4];
+    for (index, (mnemonic, operands)) in instructions.iter().enumerate() {
+        let next = instructions.get(index + 1);
+        let is_move = mnemonic.starts_with("mov") && operands.len() == 2;
+        if is_move && operands[0] == operands[1] {
+            counts[0] += 1;
+        }
+        if let Some((next_mnemonic, next_operands)) = next {
+            if is_move
+                && next_mnemonic == mnemonic
+                && next_operands.len() == 2
+                && operands[0] == next_operands[1]
+                && operands[1] == next_operands[0]
+            {
+                counts[1] += 1;
+            }
+        }
+        let is_compare = mnemonic.starts_with("cmp");
+        if is_compare {
+            if let (Some((zero, zeroed)), Some((set, _))) = (next, instructions.get(index + 2)) {
+                let is_register = zeroed.get(1).is_some_and(|dst| dst.starts_with('%'));
+                if zero.starts_with("mov")
+                    && zeroed.first() == Some(&"$0")
+                    && is_register
+                    && !operands.contains(&zeroed[1])
+                    && set.starts_with("set")
+                {
+                    counts[2] += 1;
+                }
+            }
+            if operands.first() == Some(&"$0") && operands[1].starts_with('%') {
+                counts[3] += 1;
+            }
+        }
+    }
+    counts
+}
+
+#[test]
+fn leaves_no_pattern_in_the_emitted_code() {
+    let mut before = [0; 4];
+    for program in common::programs() {
+        for level in 0..=2 {
+            let mut options = Options::for_level(level);
+            options.asm_passes.retain(|&pass| pass != AsmPass::Peephole);
+            let unoptimized = patterns(&common::assembly(&program, options.clone()));
+            for (total, count) in before.iter_mut().zip(unoptimized) {
+                *total += count;
+            }
+            options.asm_passes.push(AsmPass::Peephole);
+            assert_eq!(
+                patterns(&common::assembly(&program, options)),
+                [0; 4],
+                "{} at -O{level}",
+                program.name
+            );
+        }
+    }
+    // The allocator drops self moves and `gen_compare` tests against zero,
+    // the rules are for the other two, which would be dead if they didn't
+    // show up.
+    assert!(
+        before[0] == 0 && before[1] > 0 && before[2] > 0 && before[3] == 0,
+        "{before:?}"
+    );
+}
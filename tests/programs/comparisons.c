@@ -0,0 +1,14 @@
+// expect: 169
+int main(void) {
+    int i = 0;
+    int less = 0;
+    int equal = 0;
+    int greater = 0;
+loop:
+    less = less + (i < 4);
+    equal = equal + (i == 4);
+    greater = greater + (i > 4) * 3;
+    i = i + 1;
+    if (i < 10) goto loop;
+    return (less * 100 + equal * 10 + greater) % 256;
+}
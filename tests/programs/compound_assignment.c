@@ -0,0 +1,14 @@
+// expect: 68
+int main(void) {
+    int x = 1;
+    int y = x++;
+    int z = ++x;
+    int w = x--;
+    int v = --x;
+    x *= 3; x -= 1; x /= 2; x %= 5; x &= 7; x |= 8; x ^= 3; x <<= 2; x >>= 1;
+    {
+        int x = 40;
+        y = y + x;
+    }
+    return x + y + z + w + v;
+}
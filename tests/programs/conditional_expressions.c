@@ -0,0 +1,17 @@
+// expect: 283
+int main(void) {
+    int a = 5;
+    int b = 9;
+    int c = 0;
+    int r = 0;
+    r = r + (a < b ? a : b);
+    r = r + (a > b ? 3 : 7);
+    r = r + (c ? a + 1 : b * 2);
+    r = r + (!c ? 100 : 200);
+    r = r + (a == 5 ? b - a : a << 2);
+    int d = a >= 5 ? (b > 8 ? 1 : 2) : 3;
+    int e = c ? a / c : 4;
+    int f = b ? r : -r;
+    int g = (a > 1 && b > 1) ? 10 : 20;
+    return r + d + e + f + g;
+}
@@ -0,0 +1,4 @@
+// expect: 107
+int main(void) {
+    return 2 + 3 * 4 - (10 / 3) + (17 % 5) + (1 << 4) + (256 >> 2) + (6 & 3) + (6 | 1) + (6 ^ 3);
+}
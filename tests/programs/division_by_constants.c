@@ -0,0 +1,16 @@
+// expect: 134
+int main(void) {
+    int a = 100;
+    int b = a / 16;
+    int c = a % 16;
+    int d = a * 9;
+    int e = a * 5;
+    int f = a / 10;
+    int g = a % 10;
+    int h = -a / 16;
+    int i = -a % 16;
+    int j = -a / 10;
+    int k = -a % 10;
+    int l = -a / -7;
+    return (b + c + d + e + f + g + h + i + j + k + l) % 256;
+}
@@ -0,0 +1,8 @@
+// expect: 3
+int main(void) {
+  int x = 0;
+entry:
+  x = x + 1;
+  if (x < 3) goto entry;
+  return x;
+}
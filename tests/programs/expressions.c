@@ -0,0 +1,9 @@
+// expect: -42
+int main(void) {
+    int a = 5;
+    int b = a * 3 + 2;
+    if (a < b) a = a + 10; else b = 2;
+    int c = a > 3 ? b : a;
+    c += 4 << 2;
+    return (c % 7) + (a && b) + (a || 0) + !b + -a + ~c;
+}
@@ -0,0 +1,15 @@
+// expect: 239
+int main(void) {
+    int a = 0;
+    int b = 1;
+    int i = 0;
+fib:
+    if (i >= 20) goto done;
+    int t = a + b;
+    a = b;
+    b = t;
+    i = i + 1;
+    goto fib;
+done:
+    return a % 251;
+}
@@ -0,0 +1,12 @@
+// expect: 3
+int main(void) {
+    int a = 2147483647;
+    int b = a + 0;
+    int c = b * 1;
+    int d = b * 0;
+    int e = b & 0;
+    int f = 0 + c;
+    if (0) return 99;
+    if (1) f = f - 2147483647;
+    return f + d + e + (1 ? 3 : 4);
+}
@@ -0,0 +1,16 @@
+// expect: 60
+int main(void) {
+    int n = 0;
+    int k = 3;
+    if (k > 2) goto b;
+a:
+    n = n + 3;
+    if (n > 40) goto end;
+b:
+    n = n * 2 + 1;
+    if (n < 30) goto a;
+    k = k - 1;
+    if (k) goto a;
+end:
+    return n;
+}
@@ -0,0 +1,13 @@
+// expect: 34
+int main(void) {
+    int a = 3;
+    int b = 0;
+    int c = 0;
+    if (a > 2 && b == 0) c = 1;
+    if (a < 2 || b != 0) c = c + 10;
+    if (!(a == 3)) c = c + 100;
+    if (a >= 3 && (b <= 0 || a == 9)) c = c + 20;
+    int d = (a > b) ? a + 1 : b - 1;
+    int e = (a == b) ? 7 : 9;
+    return c + d + e;
+}
@@ -0,0 +1,12 @@
+// expect: 29
+int main(void) {
+    int i = 0;
+    int sum = 0;
+loop:
+    if (i < 10) {
+        sum = sum + i * i;
+        i++;
+        goto loop;
+    }
+    return sum % 256;
+}
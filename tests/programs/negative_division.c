@@ -0,0 +1,14 @@
+// expect: 87
+int main(void) {
+    int x = -7;
+    int y = 2;
+    int q = x / y;
+    int r = x % y;
+    int s = x >> 1;
+    int t = x / 4;
+    int u = x % 8;
+    int v = x * 8;
+    int w = x / 7;
+    int z = x % 3;
+    return (q + 10) * 1 + (r + 3) + (s + 9) + (t + 20) + (u + 30) + (v + 80) + w + 5 + z + 4;
+}
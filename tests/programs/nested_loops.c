@@ -0,0 +1,16 @@
+// expect: 105
+int main(void) {
+    int x = 10;
+    int y = 0;
+    int k = 1;
+    int i = 0;
+top:
+    y = y + x * k;
+    k = k + 1;
+    if (k < 5) goto top;
+    int n = 0;
+    int m = 100;
+again:
+    if (m > 0) { m = m / 3; n = n + 1; goto again; }
+    return y + n + i;
+}
@@ -0,0 +1,71 @@
+// expect: 67
+int main(void) {
+    int v0 = 3;
+    int v1 = 10;
+    int v2 = 17;
+    int v3 = 24;
+    int v4 = 31;
+    int v5 = 38;
+    int v6 = 45;
+    int v7 = 52;
+    int v8 = 59;
+    int v9 = 66;
+    int v10 = 73;
+    int v11 = 80;
+    int v12 = 87;
+    int v13 = 94;
+    int v14 = 101;
+    int v15 = 108;
+    int v16 = 115;
+    int v17 = 122;
+    int v18 = 129;
+    int v19 = 136;
+    int k = 0;
+loop:
+    if (k < 3) {
+        v0 = v0 * 3 + v1 - k;
+        v1 = v1 * 3 + v2 - k;
+        v2 = v2 * 3 + v3 - k;
+        v3 = v3 * 3 + v4 - k;
+        v4 = v4 * 3 + v5 - k;
+        v5 = v5 * 3 + v6 - k;
+        v6 = v6 * 3 + v7 - k;
+        v7 = v7 * 3 + v8 - k;
+        v8 = v8 * 3 + v9 - k;
+        v9 = v9 * 3 + v10 - k;
+        v10 = v10 * 3 + v11 - k;
+        v11 = v11 * 3 + v12 - k;
+        v12 = v12 * 3 + v13 - k;
+        v13 = v13 * 3 + v14 - k;
+        v14 = v14 * 3 + v15 - k;
+        v15 = v15 * 3 + v16 - k;
+        v16 = v16 * 3 + v17 - k;
+        v17 = v17 * 3 + v18 - k;
+        v18 = v18 * 3 + v19 - k;
+        v19 = v19 * 3 + v0 - k;
+        k = k + 1;
+        goto loop;
+    }
+    int s = 0;
+    s = s ^ (v0 + 0);
+    s = s ^ (v1 + 1);
+    s = s ^ (v2 + 2);
+    s = s ^ (v3 + 3);
+    s = s ^ (v4 + 4);
+    s = s ^ (v5 + 5);
+    s = s ^ (v6 + 6);
+    s = s ^ (v7 + 7);
+    s = s ^ (v8 + 8);
+    s = s ^ (v9 + 9);
+    s = s ^ (v10 + 10);
+    s = s ^ (v11 + 11);
+    s = s ^ (v12 + 12);
+    s = s ^ (v13 + 13);
+    s = s ^ (v14 + 14);
+    s = s ^ (v15 + 15);
+    s = s ^ (v16 + 16);
+    s = s ^ (v17 + 17);
+    s = s ^ (v18 + 18);
+    s = s ^ (v19 + 19);
+    return (s / 7 + (s % 13) + (s << 2) + (s >> 3)) & 255;
+}
@@ -0,0 +1,19 @@
+// expect: 314
+int main(void) {
+    int a = 1;
+    int b = 2;
+    int c = 3;
+    int i = 0;
+loop:
+    if (i < 5) {
+        int t = a;
+        a = b;
+        b = c;
+        c = t;
+        i = i + 1;
+        goto loop;
+    }
+    int x = 0;
+    if (a > b) x = a - b; else x = b - a;
+    return a * 100 + b * 10 + c + x;
+}
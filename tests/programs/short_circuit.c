@@ -0,0 +1,17 @@
+// expect: 236
+int main(void) {
+    int a = 5;
+    int b = 9;
+    int c = 0;
+    int r = 0;
+    if (a < b && b < 10) r = r + 1;
+    if (a > b || !(c == 0)) r = r + 2;
+    if (!(a < b) || (c && a)) r = r + 4; else r = r + 8;
+    if (!a) r = r + 16;
+    if (!!b) r = r + 32;
+    int x = (a >= 5 && (b <= 8 || c != 1)) ? 64 : 0;
+    r = r + x;
+    int y = (a && b) + (c || a) + !c;
+    if ((a == 5 || b == 1) && !(c > 0 && a)) r = r + 128;
+    return r + y;
+}
@@ -0,0 +1,78 @@
+mod common;
+
+use comp::{riscv64::Riscv64, session::Options};
+
+fn options(level: u8) -> Options {
+    Options {
+        target: &Riscv64,
+        ..Options::for_level(level)
+    }
+}
+
+#[test]
+fn matches_golden_output() {
+    for program in common::programs() {
+        common::check_golden(
+            &format!("riscv64/{}.s", program.name),
+            &common::assembly(&program, options(2)),
+        );
+    }
+}
+
+#[test]
+fn assembles_with_llvm_mc() {
+    if !common::has_tool("llvm-mc") {
+        return;
+    }
+    for program in common::programs() {
+        for level in 0..=2 {
+            common::run_tool_ok(
+                "llvm-mc",
+                &[
+                    "-triple=riscv64-linux-gnu",
+                    "-mattr=+m",
+                    "-filetype=obj",
+                    "--fatal-warnings",
+                    "-o",
+                    "/dev/null",
+                ],
+                Some(&common::assembly(&program, options(level))),
+            );
+        }
+    }
+}
+
+/// A program whose `if` jumps over `statements` additions.
+fn long_jump(statements: usize) -> common::Program {
+    let body = "        x = x + 1;\n".repeat(statements);
+    common::Program {
+        name: format!("long_jump_{statements}"),
+        source: format!(
+            "int main(void) {{\n    int a = 0;\n    int x = 0;\n    if (a) {{\n{body}    }}\n    return x;\n}}\n"
+        ),
+        expected: 0,
+    }
+}
+
+#[test]
+fn relaxes_long_branches() {
+    if !common::has_tool("llvm-mc") {
+        return;
+    }
+    // Beyond the reach of a branch, then beyond the reach of a jump.
+    for statements in [1000, 60000] {
+        let program = long_jump(statements);
+        common::run_tool_ok(
+            "llvm-mc",
+            &[
+                "-triple=riscv64-linux-gnu",
+                "-mattr=+m",
+                "-filetype=obj",
+                "--fatal-warnings",
+                "-o",
+                "/dev/null",
+            ],
+            Some(&common::assembly(&program, options(0))),
+        );
+    }
+}
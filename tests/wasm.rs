@@ -0,0 +1,97 @@
+mod common;
+
+use std::fs;
+
+use comp::{session::Options, wasm::Wasm32};
+
+fn options(level: u8) -> Options {
+    Options {
+        target: &Wasm32,
+        ..Options::for_level(level)
+    }
+}
+
+/// Instantiates the module and exits with what its `main` returns.
+const RUN: &str = "
+const fs = require('fs');
+WebAssembly.instantiate(fs.readFileSync(process.argv[1])).then(
+    ({ instance }) => process.exit(instance.exports.main() & 255),
+    (error) => { console.error(String(error)); process.exit(200); },
+);
+";
+
+#[test]
+fn matches_golden_output() {
+    for program in common::programs() {
+        common::check_golden(
+            &format!("wasm32/{}.wat", program.name),
+            &common::assembly(&program, options(2)),
+        );
+    }
+}
+
+#[test]
+fn dispatches_only_irreducible_loops() {
+    for program in common::programs() {
+        // The passes at -O2 fold irreducible.c down to a constant.
+        for level in 0..=1 {
+            let wat = common::assembly(&program, options(level));
+            assert_eq!(
+                wat.contains("br_table"),
+                program.name == "irreducible",
+                "{} at -O{level}:\n{wat}",
+                program.name
+            );
+        }
+    }
+}
+
+#[test]
+fn validates_with_wasm_tools() {
+    if !common::has_tool("wasm-tools") {
+        return;
+    }
+    let directory = common::temp_dir("wasm-tools");
+    for program in common::programs() {
+        for level in 0..=2 {
+            let path = directory.join(format!("{}-{level}.wat", program.name));
+            fs::write(&path, common::assembly(&program, options(level))).unwrap();
+            common::run_tool_ok("wasm-tools", &["validate".as_ref(), path.as_os_str()], None);
+        }
+    }
+}
+
+#[test]
+fn assembles_with_wat2wasm_and_runs_in_node() {
+    if !common::has_tool("wat2wasm") {
+        return;
+    }
+    let node = common::has_tool("node");
+    let directory = common::temp_dir("wat2wasm");
+    for program in common::programs() {
+        for level in 0..=2 {
+            let wat = directory.join(format!("{}-{level}.wat", program.name));
+            let wasm = wat.with_extension("wasm");
+            fs::write(&wat, common::assembly(&program, options(level))).unwrap();
+            common::run_tool_ok(
+                "wat2wasm",
+                &[wat.as_os_str(), "-o".as_ref(), wasm.as_os_str()],
+                None,
+            );
+            if node {
+                let output = common::run_tool(
+                    "node",
+                    &["-e".as_ref(), RUN.as_ref(), wasm.as_os_str()],
+                    None,
+                );
+                assert_eq!(
+                    output.status.code(),
+                    Some(program.expected as u8 as i32),
+                    "{} at -O{level}:\n{}",
+                    program.name,
+                    String::from_utf8_lossy(&output.stderr)
+                );
+            }
+        }
+    }
+}